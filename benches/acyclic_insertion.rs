@@ -0,0 +1,134 @@
+//! Compares inserting a long sequence of edges with `AcyclicGraph::insert_edge_acyclic` to the
+//! naive approach, which inserts each edge into a `FiniteGraph` and searches the whole graph
+//! for a cycle with a depth-first search afterwards, removing the edge again if there is one.
+//! The naive time should roughly quadruple whenever the size doubles. Run with `cargo bench`.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use graph_iter::{AcyclicGraph, FiniteGraph, Graph, Id};
+
+/// Returns `true` if `graph` has a cycle, using an iterative depth-first search from every
+/// vertex in `O(V + E)` time.
+fn has_cycle(graph: &FiniteGraph<(), ()>) -> bool {
+  // A vertex is on the stack while it maps to `true` and finished once it maps to `false`
+  let mut on_stack_map = HashMap::new();
+
+  for (root, _) in graph.all_vertices() {
+    if on_stack_map.contains_key(&root) {
+      continue;
+    }
+
+    on_stack_map.insert(root, true);
+
+    let mut stack = vec![(root, graph.neighbors(&root).into_iter())];
+
+    while let Some((vertex, neighbors)) = stack.last_mut() {
+      match neighbors.next() {
+        Some(neighbor) => match on_stack_map.get(&neighbor) {
+          Some(true) => return true,
+          Some(false) => {},
+          None => {
+            on_stack_map.insert(neighbor, true);
+            stack.push((neighbor, graph.neighbors(&neighbor).into_iter()));
+          }
+        },
+        None => {
+          on_stack_map.insert(*vertex, false);
+          stack.pop();
+        }
+      }
+    }
+  }
+
+  false
+}
+
+/// Returns `num_edges` pseudo-random edges between `num_vertices` vertices, where most edges
+/// go forward in a hidden order and some go backward, so they may close a cycle.
+fn insertion_sequence(num_vertices: usize, num_edges: usize) -> Vec<(usize, usize)> {
+  let mut state = 7u64;
+  let mut random = || {
+    state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    (state >> 33) as usize
+  };
+
+  (0..num_edges)
+  .map(|_| {
+    let (from, to) = (random() % num_vertices, random() % num_vertices);
+
+    if random() % 10 == 0 { (from.max(to), from.min(to)) } else { (from.min(to), from.max(to)) }
+  })
+  .collect()
+}
+
+fn insert_acyclic(num_vertices: usize, edges: &[(usize, usize)]) -> usize {
+  let mut graph = AcyclicGraph::new(FiniteGraph::new()).unwrap();
+  let v = (0..num_vertices).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+  edges.iter()
+  .filter(|&&(from, to)| graph.insert_edge_acyclic(v[from], v[to], ()).is_ok())
+  .count()
+}
+
+fn insert_naive(num_vertices: usize, edges: &[(usize, usize)]) -> usize {
+  let mut graph = FiniteGraph::new();
+  let v = (0..num_vertices).map(|_| graph.insert_vertex(())).collect::<Vec<Id>>();
+
+  edges.iter()
+  .filter(|&&(from, to)| {
+    let edge = graph.insert_edge(v[from], v[to], ()).unwrap();
+
+    if has_cycle(&graph) {
+      graph.remove_edge(edge);
+      false
+    } else {
+      true
+    }
+  })
+  .count()
+}
+
+/// Returns the fastest of `runs` runs of `f`.
+fn measure(runs: usize, mut f: impl FnMut() -> usize) -> Duration {
+  (0..runs)
+  .map(|_| {
+    let start = Instant::now();
+
+    assert!(f() > 0);
+    start.elapsed()
+  })
+  .min()
+  .unwrap()
+}
+
+fn main() {
+  let mut previous = None::<(Duration, Duration)>;
+
+  for &num_vertices in &[500, 1000, 2000, 4000] {
+    let edges = insertion_sequence(num_vertices, 2 * num_vertices);
+
+    assert_eq!(insert_acyclic(num_vertices, &edges), insert_naive(num_vertices, &edges));
+
+    let acyclic = measure(10, || insert_acyclic(num_vertices, &edges));
+    let naive = measure(3, || insert_naive(num_vertices, &edges));
+
+    print!(
+      "{:>4} vertices, {:>4} edges: insert_edge_acyclic {:>10.2?}, naive {:>10.2?}",
+      num_vertices,
+      edges.len(),
+      acyclic,
+      naive
+    );
+
+    if let Some((previous_acyclic, previous_naive)) = previous {
+      print!(
+        " (x{:.1} and x{:.1} from the previous size)",
+        acyclic.as_secs_f64() / previous_acyclic.as_secs_f64(),
+        naive.as_secs_f64() / previous_naive.as_secs_f64()
+      );
+    }
+
+    println!();
+    previous = Some((acyclic, naive));
+  }
+}
@@ -0,0 +1,98 @@
+//! Compares `FiniteGraph::articulation_points_fast` with the naive approach, which checks for
+//! every vertex whether its neighbors stay connected without it, on chains of triangles of
+//! growing size. The naive time should roughly quadruple whenever the size doubles, while the
+//! fast time only doubles. Run with `cargo bench`.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use graph_iter::{FiniteGraph, Graph, Id, ReversibleGraph};
+
+/// Returns the neighbors of `vertex` ignoring edge directions.
+fn undirected_neighbors(graph: &FiniteGraph<(), ()>, vertex: Id) -> Vec<Id> {
+  let mut neighbors = graph.neighbors(&vertex);
+
+  neighbors.extend(graph.reverse_neighbors(&vertex));
+  neighbors
+}
+
+/// Returns the articulation points in `O(V * (V + E))` time.
+fn naive_articulation_points(graph: &FiniteGraph<(), ()>) -> Vec<Id> {
+  graph.all_vertices_ordered()
+  .map(|(vertex, _)| vertex)
+  .filter(|&vertex| {
+    let neighbors = undirected_neighbors(graph, vertex).into_iter()
+      .filter(|&neighbor| neighbor != vertex)
+      .collect::<HashSet<_>>();
+    let start = match neighbors.iter().next() {
+      Some(&start) => start,
+      None => return false
+    };
+    let mut visited = vec![vertex, start].into_iter().collect::<HashSet<_>>();
+    let mut stack = vec![start];
+
+    while let Some(current) = stack.pop() {
+      for neighbor in undirected_neighbors(graph, current) {
+        if visited.insert(neighbor) {
+          stack.push(neighbor);
+        }
+      }
+    }
+
+    !neighbors.is_subset(&visited)
+  })
+  .collect()
+}
+
+/// Returns a chain of `n / 2` triangles joined at their corners, where every inner joint is an
+/// articulation point.
+fn triangle_chain(n: usize) -> FiniteGraph<(), ()> {
+  let mut graph = FiniteGraph::new();
+  let v = (0..=n).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+  for i in (0..n).step_by(2) {
+    graph.insert_edge(v[i], v[i + 1], ()).unwrap();
+    graph.insert_edge(v[i + 1], v[i + 2], ()).unwrap();
+    graph.insert_edge(v[i], v[i + 2], ()).unwrap();
+  }
+
+  graph
+}
+
+/// Returns the fastest of `runs` runs of `f`.
+fn measure(runs: usize, mut f: impl FnMut() -> usize) -> Duration {
+  (0..runs)
+  .map(|_| {
+    let start = Instant::now();
+
+    assert!(f() > 0);
+    start.elapsed()
+  })
+  .min()
+  .unwrap()
+}
+
+fn main() {
+  let mut previous = None::<(Duration, Duration)>;
+
+  for &n in &[500, 1000, 2000, 4000] {
+    let graph = triangle_chain(n);
+
+    assert_eq!(graph.articulation_points_fast(), naive_articulation_points(&graph));
+
+    let fast = measure(10, || graph.articulation_points_fast().len());
+    let naive = measure(3, || naive_articulation_points(&graph).len());
+
+    print!("n = {:>4}: articulation_points_fast {:>10.2?}, naive {:>10.2?}", n, fast, naive);
+
+    if let Some((previous_fast, previous_naive)) = previous {
+      print!(
+        " (x{:.1} and x{:.1} from the previous size)",
+        fast.as_secs_f64() / previous_fast.as_secs_f64(),
+        naive.as_secs_f64() / previous_naive.as_secs_f64()
+      );
+    }
+
+    println!();
+    previous = Some((fast, naive));
+  }
+}
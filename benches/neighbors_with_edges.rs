@@ -0,0 +1,91 @@
+//! Compares Dijkstra on high-degree vertices expanded through `FiniteGraph`'s own
+//! `neighbors_with_edges` with the default implementation, which calls `edges` once per
+//! neighbor, on hubs of growing degree. The default does quadratic work per hub, so its time
+//! grows faster than the overridden one, which is linear in the degree, and the speedup grows
+//! with the degree. Run with `cargo bench`.
+
+use std::time::{Duration, Instant};
+use graph_iter::{EdgedGraph, FiniteGraph, Graph, Id};
+use graph_iter::vertex_traverser::VertexTraverser;
+
+/// Forwards to a `FiniteGraph` without overriding `neighbors_with_edges`.
+struct DefaultExpansion<'a>(&'a FiniteGraph<(), u32>);
+
+impl<'a> Graph<Id> for DefaultExpansion<'a> {
+  type NeighborsIterator = Vec<Id>;
+
+  fn neighbors(&self, vertex: &Id) -> Vec<Id> {
+    self.0.neighbors(vertex)
+  }
+}
+
+impl<'a> EdgedGraph<Id, u32> for DefaultExpansion<'a> {
+  type EdgesIterator = Vec<u32>;
+
+  fn edges(&self, vertex: &Id, other: &Id) -> Vec<u32> {
+    self.0.edges(vertex, other)
+  }
+}
+
+/// Returns the fastest of `runs` runs of `f`.
+fn measure(runs: usize, mut f: impl FnMut() -> usize) -> Duration {
+  (0..runs)
+  .map(|_| {
+    let start = Instant::now();
+
+    assert!(f() > 0);
+    start.elapsed()
+  })
+  .min()
+  .unwrap()
+}
+
+/// Returns a few hubs connected to each other in a cycle and to `degree` leaves each by two
+/// parallel edges, together with the first hub.
+fn hub_graph(degree: u32) -> (FiniteGraph<(), u32>, Id) {
+  let mut graph = FiniteGraph::new();
+  let hubs = (0..10).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+  for (i, &hub) in hubs.iter().enumerate() {
+    graph.insert_bi_edge(hub, hubs[(i + 1) % hubs.len()], 1).unwrap();
+
+    for j in 0..degree {
+      let leaf = graph.insert_vertex(());
+
+      graph.insert_edge(hub, leaf, j % 7 + 1).unwrap();
+      graph.insert_edge(hub, leaf, j % 5 + 1).unwrap();
+    }
+  }
+
+  (graph, hubs[0])
+}
+
+fn main() {
+  let mut previous = None::<(Duration, Duration)>;
+
+  for &degree in &[500, 1000, 2000, 4000, 8000] {
+    let (graph, start) = hub_graph(degree);
+    let default_expansion = DefaultExpansion(&graph);
+    let overridden = measure(10, || graph.dijkstra(&start).iter().count());
+    let default = measure(3, || default_expansion.dijkstra(&start).iter().count());
+
+    print!(
+      "degree {:>4}: overridden {:>10.2?}, default {:>10.2?}, speedup x{:.1}",
+      degree,
+      overridden,
+      default,
+      default.as_secs_f64() / overridden.as_secs_f64()
+    );
+
+    if let Some((previous_overridden, previous_default)) = previous {
+      print!(
+        " (x{:.1} and x{:.1} from the previous degree)",
+        overridden.as_secs_f64() / previous_overridden.as_secs_f64(),
+        default.as_secs_f64() / previous_default.as_secs_f64()
+      );
+    }
+
+    println!();
+    previous = Some((overridden, default));
+  }
+}
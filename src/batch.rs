@@ -0,0 +1,136 @@
+use crate::*;
+use std::collections::HashMap;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use graph::EdgedGraph;
+use vertex::Vertex;
+use edge::WeightedEdge;
+use vertex_traverser::VertexTraverser;
+
+/// The result of a single shortest path query.
+type QueryResult<V, E> = Option<(Vec<V>, E)>;
+
+/// The query indices of a source together with their results.
+type GroupResult<V, E> = (Vec<usize>, Vec<QueryResult<V, E>>);
+
+/// Groups `queries` by source in order of first appearance, returning the targets of each
+/// source together with the indices of their queries.
+fn group_by_source<V: Vertex>(queries: &[(V, V)]) -> Vec<(V, Vec<V>, Vec<usize>)> {
+  let mut groups = vec![];
+  let mut index_map = HashMap::new();
+
+  for (i, (source, target)) in queries.iter().enumerate() {
+    let group = *index_map.entry(source.clone()).or_insert_with(|| {
+      groups.push((source.clone(), vec![], vec![]));
+      groups.len() - 1
+    });
+
+    groups[group].1.push(target.clone());
+    groups[group].2.push(i);
+  }
+
+  groups
+}
+
+/// Runs a single Dijkstra traversal from `source` that stops as soon as all `targets` are
+/// settled, and returns the path and weight sum for each target in the same order.
+fn shortest_paths_from<G, V, E>(graph: &G, source: &V, targets: &[V]) -> Vec<QueryResult<V, E>>
+where
+  G: EdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge
+{
+  let mut traverser = graph.dijkstra(source);
+  let paths = traverser.construct_paths(targets);
+
+  paths.into_iter()
+  .zip(targets)
+  .map(|(path, target)| path.map(|path| (path, traverser.cost_to(target).unwrap().clone())))
+  .collect()
+}
+
+/// Puts the results of each source group back into query order.
+fn scatter<V, E>(len: usize, results: Vec<GroupResult<V, E>>) -> Vec<QueryResult<V, E>> {
+  let mut ordered = (0..len).map(|_| None).collect::<Vec<_>>();
+
+  for (indices, paths) in results {
+    for (i, path) in indices.into_iter().zip(paths) {
+      ordered[i] = path;
+    }
+  }
+
+  ordered
+}
+
+/// See [`EdgedGraph::batch_shortest_paths`](./trait.EdgedGraph.html#method.batch_shortest_paths).
+pub(crate) fn batch_shortest_paths<G, V, E>(graph: &G, queries: &[(V, V)]) -> Vec<QueryResult<V, E>>
+where
+  G: EdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge
+{
+  let results = group_by_source(queries).into_iter()
+    .map(|(source, targets, indices)| (indices, shortest_paths_from(graph, &source, &targets)))
+    .collect();
+
+  scatter(queries.len(), results)
+}
+
+/// See [`EdgedGraph::par_batch_shortest_paths`](./trait.EdgedGraph.html#method.par_batch_shortest_paths).
+#[cfg(feature = "rayon")]
+pub(crate) fn par_batch_shortest_paths<G, V, E>(graph: &G, queries: &[(V, V)]) -> Vec<QueryResult<V, E>>
+where
+  G: EdgedGraph<V, E> + Sync,
+  V: Vertex + Send + Sync,
+  E: WeightedEdge + Send
+{
+  let results = group_by_source(queries).into_par_iter()
+    .map(|(source, targets, indices)| (indices, shortest_paths_from(graph, &source, &targets)))
+    .collect();
+
+  scatter(queries.len(), results)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use finite_graph::FiniteGraph;
+
+  #[test]
+  fn batch_shortest_paths_matches_single_queries() {
+    let mut graph = FiniteGraph::new();
+    let v = (0..20).map(|i| graph.insert_vertex(i)).collect::<Vec<_>>();
+
+    for i in 0..v.len() {
+      graph.insert_edge(v[i], v[(i * 7 + 3) % 20], (i * 13 % 5 + 1) as u32).unwrap();
+      graph.insert_edge(v[i], v[(i + 1) % 17], (i * 3 % 7 + 1) as u32).unwrap();
+    }
+
+    let queries = (0..200)
+      .map(|i| (v[i * 11 % 6], v[i * 17 % 20]))
+      .chain(vec![(v[0], v[19]), (v[19], v[0]), (v[3], v[3])])
+      .collect::<Vec<_>>();
+    let results = graph.batch_shortest_paths(&queries);
+
+    assert_eq!(results.len(), queries.len());
+
+    for ((source, target), result) in queries.iter().zip(results) {
+      let expected = graph.weighted_path_between(source, target)
+        .map(|path| {
+          let cost = graph.path_cost(&path).unwrap();
+          (path, cost)
+        });
+
+      assert_eq!(result, expected);
+    }
+
+    assert_eq!(graph.batch_shortest_paths(&[(v[3], v[3])]), vec![Some((vec![v[3]], 0))]);
+    let isolated = graph.insert_vertex(20);
+
+    assert_eq!(graph.batch_shortest_paths(&[(v[0], isolated), (v[0], v[1])])[0], None);
+    assert_eq!(graph.batch_shortest_paths(&[]), vec![]);
+
+    #[cfg(feature = "rayon")]
+    assert_eq!(graph.par_batch_shortest_paths(&queries), graph.batch_shortest_paths(&queries));
+  }
+}
@@ -0,0 +1,243 @@
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use graph::{EdgedGraph, ReversibleGraph};
+use vertex::Vertex;
+use edge::WeightedEdge;
+use vertex_container::AstarContainer;
+
+/// The state of one direction of a bidirectional search.
+struct SearchSide<V, E> {
+  queue: AstarContainer<(V, E), E>,
+  cost_map: HashMap<V, E>,
+  predecessor_map: HashMap<V, V>,
+  settled_set: HashSet<V>
+}
+
+impl<V: Vertex, E: WeightedEdge> SearchSide<V, E> {
+  fn new(start: V) -> SearchSide<V, E> {
+    let mut queue = AstarContainer::new();
+    queue.push((start.clone(), E::default()), E::default());
+
+    SearchSide {
+      queue,
+      cost_map: Some((start, E::default())).into_iter().collect(),
+      predecessor_map: HashMap::new(),
+      settled_set: HashSet::new()
+    }
+  }
+
+  /// Discards stale queue entries and returns the smallest key of the remaining ones.
+  fn top_key(&mut self) -> Option<E> {
+    loop {
+      let stale = match self.queue.peek() {
+        Some(((vertex, cost), _)) => self.settled_set.contains(vertex) || cost > &self.cost_map[vertex],
+        None => return None
+      };
+
+      if !stale {
+        return self.queue.peek().map(|(_, key)| key.clone());
+      }
+
+      self.queue.pop();
+    }
+  }
+
+  /// Settles the vertex with the smallest key and returns it with its cost.
+  fn settle(&mut self) -> (V, E) {
+    let ((vertex, cost), _) = self.queue.pop().unwrap();
+    self.settled_set.insert(vertex.clone());
+
+    (vertex, cost)
+  }
+
+  /// Returns the new cost of `vertex`, if reaching it from `predecessor` is cheaper.
+  fn relax<F: Fn(&V) -> E>(&mut self, predecessor: &V, vertex: V, cost: E, estimator: &F) -> Option<E> {
+    if self.cost_map.get(&vertex).is_some_and(|old_cost| old_cost <= &cost) {
+      return None;
+    }
+
+    let key = cost.clone() + estimator(&vertex);
+
+    self.cost_map.insert(vertex.clone(), cost.clone());
+    self.predecessor_map.insert(vertex.clone(), predecessor.clone());
+    self.queue.push((vertex, cost.clone()), key);
+
+    Some(cost)
+  }
+
+  /// Follows the predecessors of `vertex` and returns the visited vertices, starting with `vertex`.
+  fn trace(&self, vertex: &V) -> Vec<V> {
+    let mut path = vec![vertex.clone()];
+
+    while let Some(predecessor) = self.predecessor_map.get(path.last().unwrap()) {
+      path.push(predecessor.clone());
+    }
+
+    path
+  }
+}
+
+fn update_best<V: Clone + Eq + Hash, E: WeightedEdge>(
+  best: &mut Option<(E, V)>,
+  vertex: &V,
+  cost: E,
+  other_side: &SearchSide<V, E>
+) {
+  if let Some(other_cost) = other_side.cost_map.get(vertex) {
+    let total = cost + other_cost.clone();
+
+    if best.as_ref().is_none_or(|(best_cost, _)| &total < best_cost) {
+      *best = Some((total, vertex.clone()));
+    }
+  }
+}
+
+/// See [`EdgedGraph::bidirectional_astar`](../trait.EdgedGraph.html#method.bidirectional_astar).
+pub(crate) fn bidirectional_astar<G, V, E, F1, F2>(
+  graph: &G,
+  start: &V,
+  target: &V,
+  forward_estimator: F1,
+  backward_estimator: F2
+) -> Option<(Vec<V>, E)>
+where
+  G: EdgedGraph<V, E> + ReversibleGraph<V>,
+  V: Vertex,
+  E: WeightedEdge,
+  F1: Fn(&V) -> E,
+  F2: Fn(&V) -> E
+{
+  let mut forward = SearchSide::<V, E>::new(start.clone());
+  let mut backward = SearchSide::<V, E>::new(target.clone());
+  let mut best = None::<(E, V)>;
+
+  update_best(&mut best, start, E::default(), &backward);
+
+  loop {
+    let (forward_key, backward_key) = match (forward.top_key(), backward.top_key()) {
+      (Some(forward_key), Some(backward_key)) => (forward_key, backward_key),
+      _ => break
+    };
+
+    // Every path that is cheaper than the best one found so far needs to go through
+    // unsettled vertices of both searches, whose keys are lower bounds of its cost
+    let bound = forward_key.clone().max(backward_key.clone());
+
+    if best.as_ref().is_some_and(|(cost, _)| cost <= &bound) {
+      break;
+    }
+
+    if forward_key <= backward_key {
+      let (vertex, cost) = forward.settle();
+
+      for (neighbor, edge) in graph.neighbors_with_edges(&vertex) {
+        if let Some(new_cost) = forward.relax(&vertex, neighbor.clone(), cost.clone() + edge, &forward_estimator) {
+          update_best(&mut best, &neighbor, new_cost, &backward);
+        }
+      }
+    } else {
+      let (vertex, cost) = backward.settle();
+
+      for neighbor in graph.reverse_neighbors(&vertex) {
+        let edge = match graph.edges(&neighbor, &vertex).into_iter().min() {
+          Some(edge) => edge,
+          None => continue
+        };
+
+        if let Some(new_cost) = backward.relax(&vertex, neighbor.clone(), cost.clone() + edge, &backward_estimator) {
+          update_best(&mut best, &neighbor, new_cost, &forward);
+        }
+      }
+    }
+  }
+
+  best.map(|(cost, meeting_vertex)| {
+    let mut path = forward.trace(&meeting_vertex);
+    path.reverse();
+    path.extend(backward.trace(&meeting_vertex).into_iter().skip(1));
+
+    (path, cost)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use vertex_traverser::VertexTraverser;
+
+  type Position = (i32, i32);
+
+  fn grid_graph(size: i32, blocked: &[Position]) -> (FiniteGraph<Position, u32>, HashMap<Position, Id>) {
+    let mut graph = FiniteGraph::new();
+    let mut id_map = HashMap::new();
+
+    for x in 0..size {
+      for y in 0..size {
+        if !blocked.contains(&(x, y)) {
+          id_map.insert((x, y), graph.insert_vertex((x, y)));
+        }
+      }
+    }
+
+    for (&(x, y), &vertex) in &id_map {
+      for &(dx, dy) in &[(1, 0), (0, 1)] {
+        if let Some(&other) = id_map.get(&(x + dx, y + dy)) {
+          graph.insert_edge(vertex, other, ((x * 3 + y * 5) % 4 + 1) as u32).unwrap();
+          graph.insert_edge(other, vertex, ((x * 7 + y) % 3 + 1) as u32).unwrap();
+        }
+      }
+    }
+
+    (graph, id_map)
+  }
+
+  #[test]
+  fn bidirectional_astar_matches_astar_on_grids() {
+    let blocked = (1..9).map(|y| (4, y)).chain((0..7).map(|x| (x, 6))).collect::<Vec<_>>();
+    let (graph, id_map) = grid_graph(10, &blocked);
+    let manhattan = |a: &Position, b: &Position| ((a.0 - b.0).abs() + (a.1 - b.1).abs()) as u32;
+
+    for &(start, target) in &[((0, 0), (9, 9)), ((0, 9), (9, 0)), ((2, 2), (5, 3)), ((3, 3), (3, 3))] {
+      let (s, t) = (id_map[&start], id_map[&target]);
+      let position = |v: &Id| *graph.get_vertex(*v).unwrap();
+
+      let (path, cost) = graph.bidirectional_astar(
+        &s, &t,
+        |v| manhattan(&position(v), &target),
+        |v| manhattan(&position(v), &start)
+      ).unwrap();
+
+      let mut astar = graph.astar(&s, |v| manhattan(&position(v), &target));
+      let astar_path = astar.construct_path(&t).unwrap();
+
+      assert_eq!(Some(cost), graph.path_cost(&astar_path));
+      assert_eq!(Some(cost), graph.path_cost(&path));
+      assert_eq!((path[0], path[path.len() - 1]), (s, t));
+    }
+
+    let (graph, id_map) = grid_graph(5, &[(1, 0), (1, 1), (0, 1)]);
+
+    assert_eq!(graph.bidirectional_astar(&id_map[&(0, 0)], &id_map[&(4, 4)], |_| 0, |_| 0), None);
+  }
+
+  #[test]
+  fn bidirectional_astar_does_not_stop_at_first_meeting() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let s = graph.insert_vertex(());
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+    let t = graph.insert_vertex(());
+
+    // The searches first meet at `a` as soon as the backward search reaches it, which gives a
+    // route of cost 6, but the cheapest path goes through `b` and `c`
+    graph.insert_edge(s, a, 3).unwrap();
+    graph.insert_edge(a, t, 3).unwrap();
+    graph.insert_edge(s, b, 2).unwrap();
+    graph.insert_edge(b, c, 1).unwrap();
+    graph.insert_edge(c, t, 2).unwrap();
+
+    assert_eq!(graph.bidirectional_astar(&s, &t, |_| 0, |_| 0), Some((vec![s, b, c, t], 5)));
+  }
+}
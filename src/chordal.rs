@@ -0,0 +1,138 @@
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use graph::Graph;
+use vertex::Vertex;
+
+/// Returns the adjacency sets of the given vertices by index, where edge directions, self-loops
+/// and neighbors that are not part of `vertices` are ignored, or `None` if `vertices` contains
+/// duplicates.
+fn undirected_adjacency<G, V>(graph: &G, vertices: &[V]) -> Option<Vec<HashSet<usize>>>
+where
+  G: Graph<V>,
+  V: Vertex
+{
+  let index_map = vertices.iter().cloned()
+    .enumerate()
+    .map(|(index, vertex)| (vertex, index))
+    .collect::<HashMap<_, _>>();
+
+  if index_map.len() < vertices.len() {
+    return None;
+  }
+
+  let mut adjacency = vec![HashSet::new(); vertices.len()];
+
+  for (index, vertex) in vertices.iter().enumerate() {
+    for neighbor in graph.neighbors(vertex) {
+      if let Some(&other) = index_map.get(&neighbor) {
+        if other != index {
+          adjacency[index].insert(other);
+          adjacency[other].insert(index);
+        }
+      }
+    }
+  }
+
+  Some(adjacency)
+}
+
+/// Removes all but the first occurrence of every vertex.
+fn distinct<V: Vertex>(vertices: Vec<V>) -> Vec<V> {
+  let mut seen = HashSet::new();
+
+  vertices.into_iter()
+  .filter(|vertex| seen.insert(vertex.clone()))
+  .collect()
+}
+
+/// Returns the lexicographic breadth-first ordering of the vertex indices.
+fn lexicographic_bfs_indices(adjacency: &[HashSet<usize>]) -> Vec<usize> {
+  // Unvisited vertices are kept in an ordered partition, whose classes are split whenever a
+  // vertex is visited, so that its neighbors come first
+  let mut partition = vec![(0..adjacency.len()).collect::<Vec<_>>()];
+  let mut order = Vec::with_capacity(adjacency.len());
+
+  while let Some(class) = partition.first_mut() {
+    let index = class.remove(0);
+
+    if class.is_empty() {
+      partition.remove(0);
+    }
+
+    order.push(index);
+
+    partition = partition.into_iter()
+      .flat_map(|class| {
+        let (neighbors, others): (Vec<_>, Vec<_>) = class.into_iter()
+          .partition(|other| adjacency[index].contains(other));
+
+        vec![neighbors, others]
+      })
+      .filter(|class| !class.is_empty())
+      .collect();
+  }
+
+  order
+}
+
+/// Returns `true` if the later neighbors of every vertex in `order` form a clique.
+fn is_perfect_elimination_ordering_indices(adjacency: &[HashSet<usize>], order: &[usize]) -> bool {
+  let mut position = vec![0; order.len()];
+
+  for (i, &index) in order.iter().enumerate() {
+    position[index] = i;
+  }
+
+  // It suffices to check that the later neighbors are adjacent to the earliest of them
+  order.iter().enumerate().all(|(i, &index)| {
+    let later = adjacency[index].iter()
+      .copied()
+      .filter(|&other| position[other] > i)
+      .collect::<Vec<_>>();
+
+    match later.iter().copied().min_by_key(|&other| position[other]) {
+      None => true,
+      Some(first) => later.iter().all(|&other| other == first || adjacency[first].contains(&other))
+    }
+  })
+}
+
+/// See [`Graph::lexicographic_bfs`](./trait.Graph.html#method.lexicographic_bfs).
+pub(crate) fn lexicographic_bfs<G, V>(graph: &G, all_vertices: Vec<V>) -> Vec<V>
+where
+  G: Graph<V>,
+  V: Vertex
+{
+  let vertices = distinct(all_vertices);
+  let adjacency = undirected_adjacency(graph, &vertices).unwrap();
+
+  lexicographic_bfs_indices(&adjacency).into_iter()
+    .map(|index| vertices[index].clone())
+    .collect()
+}
+
+/// See [`Graph::is_perfect_elimination_ordering`](./trait.Graph.html#method.is_perfect_elimination_ordering).
+pub(crate) fn is_perfect_elimination_ordering<G, V>(graph: &G, ordering: &[V]) -> bool
+where
+  G: Graph<V>,
+  V: Vertex
+{
+  match undirected_adjacency(graph, ordering) {
+    None => false,
+    Some(adjacency) => is_perfect_elimination_ordering_indices(&adjacency, &(0..ordering.len()).collect::<Vec<_>>())
+  }
+}
+
+/// See [`Graph::is_chordal`](./trait.Graph.html#method.is_chordal).
+pub(crate) fn is_chordal<G, V>(graph: &G, all_vertices: Vec<V>) -> bool
+where
+  G: Graph<V>,
+  V: Vertex
+{
+  let vertices = distinct(all_vertices);
+  let adjacency = undirected_adjacency(graph, &vertices).unwrap();
+  let mut order = lexicographic_bfs_indices(&adjacency);
+
+  order.reverse();
+  is_perfect_elimination_ordering_indices(&adjacency, &order)
+}
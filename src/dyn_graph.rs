@@ -0,0 +1,182 @@
+use crate::*;
+use vertex::Vertex;
+use edge::Edge;
+
+/// An object-safe companion trait of [`Graph<V>`](./trait.Graph.html).
+///
+/// `DynGraph<V>` is implemented for every type that implements `Graph<V>`, and in turn
+/// `&dyn DynGraph<V>` and `Box<dyn DynGraph<V>>` implement `Graph<V>`, so graph
+/// implementations can be swapped at runtime while still using all vertex traversers.
+///
+/// # Example
+///
+/// ```
+/// use graph_iter::{Graph, DynGraph};
+/// use graph_iter::vertex_traverser::VertexTraverser;
+///
+/// struct Line;
+///
+/// impl Graph<i32> for Line {
+///   type NeighborsIterator = Vec<i32>;
+///
+///   fn neighbors(&self, &v: &i32) -> Vec<i32> {
+///     vec![v - 1, v + 1]
+///   }
+/// }
+///
+/// let graph: Box<dyn DynGraph<i32>> = Box::new(Line);
+/// let path = graph.bfs(&0).construct_path(&3).unwrap();
+///
+/// assert_eq!(path, [0, 1, 2, 3]);
+/// ```
+pub trait DynGraph<V: Vertex> {
+  /// Generates a boxed iterator of adjacent vertices that can be reached from `vertex`
+  /// by traveling along an edge.
+  fn neighbors_dyn<'a>(&'a self, vertex: &V) -> Box<dyn Iterator<Item = V> + 'a> where V: 'a;
+}
+
+impl<V: Vertex, G: Graph<V>> DynGraph<V> for G {
+  fn neighbors_dyn<'a>(&'a self, vertex: &V) -> Box<dyn Iterator<Item = V> + 'a> where V: 'a {
+    Box::new(self.neighbors(vertex).into_iter())
+  }
+}
+
+impl<'a, V: Vertex + 'a> Graph<V> for &'a dyn DynGraph<V> {
+  type NeighborsIterator = Box<dyn Iterator<Item = V> + 'a>;
+
+  fn neighbors(&self, vertex: &V) -> Self::NeighborsIterator {
+    (**self).neighbors_dyn(vertex)
+  }
+}
+
+/// Since the neighbors iterator of a boxed graph cannot borrow from the box,
+/// the neighbors are collected into a `Vec<V>`. Use `&*graph` to avoid this allocation.
+impl<'a, V: Vertex + 'a> Graph<V> for Box<dyn DynGraph<V> + 'a> {
+  type NeighborsIterator = Vec<V>;
+
+  fn neighbors(&self, vertex: &V) -> Vec<V> {
+    (**self).neighbors_dyn(vertex).collect()
+  }
+}
+
+/// An object-safe companion trait of [`EdgedGraph<V, E>`](./trait.EdgedGraph.html).
+///
+/// Like [`DynGraph<V>`](./trait.DynGraph.html), it is implemented for every type that
+/// implements `EdgedGraph<V, E>`, and `&dyn DynEdgedGraph<V, E>` and
+/// `Box<dyn DynEdgedGraph<V, E>>` implement `EdgedGraph<V, E>`.
+pub trait DynEdgedGraph<V: Vertex, E: Edge>: DynGraph<V> {
+  /// Generates a boxed iterator of edges that connect `vertex` with `other`.
+  fn edges_dyn<'a>(&'a self, vertex: &V, other: &V) -> Box<dyn Iterator<Item = E> + 'a> where V: 'a, E: 'a;
+
+  /// Generates a boxed iterator of adjacent vertices of `vertex`, each paired with an edge
+  /// that connects `vertex` with it.
+  fn neighbors_with_edges_dyn<'a>(&'a self, vertex: &V) -> Box<dyn Iterator<Item = (V, E)> + 'a> where V: 'a, E: 'a;
+}
+
+impl<V: Vertex, E: Edge, G: EdgedGraph<V, E>> DynEdgedGraph<V, E> for G {
+  fn edges_dyn<'a>(&'a self, vertex: &V, other: &V) -> Box<dyn Iterator<Item = E> + 'a> where V: 'a, E: 'a {
+    Box::new(self.edges(vertex, other).into_iter())
+  }
+
+  fn neighbors_with_edges_dyn<'a>(&'a self, vertex: &V) -> Box<dyn Iterator<Item = (V, E)> + 'a> where V: 'a, E: 'a {
+    Box::new(self.neighbors_with_edges(vertex))
+  }
+}
+
+impl<'a, V: Vertex + 'a, E: Edge + 'a> Graph<V> for &'a dyn DynEdgedGraph<V, E> {
+  type NeighborsIterator = Box<dyn Iterator<Item = V> + 'a>;
+
+  fn neighbors(&self, vertex: &V) -> Self::NeighborsIterator {
+    (**self).neighbors_dyn(vertex)
+  }
+}
+
+impl<'a, V: Vertex + 'a, E: Edge + 'a> EdgedGraph<V, E> for &'a dyn DynEdgedGraph<V, E> {
+  type EdgesIterator = Box<dyn Iterator<Item = E> + 'a>;
+
+  fn edges(&self, vertex: &V, other: &V) -> Self::EdgesIterator {
+    (**self).edges_dyn(vertex, other)
+  }
+
+  fn neighbors_with_edges<'s>(&'s self, vertex: &V) -> impl Iterator<Item = (V, E)> + use<'s, 'a, V, E> {
+    (**self).neighbors_with_edges_dyn(vertex)
+  }
+}
+
+impl<'a, V: Vertex + 'a, E: Edge + 'a> Graph<V> for Box<dyn DynEdgedGraph<V, E> + 'a> {
+  type NeighborsIterator = Vec<V>;
+
+  fn neighbors(&self, vertex: &V) -> Vec<V> {
+    (**self).neighbors_dyn(vertex).collect()
+  }
+}
+
+impl<'a, V: Vertex + 'a, E: Edge + 'a> EdgedGraph<V, E> for Box<dyn DynEdgedGraph<V, E> + 'a> {
+  type EdgesIterator = Vec<E>;
+
+  fn edges(&self, vertex: &V, other: &V) -> Vec<E> {
+    (**self).edges_dyn(vertex, other).collect()
+  }
+
+  fn neighbors_with_edges<'s>(&'s self, vertex: &V) -> impl Iterator<Item = (V, E)> + use<'s, 'a, V, E> {
+    (**self).neighbors_with_edges_dyn(vertex)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use vertex_traverser::VertexTraverser;
+
+  struct Line;
+
+  impl Graph<i32> for Line {
+    type NeighborsIterator = Vec<i32>;
+
+    fn neighbors(&self, &v: &i32) -> Vec<i32> {
+      vec![v - 1, v + 1]
+    }
+  }
+
+  struct Skip;
+
+  impl Graph<i32> for Skip {
+    type NeighborsIterator = Vec<i32>;
+
+    fn neighbors(&self, &v: &i32) -> Vec<i32> {
+      vec![v + 2, v + 1]
+    }
+  }
+
+  impl EdgedGraph<i32, u32> for Line {
+    type EdgesIterator = Vec<u32>;
+
+    fn edges(&self, &v: &i32, &w: &i32) -> Vec<u32> {
+      vec![(v * w).unsigned_abs()]
+    }
+  }
+
+  #[test]
+  fn traverse_graph_trait_objects() {
+    let graphs: Vec<Box<dyn DynGraph<i32>>> = vec![Box::new(Line), Box::new(Skip)];
+    let paths = graphs.iter()
+      .map(|graph| graph.bfs(&0).construct_path(&4).unwrap())
+      .collect::<Vec<_>>();
+
+    assert_eq!(paths, vec![vec![0, 1, 2, 3, 4], vec![0, 2, 4]]);
+
+    let graph_ref: &dyn DynGraph<i32> = &*graphs[0];
+    assert_eq!(graph_ref.dfs(&0).iter().take(3).collect::<Vec<_>>(), vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn dijkstra_on_edged_graph_trait_objects() {
+    let graph: Box<dyn DynEdgedGraph<i32, u32>> = Box::new(Line);
+    let path = graph.dijkstra(&-1).construct_path(&2);
+
+    assert_eq!(path, Some(vec![-1, 0, 1, 2]));
+
+    let graph_ref: &dyn DynEdgedGraph<i32, u32> = &*graph;
+    assert_eq!(graph_ref.dijkstra(&-1).construct_path(&2), path);
+  }
+}
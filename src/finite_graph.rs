@@ -1,11 +1,65 @@
 use crate::*;
-use std::collections::HashMap;
+use std::collections::{hash_map, HashMap};
+use std::fmt;
 use std::hash::Hash;
 use graph::Graph;
 use edge::Edge;
+use vertex_traverser::{BfsVertexTrav, DfsVertexTrav};
+
+mod shortest_paths;
+mod eulerian;
+mod dag;
+mod centrality;
+mod matching;
+mod collect;
+mod flow;
+mod weights;
+mod cuts;
+mod dynamic_sssp;
+mod isomorphism;
+mod cliques;
+mod independent_sets;
+mod coloring;
+mod transforms;
+mod summary;
+mod disjoint_paths;
+mod coarsen;
+mod overlay;
+mod recorded;
+mod bridges;
+mod ordering;
+#[cfg(feature = "rand")]
+mod diameter;
+mod canonical;
+mod spectral;
+mod communities;
+mod builder;
+mod components;
+mod acyclic;
+mod graph6;
+mod display;
+mod simple;
+
+pub use eulerian::*;
+pub use centrality::*;
+pub use dynamic_sssp::*;
+pub use coloring::*;
+pub use summary::*;
+pub use coarsen::*;
+pub use overlay::*;
+pub use recorded::*;
+pub use bridges::*;
+pub use canonical::*;
+pub use builder::*;
+pub use weights::*;
+pub use acyclic::*;
+pub use graph6::*;
+pub use display::*;
+pub use simple::*;
+pub use spectral::*;
 
 /// An identifier struct to reference vertices and edges in [`FiniteGraph`](struct.FiniteGraph.html).
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Id(usize);
 
 impl Id {
@@ -25,6 +79,12 @@ pub struct FiniteGraph<V, E> {
   reverse_neighbors_map: HashMap<Id, Vec<(Id, Id)>>
 }
 
+impl<V, E> Default for FiniteGraph<V, E> {
+  fn default() -> FiniteGraph<V, E> {
+    FiniteGraph::new()
+  }
+}
+
 impl<V, E> FiniteGraph<V, E> {
   /// Constructs a new, empty `FiniteGraph<V, E>`.
   pub fn new() -> FiniteGraph<V, E> {
@@ -59,6 +119,18 @@ impl<V, E> FiniteGraph<V, E> {
     (self.vertices_map.capacity(), self.edges_map.capacity())
   }
 
+  /// Reserves capacity for at least `additional` more vertices to be inserted.
+  pub fn reserve_vertices(&mut self, additional: usize) {
+    self.vertices_map.reserve(additional);
+    self.neighbors_map.reserve(additional);
+    self.reverse_neighbors_map.reserve(additional);
+  }
+
+  /// Reserves capacity for at least `additional` more edges to be inserted.
+  pub fn reserve_edges(&mut self, additional: usize) {
+    self.edges_map.reserve(additional);
+  }
+
   /// An iterator visiting all vertices in arbitrary order.
   pub fn all_vertices(&self) -> impl Iterator<Item = (Id, &V)> {
     self.vertices_map.iter().map(|(id, v)| (*id, v))
@@ -69,6 +141,11 @@ impl<V, E> FiniteGraph<V, E> {
     self.vertices_map.iter_mut().map(|(id, v)| (*id, v))
   }
 
+  /// Creates a consuming iterator visiting all vertices in arbitrary order.
+  pub fn vertices_iter(self) -> impl Iterator<Item = (Id, V)> {
+    self.vertices_map.into_iter()
+  }
+
   /// An iterator visiting all edges in arbitrary order.
   pub fn all_edges(&self) -> impl Iterator<Item = (Id, &E)> {
     self.edges_map.iter().map(|(id, (e, _, _))| (*id, e))
@@ -79,6 +156,37 @@ impl<V, E> FiniteGraph<V, E> {
     self.edges_map.iter_mut().map(|(id, (e, _, _))| (*id, e))
   }
 
+  /// An iterator visiting the endpoints of all edges as `(from, to)` pairs in arbitrary
+  /// order. Parallel edges yield the same pair multiple times. Bidirectional edges are yielded
+  /// only once, in the direction they have been inserted with.
+  pub fn vertex_pairs(&self) -> impl Iterator<Item = (Id, Id)> + '_ {
+    self.edges_map.values().map(|&(_, from, to)| (from, to))
+  }
+
+  /// An iterator visiting all edges as `(from, to, edge)` triples in arbitrary order, see
+  /// [`vertex_pairs`](#method.vertex_pairs).
+  pub fn vertex_pairs_with_data(&self) -> impl Iterator<Item = (Id, Id, &E)> {
+    self.edges_map.values().map(|(e, from, to)| (*from, *to, e))
+  }
+
+  /// An iterator visiting all vertices in ascending order of their ids, i.e. in insertion
+  /// order. Unlike [`all_vertices`](#method.all_vertices), this needs to sort the ids first.
+  pub fn all_vertices_ordered(&self) -> impl Iterator<Item = (Id, &V)> {
+    let mut vertices = self.vertices_map.iter().map(|(&id, v)| (id, v)).collect::<Vec<_>>();
+    vertices.sort_by_key(|&(id, _)| id);
+
+    vertices.into_iter()
+  }
+
+  /// An iterator visiting all edges in ascending order of their ids, i.e. in insertion
+  /// order. Unlike [`all_edges`](#method.all_edges), this needs to sort the ids first.
+  pub fn all_edges_ordered(&self) -> impl Iterator<Item = (Id, &E)> {
+    let mut edges = self.edges_map.iter().map(|(&id, (e, _, _))| (id, e)).collect::<Vec<_>>();
+    edges.sort_by_key(|&(id, _)| id);
+
+    edges.into_iter()
+  }
+
   /// Returns a reference to the value corresponding to the vertex.
   pub fn get_vertex(&self, vertex: Id) -> Option<&V> {
     self.vertices_map.get(&vertex)
@@ -99,6 +207,41 @@ impl<V, E> FiniteGraph<V, E> {
     self.edges_map.get_mut(&edge).map(|(e, _, _)| e)
   }
 
+  /// Returns all edges going from `from` to `to` together with their values, in the order
+  /// they were inserted. Unlike [`EdgedGraph::edges`](./trait.EdgedGraph.html#tymethod.edges),
+  /// this keeps the ids, so parallel edges can be told apart.
+  pub fn parallel_edges_between(&self, from: Id, to: Id) -> Vec<(Id, &E)> {
+    self.neighbors_map.get(&from).into_iter().flatten()
+    .filter(|&&(neighbor, _)| neighbor == to)
+    .map(|&(_, edge)| (edge, &self.edges_map[&edge].0))
+    .collect()
+  }
+
+  /// Returns the number of edges going from `from` to `to`.
+  pub fn parallel_edge_count(&self, from: Id, to: Id) -> usize {
+    self.neighbors_map.get(&from).map_or(0, |neighbors| {
+      neighbors.iter().filter(|&&(neighbor, _)| neighbor == to).count()
+    })
+  }
+
+  /// Returns the number of edges going out of `vertex`.
+  pub fn out_degree(&self, vertex: Id) -> usize {
+    self.neighbors_map.get(&vertex).map_or(0, Vec::len)
+  }
+
+  /// Returns the number of edges coming into `vertex`.
+  pub fn in_degree(&self, vertex: Id) -> usize {
+    self.reverse_neighbors_map.get(&vertex).map_or(0, Vec::len)
+  }
+
+  /// An iterator visiting all vertices that are connected with `vertex` by an edge
+  /// in any direction, together with the connecting edge.
+  pub(crate) fn undirected_neighbors(&self, vertex: Id) -> impl Iterator<Item = (Id, Id)> + '_ {
+    self.neighbors_map.get(&vertex).into_iter().flatten()
+    .chain(self.reverse_neighbors_map.get(&vertex).into_iter().flatten())
+    .copied()
+  }
+
   /// Returns `true` if the graph contains a value for the specified vertex.
   pub fn contains_vertex(&self, vertex: Id) -> bool {
     self.vertices_map.contains_key(&vertex)
@@ -109,6 +252,24 @@ impl<V, E> FiniteGraph<V, E> {
     self.edges_map.contains_key(&edge)
   }
 
+  /// Returns a [`DfsVertexTrav`](./vertex_traverser/struct.DfsVertexTrav.html) that iterates
+  /// all vertices of the graph in a depth-first manner. Whenever the depth-first search runs out
+  /// of reachable vertices, it continues with the unvisited vertex with the smallest id, so every
+  /// vertex is visited exactly once.
+  pub fn dfs_all(&self) -> DfsVertexTrav<'_, Self, Id> {
+    let mut roots = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    roots.sort();
+
+    DfsVertexTrav::with_roots(self, roots.first().copied().unwrap_or(Id(0)), roots)
+  }
+
+  /// Returns an iterator that yields a fresh [`BfsVertexTrav`](./vertex_traverser/struct.BfsVertexTrav.html)
+  /// for every vertex of the graph in ascending order of their ids. The traversers are lazy,
+  /// so they have to be advanced before querying distances.
+  pub fn bfs_from_all(&self) -> impl Iterator<Item = BfsVertexTrav<'_, Self, Id>> {
+    self.all_vertices_ordered().map(move |(vertex, _)| self.bfs(&vertex))
+  }
+
   pub fn insert_vertex(&mut self, value: V) -> Id {
     let id = self.id.next();
     self.vertices_map.insert(id, value);
@@ -118,8 +279,8 @@ impl<V, E> FiniteGraph<V, E> {
 
   pub fn remove_vertex(&mut self, vertex: Id) -> Option<V> {
     let result = self.vertices_map.remove(&vertex);
-    let neighbors = self.neighbors_map.remove(&vertex).unwrap_or_else(|| vec![]);
-    let reverse_neighbors = self.reverse_neighbors_map.remove(&vertex).unwrap_or_else(|| vec![]);
+    let neighbors = self.neighbors_map.remove(&vertex).unwrap_or_default();
+    let reverse_neighbors = self.reverse_neighbors_map.remove(&vertex).unwrap_or_default();
 
     let to_delete = neighbors.into_iter()
       .map(|(_, e)| e)
@@ -135,6 +296,51 @@ impl<V, E> FiniteGraph<V, E> {
     result
   }
 
+  /// Merges `other` into `vertex` by moving all edges of `other` to `vertex`. Edges between
+  /// `vertex` and `other` are removed, while self-loops of `other` become self-loops of `vertex`.
+  /// Moved edges keep their ids and directions.
+  ///
+  /// Returns the value of the removed vertex `other`, or `None` if one of the vertices doesn't
+  /// exist or both are equal.
+  pub fn contract_vertices(&mut self, vertex: Id, other: Id) -> Option<V> {
+    if vertex == other || !self.contains_vertex(vertex) || !self.contains_vertex(other) {
+      return None;
+    }
+
+    let mut incident_edges = self.undirected_neighbors(other)
+      .map(|(_, edge)| edge)
+      .collect::<Vec<_>>();
+
+    incident_edges.sort();
+    incident_edges.dedup();
+
+    for edge in incident_edges {
+      let (from, to) = {
+        let &(_, from, to) = &self.edges_map[&edge];
+        (from, to)
+      };
+
+      let bidirectional = self.is_bi_edge(edge, from, to);
+      let data = self.remove_edge(edge).unwrap();
+
+      if from != to && (from == vertex || to == vertex) {
+        continue;
+      }
+
+      let moved = |v: Id| if v == other { vertex } else { v };
+      let (from, to) = (moved(from), moved(to));
+
+      self.edges_map.insert(edge, (data, from, to));
+      self.insert_edge_id(from, to, edge);
+
+      if bidirectional {
+        self.insert_edge_id(to, from, edge);
+      }
+    }
+
+    self.remove_vertex(other)
+  }
+
   fn insert_edge_id(&mut self, from: Id, to: Id, edge: Id) -> Option<Id> {
     if let Some(neighbors) = self.neighbors_map.get_mut(&from) {
       neighbors.push((to, edge));
@@ -162,6 +368,11 @@ impl<V, E> FiniteGraph<V, E> {
     self.insert_edge_id(from, to, id)
   }
 
+  /// Inserts an edge that can be traversed in both directions and returns its id, or `None`
+  /// if one of the vertices doesn't exist. The edge is stored once with `from` and `to` as its
+  /// endpoints, so it counts as a single edge in [`len`](#method.len) and iterators over all
+  /// edges like [`all_edges`](#method.all_edges) and [`vertex_pairs`](#method.vertex_pairs)
+  /// yield it once, as going from `from` to `to`.
   pub fn insert_bi_edge(&mut self, from: Id, to: Id, data: E) -> Option<Id> {
     let edge = self.insert_edge(from, to, data);
 
@@ -176,11 +387,11 @@ impl<V, E> FiniteGraph<V, E> {
     self.edges_map.remove(&edge).map(|(data, vertex, other)| {
       for vertex in &[vertex, other] {
         for map in &mut [&mut self.neighbors_map, &mut self.reverse_neighbors_map] {
-          map.get_mut(&vertex).map(|neighbors| {
-            neighbors.iter().position(|(_, e)| e == &edge).map(|index| {
+          if let Some(neighbors) = map.get_mut(vertex) {
+            if let Some(index) = neighbors.iter().position(|(_, e)| e == &edge) {
               neighbors.remove(index);
-            });
-          });
+            }
+          }
         }
       }
 
@@ -189,17 +400,92 @@ impl<V, E> FiniteGraph<V, E> {
   }
 }
 
+/// A list of key-value pairs that is formatted like a map.
+struct DebugEntries<K, T>(Vec<(K, T)>);
+
+impl<K: fmt::Debug, T: fmt::Debug> fmt::Debug for DebugEntries<K, T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.0.iter().map(|(k, v)| (k, v))).finish()
+  }
+}
+
+/// Formats vertices and edges in ascending order of their ids, so the output is reproducible.
+/// Edges are formatted as `(from, to, data)` triples.
+impl<V: fmt::Debug, E: fmt::Debug> fmt::Debug for FiniteGraph<V, E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut edges = self.edges_map.iter()
+      .map(|(&id, (data, from, to))| (id, (*from, *to, data)))
+      .collect::<Vec<_>>();
+    edges.sort_by_key(|&(id, _)| id);
+
+    f.debug_struct("FiniteGraph")
+    .field("vertices", &DebugEntries(self.all_vertices_ordered().collect()))
+    .field("edges", &DebugEntries(edges))
+    .finish()
+  }
+}
+
+/// An iterator over all edges of a [`FiniteGraph`](struct.FiniteGraph.html) as
+/// `(from, to, edge)` triples of ids. Bidirectional edges are yielded only once.
+pub struct EdgeTriples<'a, E>(hash_map::Iter<'a, Id, (E, Id, Id)>);
+
+impl<'a, E> Iterator for EdgeTriples<'a, E> {
+  type Item = (Id, Id, Id);
+
+  fn next(&mut self) -> Option<(Id, Id, Id)> {
+    self.0.next().map(|(&edge, &(_, from, to))| (from, to, edge))
+  }
+}
+
+impl<'a, V, E> IntoIterator for &'a FiniteGraph<V, E> {
+  type Item = (Id, Id, Id);
+  type IntoIter = EdgeTriples<'a, E>;
+
+  fn into_iter(self) -> EdgeTriples<'a, E> {
+    EdgeTriples(self.edges_map.iter())
+  }
+}
+
+/// A consuming iterator over all edges of a [`FiniteGraph`](struct.FiniteGraph.html) as
+/// `(from, to, edge)` triples of values. Bidirectional edges are yielded only once.
+pub struct IntoEdgeTriples<V, E> {
+  vertices_map: HashMap<Id, V>,
+  edges: hash_map::IntoIter<Id, (E, Id, Id)>
+}
+
+impl<V: Clone, E> Iterator for IntoEdgeTriples<V, E> {
+  type Item = (V, V, E);
+
+  fn next(&mut self) -> Option<(V, V, E)> {
+    self.edges.next().map(|(_, (edge, from, to))| {
+      (self.vertices_map[&from].clone(), self.vertices_map[&to].clone(), edge)
+    })
+  }
+}
+
+impl<V: Clone, E> IntoIterator for FiniteGraph<V, E> {
+  type Item = (V, V, E);
+  type IntoIter = IntoEdgeTriples<V, E>;
+
+  fn into_iter(self) -> IntoEdgeTriples<V, E> {
+    IntoEdgeTriples {
+      vertices_map: self.vertices_map,
+      edges: self.edges_map.into_iter()
+    }
+  }
+}
+
 impl<V, E> Graph<Id> for FiniteGraph<V, E> {
   type NeighborsIterator = Vec<Id>;
 
   fn neighbors(&self, vertex: &Id) -> Vec<Id> {
-    self.neighbors_map.get(&vertex)
+    self.neighbors_map.get(vertex)
     .map(|neighbors| {
       neighbors.iter()
       .map(|(v, _)| *v)
       .collect()
     })
-    .unwrap_or_else(|| vec![])
+    .unwrap_or_default()
   }
 }
 
@@ -207,13 +493,13 @@ impl<V, E> ReversibleGraph<Id> for FiniteGraph<V, E> {
   type ReverseNeighborsIterator = Vec<Id>;
 
   fn reverse_neighbors(&self, vertex: &Id) -> Vec<Id> {
-    self.reverse_neighbors_map.get(&vertex)
+    self.reverse_neighbors_map.get(vertex)
     .map(|neighbors| {
       neighbors.iter()
       .map(|(v, _)| *v)
       .collect()
     })
-    .unwrap_or_else(|| vec![])
+    .unwrap_or_default()
   }
 }
 
@@ -221,14 +507,24 @@ impl<V, E: Edge> EdgedGraph<Id, E> for FiniteGraph<V, E> {
   type EdgesIterator = Vec<E>;
 
   fn edges(&self, vertex: &Id, other: &Id) -> Vec<E> {
-    self.neighbors_map.get(&vertex)
+    self.neighbors_map.get(vertex)
     .map(|neighbors| {
       neighbors.iter()
       .filter(|&(v, _)| v == other)
       .filter_map(|&(_, e)| self.get_edge(e).cloned())
       .collect()
     })
-    .unwrap_or_else(|| vec![])
+    .unwrap_or_default()
+  }
+
+  fn neighbors_with_edges<'a>(&'a self, vertex: &Id) -> impl Iterator<Item = (Id, E)> + use<'a, V, E> {
+    self.neighbors_map.get(vertex).into_iter().flatten()
+    .filter_map(move |&(v, e)| self.get_edge(e).map(|edge| (v, edge.clone())))
+  }
+
+  fn shortest_cycle_through(&self, vertex: &Id) -> Option<(Vec<Id>, E)>
+  where E: edge::WeightedEdge {
+    self.shortest_cycle_through_vertex(*vertex)
   }
 }
 
@@ -312,10 +608,345 @@ mod tests {
     assert_eq!(graph.edges(&d, &a), vec![4]);
     assert_eq!(graph.edges(&b, &d), vec![]);
     assert_eq!(graph.edges(&d, &b), vec![]);
+    assert_eq!(graph.neighbors_with_edges(&a).collect::<Vec<_>>(), vec![(b, 1), (d, 4)]);
 
     graph.remove_edge(e1);
 
     assert_eq!(graph.edges(&a, &b), vec![]);
     assert_eq!(graph.edges(&b, &a), vec![]);
   }
+
+  #[test]
+  fn vertex_pairs_yield_bi_edges_once() {
+    let mut graph = FiniteGraph::<(), usize>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+
+    graph.insert_edge(a, b, 1).unwrap();
+    graph.insert_edge(a, b, 2).unwrap();
+    graph.insert_bi_edge(c, b, 3).unwrap();
+
+    let mut pairs = graph.vertex_pairs().collect::<Vec<_>>();
+    let mut triples = graph.vertex_pairs_with_data().collect::<Vec<_>>();
+
+    pairs.sort();
+    triples.sort();
+
+    assert_eq!(pairs, vec![(a, b), (a, b), (c, b)]);
+    assert_eq!(triples, vec![(a, b, &1), (a, b, &2), (c, b, &3)]);
+  }
+
+  #[test]
+  fn parallel_edges_between_keeps_ids() {
+    let mut graph = FiniteGraph::<(), &str>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+
+    let e1 = graph.insert_edge(a, b, "first").unwrap();
+    let e2 = graph.insert_bi_edge(a, b, "second").unwrap();
+    let e3 = graph.insert_edge(a, b, "first").unwrap();
+    graph.insert_edge(b, b, "loop").unwrap();
+
+    assert_eq!(graph.parallel_edges_between(a, b), vec![(e1, &"first"), (e2, &"second"), (e3, &"first")]);
+    assert_eq!(graph.parallel_edges_between(b, a), vec![(e2, &"second")]);
+    assert_eq!(graph.parallel_edge_count(a, b), 3);
+    assert_eq!(graph.parallel_edge_count(b, b), 1);
+
+    graph.remove_edge(e2);
+
+    assert_eq!(graph.parallel_edge_count(b, a), 0);
+    assert_eq!(graph.parallel_edges_between(a, Id(100)), vec![]);
+  }
+
+  #[test]
+  fn contract_vertices_moves_edges() {
+    let mut graph = FiniteGraph::<Position, usize>::new();
+
+    let a = graph.insert_vertex((0, 0));
+    let b = graph.insert_vertex((0, 1));
+    let c = graph.insert_vertex((1, 1));
+
+    graph.insert_edge(a, b, 1).unwrap();
+    let e2 = graph.insert_bi_edge(b, c, 2).unwrap();
+    let e3 = graph.insert_edge(c, b, 3).unwrap();
+    let e4 = graph.insert_edge(b, b, 4).unwrap();
+
+    assert_eq!(graph.contract_vertices(a, a), None);
+    assert_eq!(graph.contract_vertices(a, b), Some((0, 1)));
+    assert_eq!(graph.len(), (2, 3));
+
+    let mut neighbors = graph.neighbors(&a);
+    neighbors.sort();
+
+    assert_eq!(neighbors, vec![a, c]);
+    assert_eq!(graph.edges(&c, &a), vec![2, 3]);
+    assert_eq!(graph.edges(&a, &c), vec![2]);
+    assert_eq!(graph.edges(&a, &a), vec![4]);
+    assert!(graph.contains_edge(e2) && graph.contains_edge(e3) && graph.contains_edge(e4));
+  }
+
+  #[test]
+  fn reserve_capacity() {
+    let mut graph = FiniteGraph::<Position, usize>::with_capacity(2, 2);
+
+    graph.reserve_vertices(100);
+    graph.reserve_edges(200);
+
+    let (vertex_capacity, edge_capacity) = graph.capacity();
+
+    assert!(vertex_capacity >= 100);
+    assert!(edge_capacity >= 200);
+  }
+
+  #[test]
+  fn iterate_edge_triples() {
+    let mut graph = FiniteGraph::<Position, usize>::new();
+
+    let a = graph.insert_vertex((0, 0));
+    let b = graph.insert_vertex((0, 1));
+    let c = graph.insert_vertex((1, 1));
+
+    let e1 = graph.insert_edge(a, b, 1).unwrap();
+    let e2 = graph.insert_bi_edge(b, c, 2).unwrap();
+
+    let mut triples = (&graph).into_iter().collect::<Vec<_>>();
+    triples.sort_by_key(|&(_, _, edge)| graph.get_edge(edge).cloned());
+
+    assert_eq!(triples, vec![(a, b, e1), (b, c, e2)]);
+
+    let mut vertices = graph.clone().vertices_iter().collect::<Vec<_>>();
+    vertices.sort_by_key(|&(_, v)| v);
+
+    assert_eq!(vertices, vec![(a, (0, 0)), (b, (0, 1)), (c, (1, 1))]);
+
+    let mut value_triples = graph.into_iter().collect::<Vec<_>>();
+    value_triples.sort_by_key(|&(_, _, edge)| edge);
+
+    assert_eq!(value_triples, vec![((0, 0), (0, 1), 1), ((0, 1), (1, 1), 2)]);
+  }
+
+  #[test]
+  fn ordered_iteration_and_debug_output() {
+    let mut graph = FiniteGraph::<&str, usize>::new();
+    let ids = (0..20)
+      .map(|i| graph.insert_vertex(if i % 2 == 0 { "even" } else { "odd" }))
+      .collect::<Vec<_>>();
+
+    for pair in ids.windows(2) {
+      graph.insert_edge(pair[1], pair[0], 0).unwrap();
+    }
+
+    assert_eq!(graph.all_vertices_ordered().map(|(id, _)| id).collect::<Vec<_>>(), ids);
+
+    let edges = graph.all_edges_ordered().map(|(id, _)| id).collect::<Vec<_>>();
+    let mut sorted_edges = edges.clone();
+    sorted_edges.sort();
+
+    assert_eq!(edges, sorted_edges);
+
+    let mut small_graph = FiniteGraph::<&str, usize>::new();
+    let a = small_graph.insert_vertex("a");
+    let b = small_graph.insert_vertex("b");
+
+    small_graph.insert_edge(b, a, 1).unwrap();
+
+    assert_eq!(
+      format!("{:?}", small_graph),
+      "FiniteGraph { vertices: {Id(1): \"a\", Id(2): \"b\"}, edges: {Id(3): (Id(2), Id(1), 1)} }"
+    );
+  }
+
+  #[test]
+  fn bfs_from_all_yields_traverser_per_vertex() {
+    use vertex_traverser::VertexTraverser;
+
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..3).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], ()).unwrap();
+    graph.insert_bi_edge(v[1], v[2], ()).unwrap();
+
+    let matrix = graph.bfs_from_all()
+      .map(|mut traverser| {
+        traverser.iter().for_each(drop);
+        v.iter().map(|vertex| traverser.distance_to(vertex)).collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+
+    assert_eq!(matrix, vec![
+      vec![Some(0), Some(1), Some(2)],
+      vec![None, Some(0), Some(1)],
+      vec![None, Some(1), Some(0)]
+    ]);
+  }
+
+  fn assert_send_sync<T: Send + Sync>() {}
+
+  #[test]
+  fn finite_graph_and_shared_traversers_are_send_sync() {
+    use std::sync::Arc;
+    use vertex_traverser::{BfsVertexTrav, DfsVertexTrav, AstarVertexTrav};
+
+    assert_send_sync::<FiniteGraph<Position, usize>>();
+    assert_send_sync::<Id>();
+    assert_send_sync::<BfsVertexTrav<'static, FiniteGraph<Position, usize>, Id>>();
+    assert_send_sync::<DfsVertexTrav<'static, FiniteGraph<Position, usize>, Id>>();
+    assert_send_sync::<AstarVertexTrav<'static, FiniteGraph<Position, usize>, Id, usize, fn(&Id) -> usize>>();
+
+    let graph = Arc::new(FiniteGraph::<Position, usize>::new());
+    let _ = BfsVertexTrav::from_arc(graph.clone(), &Id(0));
+    let _ = DfsVertexTrav::from_arc(graph.clone(), &Id(0));
+    let _ = AstarVertexTrav::from_arc_with_estimator(graph, &Id(0), |_: &Id| 0);
+  }
+
+  #[test]
+  fn parallel_dijkstra_queries_on_shared_graph() {
+    use std::sync::Arc;
+    use std::thread;
+    use vertex_traverser::{VertexTraverser, AstarVertexTrav};
+
+    let mut graph = FiniteGraph::<Position, usize>::new();
+    let size = 6;
+
+    let vertices = (0..size * size)
+      .map(|i| graph.insert_vertex((i / size, i % size)))
+      .collect::<Vec<_>>();
+
+    for i in 0..size * size {
+      let (x, y) = (i / size, i % size);
+
+      if x + 1 < size {
+        graph.insert_bi_edge(vertices[i as usize], vertices[(i + size) as usize], (x * y + 1) as usize);
+      }
+
+      if y + 1 < size {
+        graph.insert_bi_edge(vertices[i as usize], vertices[(i + 1) as usize], (x + 2 * y + 1) as usize);
+      }
+    }
+
+    let target = vertices[vertices.len() - 1];
+    let sequential = vertices.iter().take(8)
+      .map(|start| graph.dijkstra(start).construct_path(&target))
+      .collect::<Vec<_>>();
+
+    let graph = Arc::new(graph);
+    let handles = vertices.iter().take(8)
+      .map(|start| {
+        let mut traverser = AstarVertexTrav::from_arc(graph.clone(), start);
+        thread::spawn(move || traverser.construct_path(&target))
+      })
+      .collect::<Vec<_>>();
+
+    let parallel = handles.into_iter()
+      .map(|handle| handle.join().unwrap())
+      .collect::<Vec<_>>();
+
+    assert!(sequential.iter().all(|path| path.is_some()));
+    assert_eq!(parallel, sequential);
+  }
+
+  #[test]
+  fn neighbors_with_edges_on_high_degree_vertices() {
+    use std::cell::Cell;
+    use vertex_traverser::VertexTraverser;
+
+    struct EdgesCounter<'a> {
+      graph: &'a FiniteGraph<(), usize>,
+      edges_calls: Cell<usize>
+    }
+
+    impl<'a> Graph<Id> for EdgesCounter<'a> {
+      type NeighborsIterator = Vec<Id>;
+
+      fn neighbors(&self, vertex: &Id) -> Vec<Id> {
+        self.graph.neighbors(vertex)
+      }
+    }
+
+    impl<'a> EdgedGraph<Id, usize> for EdgesCounter<'a> {
+      type EdgesIterator = Vec<usize>;
+
+      fn edges(&self, vertex: &Id, other: &Id) -> Vec<usize> {
+        self.edges_calls.set(self.edges_calls.get() + 1);
+        self.graph.edges(vertex, other)
+      }
+    }
+
+    let mut graph = FiniteGraph::<(), usize>::new();
+    let center = graph.insert_vertex(());
+
+    for i in 0..1000 {
+      let leaf = graph.insert_vertex(());
+
+      graph.insert_edge(center, leaf, i % 7 + 1);
+      graph.insert_edge(center, leaf, i % 5 + 1);
+    }
+
+    let counter = EdgesCounter {
+      graph: &graph,
+      edges_calls: Cell::new(0)
+    };
+
+    let expected_order = counter.dijkstra(&center).iter().collect::<Vec<_>>();
+
+    assert_eq!(counter.edges_calls.get(), 1000);
+    assert_eq!(graph.neighbors_with_edges(&center).count(), 2000);
+    assert_eq!(graph.dijkstra(&center).iter().collect::<Vec<_>>(), expected_order);
+  }
+
+  #[test]
+  fn depth_first_search_over_all_components() {
+    use vertex_traverser::{VertexTraverser, PrePostItem};
+
+    let mut graph = FiniteGraph::<usize, ()>::new();
+
+    assert_eq!(graph.dfs_all().iter().count(), 0);
+
+    let vertices = (0..7).map(|i| graph.insert_vertex(i)).collect::<Vec<_>>();
+
+    graph.insert_edge(vertices[0], vertices[1], ()).unwrap();
+    graph.insert_edge(vertices[0], vertices[2], ()).unwrap();
+    graph.insert_edge(vertices[3], vertices[4], ()).unwrap();
+    graph.insert_edge(vertices[4], vertices[3], ()).unwrap();
+    graph.insert_edge(vertices[5], vertices[6], ()).unwrap();
+
+    let mut traverser = graph.dfs_all();
+    let events = traverser.pre_post_iter().collect::<Vec<_>>();
+
+    for &vertex in &vertices {
+      assert_eq!(events.iter().filter(|&item| item == &PrePostItem::PreorderItem(vertex)).count(), 1);
+      assert_eq!(events.iter().filter(|&item| item == &PrePostItem::PostorderItem(vertex)).count(), 1);
+    }
+
+    let roots = vertices.iter()
+      .filter(|&vertex| traverser.predecessor(vertex).is_none())
+      .copied()
+      .collect::<Vec<_>>();
+
+    assert_eq!(roots, vec![vertices[0], vertices[3], vertices[5]]);
+    assert_eq!(events.first(), Some(&PrePostItem::PreorderItem(vertices[0])));
+    assert_eq!(events[5], PrePostItem::PostorderItem(vertices[0]));
+    assert_eq!(events[6], PrePostItem::PreorderItem(vertices[3]));
+    assert_eq!(events.last(), Some(&PrePostItem::PostorderItem(vertices[5])));
+  }
+
+  #[test]
+  fn bfs_ignores_weights_unlike_uniform_cost() {
+    use vertex_traverser::VertexTraverser;
+
+    let mut graph = FiniteGraph::<(), u32>::new();
+
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+
+    graph.insert_edge(a, b, 1).unwrap();
+    graph.insert_edge(b, c, 1).unwrap();
+    graph.insert_edge(a, c, 5).unwrap();
+
+    assert_eq!(graph.bfs(&a).construct_path(&c), Some(vec![a, c]));
+    assert_eq!(graph.uniform_cost(&a).construct_path(&c), Some(vec![a, b, c]));
+    assert_eq!(graph.uniform_cost(&a).construct_path(&c), graph.dijkstra(&a).construct_path(&c));
+  }
 }
@@ -0,0 +1,318 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use super::*;
+
+/// The error returned by [`AcyclicGraph`](struct.AcyclicGraph.html) if a graph contains or
+/// an insertion would create a cycle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CycleError<V> {
+  /// The vertices of the cycle, such that each vertex has an edge to the next one and the last
+  /// vertex has or would get an edge to the first one. The cycle is empty if an endpoint of an
+  /// inserted edge doesn't exist.
+  pub cycle: Vec<V>
+}
+
+impl<V> fmt::Display for CycleError<V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.cycle.is_empty() {
+      write!(f, "edge endpoint doesn't exist")
+    } else {
+      write!(f, "cycle through {} vertices", self.cycle.len())
+    }
+  }
+}
+
+impl<V: fmt::Debug> Error for CycleError<V> {}
+
+/// A wrapper around a [`FiniteGraph`](struct.FiniteGraph.html) that stays acyclic and
+/// maintains a topological order of its vertices.
+///
+/// Edges are inserted with [`insert_edge_acyclic`](#method.insert_edge_acyclic), which only
+/// reorders the vertices between the endpoints of the new edge in the current order, as in the
+/// algorithm by [Pearce and Kelly](https://doi.org/10.1145/1187436.1210590). This is much
+/// faster than checking the whole graph for cycles after every insertion, since most
+/// insertions only affect a small part of the order.
+///
+/// # Example
+///
+/// ```
+/// # use graph_iter::{AcyclicGraph, FiniteGraph};
+/// let mut graph = AcyclicGraph::new(FiniteGraph::new()).unwrap();
+/// let a = graph.insert_vertex("a");
+/// let b = graph.insert_vertex("b");
+/// let c = graph.insert_vertex("c");
+///
+/// graph.insert_edge_acyclic(c, b, ()).unwrap();
+/// graph.insert_edge_acyclic(b, a, ()).unwrap();
+///
+/// assert_eq!(graph.current_topological_order(), vec![c, b, a]);
+/// assert_eq!(graph.insert_edge_acyclic(a, c, ()).unwrap_err().cycle, vec![c, b, a]);
+/// ```
+#[derive(Clone)]
+pub struct AcyclicGraph<V, E> {
+  graph: FiniteGraph<V, E>,
+  // The positions of the vertices in the topological order, which may have gaps
+  order_map: HashMap<Id, usize>,
+  next_position: usize
+}
+
+impl<V, E> AcyclicGraph<V, E> {
+  /// Starts maintaining a topological order of `graph`, or returns a cycle of `graph` if
+  /// there is one. Bidirectional edges and self-loops are cycles.
+  pub fn new(graph: FiniteGraph<V, E>) -> Result<AcyclicGraph<V, E>, CycleError<Id>> {
+    let mut finished = HashSet::new();
+    let mut on_stack = HashMap::new();
+    let mut postorder = vec![];
+
+    for (root, _) in graph.all_vertices_ordered() {
+      if finished.contains(&root) {
+        continue;
+      }
+
+      let mut stack = vec![(root, 0)];
+
+      on_stack.insert(root, 0);
+
+      while let Some(&mut (vertex, ref mut index)) = stack.last_mut() {
+        let neighbors = graph.neighbors_map.get(&vertex).map_or(&[][..], |neighbors| &neighbors[..]);
+
+        match neighbors.get(*index) {
+          Some(&(neighbor, _)) => {
+            *index += 1;
+
+            if let Some(&depth) = on_stack.get(&neighbor) {
+              return Err(CycleError {
+                cycle: stack[depth..].iter().map(|&(vertex, _)| vertex).collect()
+              });
+            } else if !finished.contains(&neighbor) {
+              on_stack.insert(neighbor, stack.len());
+              stack.push((neighbor, 0));
+            }
+          },
+          None => {
+            stack.pop();
+            on_stack.remove(&vertex);
+            finished.insert(vertex);
+            postorder.push(vertex);
+          }
+        }
+      }
+    }
+
+    let order_map = postorder.iter()
+      .rev()
+      .enumerate()
+      .map(|(position, &vertex)| (vertex, position))
+      .collect::<HashMap<_, _>>();
+
+    Ok(AcyclicGraph {
+      graph,
+      next_position: order_map.len(),
+      order_map
+    })
+  }
+
+  /// Returns the acyclic graph.
+  pub fn graph(&self) -> &FiniteGraph<V, E> {
+    &self.graph
+  }
+
+  /// Stops maintaining the topological order and returns the graph.
+  pub fn into_inner(self) -> FiniteGraph<V, E> {
+    self.graph
+  }
+
+  /// Returns all vertices in the maintained topological order, i.e. every edge goes from a
+  /// vertex to a later vertex.
+  pub fn current_topological_order(&self) -> Vec<Id> {
+    let mut order = self.order_map.iter()
+      .map(|(&vertex, &position)| (position, vertex))
+      .collect::<Vec<_>>();
+
+    order.sort_unstable();
+    order.into_iter().map(|(_, vertex)| vertex).collect()
+  }
+
+  /// Inserts a vertex at the end of the topological order, see
+  /// [`FiniteGraph::insert_vertex`](struct.FiniteGraph.html#method.insert_vertex).
+  pub fn insert_vertex(&mut self, value: V) -> Id {
+    let vertex = self.graph.insert_vertex(value);
+
+    self.order_map.insert(vertex, self.next_position);
+    self.next_position += 1;
+
+    vertex
+  }
+
+  /// See [`FiniteGraph::remove_vertex`](struct.FiniteGraph.html#method.remove_vertex).
+  pub fn remove_vertex(&mut self, vertex: Id) -> Option<V> {
+    self.order_map.remove(&vertex);
+    self.graph.remove_vertex(vertex)
+  }
+
+  /// See [`FiniteGraph::remove_edge`](struct.FiniteGraph.html#method.remove_edge).
+  pub fn remove_edge(&mut self, edge: Id) -> Option<E> {
+    self.graph.remove_edge(edge)
+  }
+
+  /// Returns the vertices reachable from `start` in the direction of the edges if `forward`
+  /// is `true`, or against it otherwise, that lie within `bounds` of the topological order,
+  /// together with the vertex each one has been reached from.
+  fn affected_vertices(&self, start: Id, forward: bool, bounds: (usize, usize)) -> HashMap<Id, Option<Id>> {
+    let map = if forward { &self.graph.neighbors_map } else { &self.graph.reverse_neighbors_map };
+    let mut parent_map = HashMap::new();
+    let mut stack = vec![start];
+
+    parent_map.insert(start, None);
+
+    while let Some(vertex) = stack.pop() {
+      for &(neighbor, _) in map.get(&vertex).into_iter().flatten() {
+        let position = self.order_map[&neighbor];
+
+        if position >= bounds.0 && position <= bounds.1 && !parent_map.contains_key(&neighbor) {
+          parent_map.insert(neighbor, Some(vertex));
+          stack.push(neighbor);
+        }
+      }
+    }
+
+    parent_map
+  }
+
+  /// Inserts an edge from `from` to `to` if this doesn't create a cycle, updating the
+  /// topological order, and returns the edge. Otherwise, the graph is left unchanged and the
+  /// existing path from `to` to `from` is returned, which the edge would close into a cycle.
+  ///
+  /// Only the vertices between `to` and `from` in the current order are visited and
+  /// reordered, by moving the vertices that reach `from` before the vertices reachable from
+  /// `to`. If `to` already comes after `from`, the edge is inserted in constant time.
+  pub fn insert_edge_acyclic(&mut self, from: Id, to: Id, value: E) -> Result<Id, CycleError<Id>> {
+    let (upper, lower) = match (self.order_map.get(&from), self.order_map.get(&to)) {
+      (Some(&upper), Some(&lower)) => (upper, lower),
+      _ => return Err(CycleError { cycle: vec![] })
+    };
+
+    if from == to {
+      return Err(CycleError { cycle: vec![from] });
+    }
+
+    if lower < upper {
+      let forward = self.affected_vertices(to, true, (lower, upper));
+
+      if forward.contains_key(&from) {
+        let mut cycle = vec![from];
+
+        while let Some(&Some(parent)) = forward.get(cycle.last().unwrap()) {
+          cycle.push(parent);
+        }
+
+        cycle.reverse();
+
+        return Err(CycleError { cycle });
+      }
+
+      let backward = self.affected_vertices(from, false, (lower, upper));
+      let sorted = |vertices: HashMap<Id, Option<Id>>| {
+        let mut vertices = vertices.into_keys().collect::<Vec<_>>();
+
+        vertices.sort_unstable_by_key(|vertex| self.order_map[vertex]);
+        vertices
+      };
+      let vertices = [sorted(backward), sorted(forward)].concat();
+      let mut positions = vertices.iter().map(|vertex| self.order_map[vertex]).collect::<Vec<_>>();
+
+      positions.sort_unstable();
+
+      for (vertex, position) in vertices.into_iter().zip(positions) {
+        self.order_map.insert(vertex, position);
+      }
+    }
+
+    Ok(self.graph.insert_edge(from, to, value).unwrap())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use graph::Graph;
+  use vertex_traverser::VertexTraverser;
+
+  fn assert_topological(graph: &AcyclicGraph<(), ()>) {
+    let order = graph.current_topological_order();
+    let position_map = order.iter()
+      .enumerate()
+      .map(|(position, &vertex)| (vertex, position))
+      .collect::<HashMap<_, _>>();
+
+    assert_eq!(order.len(), graph.graph().len().0);
+
+    for (edge, _) in graph.graph().all_edges_ordered() {
+      let (_, from, to) = graph.graph().edges_map[&edge];
+
+      assert!(position_map[&from] < position_map[&to]);
+    }
+  }
+
+  #[test]
+  fn insert_edge_acyclic_matches_naive_cycle_check() {
+    let mut state = 7u64;
+    let mut random = |n: usize| {
+      state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+      (state >> 33) as usize % n
+    };
+
+    let mut graph = AcyclicGraph::new(FiniteGraph::new()).unwrap();
+    let mut vertices = (0..30).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for step in 0..600 {
+      let (from, to) = (vertices[random(vertices.len())], vertices[random(vertices.len())]);
+      let creates_cycle = graph.graph().bfs(&to).iter().any(|vertex| vertex == from);
+      let len = graph.graph().len();
+
+      match graph.insert_edge_acyclic(from, to, ()) {
+        Ok(_) => assert!(!creates_cycle),
+        Err(CycleError { cycle }) => {
+          assert!(creates_cycle);
+          assert_eq!((cycle[0], cycle.last().copied()), (to, Some(from)));
+          assert!(graph.graph().is_valid_path(&cycle));
+          assert_eq!(graph.graph().len(), len);
+        }
+      }
+
+      if step % 50 == 0 {
+        let vertex = vertices.swap_remove(random(vertices.len()));
+
+        graph.remove_vertex(vertex);
+        vertices.push(graph.insert_vertex(()));
+      }
+
+      assert_topological(&graph);
+    }
+
+    assert_eq!(graph.insert_edge_acyclic(vertices[0], Id(0), ()), Err(CycleError { cycle: vec![] }));
+  }
+
+  #[test]
+  fn new_rejects_cyclic_graphs() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_edge(v[2], v[1], ()).unwrap();
+    graph.insert_edge(v[1], v[0], ()).unwrap();
+    graph.insert_edge(v[3], v[1], ()).unwrap();
+
+    let acyclic = AcyclicGraph::new(graph.clone()).unwrap();
+
+    assert_topological(&acyclic);
+
+    let edge = graph.insert_bi_edge(v[3], v[0], ()).unwrap();
+
+    assert_eq!(AcyclicGraph::new(graph.clone()).err().unwrap().cycle, vec![v[0], v[3], v[1]]);
+
+    graph.remove_edge(edge);
+    graph.insert_edge(v[0], v[0], ()).unwrap();
+
+    assert_eq!(AcyclicGraph::new(graph).err().unwrap().cycle, vec![v[0]]);
+  }
+}
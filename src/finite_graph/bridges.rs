@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+use super::*;
+
+/// The role of an edge for the connectivity of a graph as computed by
+/// [`FiniteGraph::classify_edges`](struct.FiniteGraph.html#method.classify_edges).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum EdgeKind {
+  /// Removing the edge disconnects its endpoints, so there is no alternative route.
+  Bridge,
+  /// The edge lies on a cycle, so its endpoints stay connected if it is removed.
+  CycleMember,
+  /// The edge starts and ends at the same vertex.
+  SelfLoop
+}
+
+/// A tree edge of the depth-first search in `low_link_search`, reported as soon as the
+/// subtree of `child` has been finished.
+struct TreeEdge {
+  /// The root of the depth-first search tree.
+  root: Id,
+  parent: Id,
+  edge: Id,
+  /// The earliest discovery time reachable from the subtree of the child via a back edge.
+  low: usize,
+  parent_discovery: usize
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Classifies every edge as a [`Bridge`](enum.EdgeKind.html#variant.Bridge), a
+  /// [`CycleMember`](enum.EdgeKind.html#variant.CycleMember), or a
+  /// [`SelfLoop`](enum.EdgeKind.html#variant.SelfLoop), ignoring edge directions. Parallel
+  /// edges are never bridges.
+  ///
+  /// Bridges are found with a single depth-first search that compares discovery times with
+  /// the earliest vertex reachable via a back edge, which takes `O(V + E)` time. The result can
+  /// be reused to [`annotate_path`](#method.annotate_path) as long as the graph isn't modified.
+  pub fn classify_edges(&self) -> HashMap<Id, EdgeKind> {
+    let mut bridges = vec![];
+
+    self.low_link_search(|tree_edge| {
+      if tree_edge.low > tree_edge.parent_discovery {
+        bridges.push(tree_edge.edge);
+      }
+    });
+
+    let mut result = self.edges_map.iter()
+      .map(|(&edge, &(_, from, to))| {
+        (edge, if from == to { EdgeKind::SelfLoop } else { EdgeKind::CycleMember })
+      })
+      .collect::<HashMap<_, _>>();
+
+    for edge in bridges {
+      result.insert(edge, EdgeKind::Bridge);
+    }
+
+    result
+  }
+
+  /// Returns the articulation points in ascending order, i.e. the vertices whose removal
+  /// increases the number of connected components, ignoring edge directions and self-loops.
+  ///
+  /// This is the algorithm by [Hopcroft and Tarjan](https://doi.org/10.1145/362248.362272)
+  /// with an explicit stack, so it takes `O(V + E)` time and doesn't overflow on deep graphs.
+  /// A vertex other than the root of a depth-first search is an articulation point if the
+  /// subtree of one of its children has no back edge to an earlier vertex, and a root is one
+  /// if it has more than one child.
+  pub fn articulation_points_fast(&self) -> Vec<Id> {
+    let mut articulation_points = HashSet::new();
+    let mut root_children = HashMap::new();
+
+    self.low_link_search(|tree_edge| {
+      if tree_edge.parent == tree_edge.root {
+        *root_children.entry(tree_edge.root).or_insert(0) += 1;
+      } else if tree_edge.low >= tree_edge.parent_discovery {
+        articulation_points.insert(tree_edge.parent);
+      }
+    });
+
+    articulation_points.extend(
+      root_children.into_iter()
+      .filter(|&(_, children)| children > 1)
+      .map(|(root, _)| root)
+    );
+
+    let mut result = articulation_points.into_iter().collect::<Vec<_>>();
+
+    result.sort();
+    result
+  }
+
+  /// Runs a depth-first search from every undiscovered vertex in ascending order, ignoring
+  /// edge directions and self-loops, and tracks the discovery times together with the earliest
+  /// discovery time reachable via a back edge. Every tree edge is passed to `on_tree_edge` once
+  /// the subtree below it is finished. The search uses an explicit stack, so it takes
+  /// `O(V + E)` time and doesn't overflow on deep graphs.
+  fn low_link_search(&self, mut on_tree_edge: impl FnMut(TreeEdge)) {
+    let mut discovery_map = HashMap::with_capacity(self.vertices_map.len());
+    let mut low_map = HashMap::with_capacity(self.vertices_map.len());
+
+    for (root, _) in self.all_vertices_ordered() {
+      if discovery_map.contains_key(&root) {
+        continue;
+      }
+
+      discovery_map.insert(root, discovery_map.len());
+      low_map.insert(root, discovery_map[&root]);
+
+      // Each stack entry holds a vertex, the edge it has been entered by, and the iterator over
+      // its remaining incident edges
+      let mut stack = vec![(root, None, self.undirected_neighbors(root))];
+
+      while let Some((vertex, parent_edge, neighbors)) = stack.last_mut() {
+        let (vertex, parent_edge) = (*vertex, *parent_edge);
+
+        match neighbors.next() {
+          Some((neighbor, edge)) => {
+            if parent_edge == Some(edge) || neighbor == vertex {
+              continue;
+            }
+
+            match discovery_map.get(&neighbor) {
+              Some(&discovery) => {
+                let low = low_map.get_mut(&vertex).unwrap();
+                *low = discovery.min(*low);
+              },
+              None => {
+                discovery_map.insert(neighbor, discovery_map.len());
+                low_map.insert(neighbor, discovery_map[&neighbor]);
+                stack.push((neighbor, Some(edge), self.undirected_neighbors(neighbor)));
+              }
+            }
+          },
+          None => {
+            stack.pop();
+
+            if let (Some(edge), Some((parent, _, _))) = (parent_edge, stack.last()) {
+              let low = low_map[&vertex];
+              let parent_low = low_map.get_mut(parent).unwrap();
+
+              *parent_low = low.min(*parent_low);
+              on_tree_edge(TreeEdge {root, parent: *parent, edge, low, parent_discovery: discovery_map[parent]});
+            }
+          }
+        }
+      }
+    }
+  }
+
+  /// Annotates every hop of the vertex sequence `path` with an edge it can take and the kind
+  /// of that edge according to `classification`, which is usually obtained once from
+  /// [`classify_edges`](#method.classify_edges). Among parallel edges, the edge with the
+  /// smallest id is chosen.
+  ///
+  /// Returns `None` if a hop has no edge, i.e. `path` is not a valid path, or an edge is missing
+  /// in `classification`.
+  pub fn annotate_path(&self, path: &[Id], classification: &HashMap<Id, EdgeKind>) -> Option<Vec<(Id, EdgeKind)>> {
+    path.windows(2)
+    .map(|pair| {
+      let edge = self.neighbors_map.get(&pair[0])?.iter()
+        .filter(|&&(neighbor, _)| neighbor == pair[1])
+        .map(|&(_, edge)| edge)
+        .min()?;
+
+      classification.get(&edge).map(|&kind| (edge, kind))
+    })
+    .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use vertex_traverser::VertexTraverser;
+
+  #[test]
+  fn classify_edges_and_annotate_path() {
+    // A triangle 0 - 1 - 2, the bridges 2 - 3 and 3 - 4, two opposite edges between 4 and 5,
+    // and a self-loop at the isolated vertex 6
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..7).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    let e01 = graph.insert_bi_edge(v[0], v[1], ()).unwrap();
+    let e12 = graph.insert_edge(v[1], v[2], ()).unwrap();
+    let e20 = graph.insert_edge(v[2], v[0], ()).unwrap();
+    let e23 = graph.insert_bi_edge(v[2], v[3], ()).unwrap();
+    let e34 = graph.insert_edge(v[4], v[3], ()).unwrap();
+    let e45 = graph.insert_edge(v[4], v[5], ()).unwrap();
+    let e54 = graph.insert_edge(v[5], v[4], ()).unwrap();
+    let loop_edge = graph.insert_edge(v[6], v[6], ()).unwrap();
+
+    let classification = graph.classify_edges();
+
+    assert_eq!(classification.len(), 8);
+
+    for &edge in &[e01, e12, e20, e45, e54] {
+      assert_eq!(classification[&edge], EdgeKind::CycleMember);
+    }
+
+    assert_eq!(classification[&e23], EdgeKind::Bridge);
+    assert_eq!(classification[&e34], EdgeKind::Bridge);
+    assert_eq!(classification[&loop_edge], EdgeKind::SelfLoop);
+
+    let path = graph.bfs(&v[1]).construct_path(&v[3]).unwrap();
+
+    assert_eq!(
+      graph.annotate_path(&path, &classification),
+      Some(vec![(e12, EdgeKind::CycleMember), (e23, EdgeKind::Bridge)])
+    );
+    assert_eq!(graph.annotate_path(&[v[3], v[4]], &classification), None);
+    assert_eq!(graph.annotate_path(&[v[6]], &classification), Some(vec![]));
+  }
+
+  /// Returns the articulation points by checking for every vertex whether its neighbors stay
+  /// connected without it, which takes `O(V * (V + E))` time.
+  fn naive_articulation_points<V, E>(graph: &FiniteGraph<V, E>) -> Vec<Id> {
+    graph.all_vertices_ordered()
+    .map(|(vertex, _)| vertex)
+    .filter(|&vertex| {
+      let neighbors = graph.undirected_neighbors(vertex)
+        .map(|(neighbor, _)| neighbor)
+        .filter(|&neighbor| neighbor != vertex)
+        .collect::<HashSet<_>>();
+      let start = match neighbors.iter().next() {
+        Some(&start) => start,
+        None => return false
+      };
+      let mut visited = vec![vertex, start].into_iter().collect::<HashSet<_>>();
+      let mut stack = vec![start];
+
+      while let Some(current) = stack.pop() {
+        for (neighbor, _) in graph.undirected_neighbors(current) {
+          if visited.insert(neighbor) {
+            stack.push(neighbor);
+          }
+        }
+      }
+
+      !neighbors.is_subset(&visited)
+    })
+    .collect()
+  }
+
+  #[test]
+  fn articulation_points_fast_matches_naive_approach() {
+    let mut state = 11u64;
+    let mut random = |n: usize| {
+      state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+      (state >> 33) as usize % n
+    };
+
+    for &(num_vertices, num_edges) in &[(1, 2), (8, 6), (20, 19), (40, 45), (60, 120)] {
+      let mut graph = FiniteGraph::<(), ()>::new();
+      let v = (0..num_vertices).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+      for _ in 0..num_edges {
+        let (from, to) = (v[random(num_vertices)], v[random(num_vertices)]);
+
+        if random(3) == 0 {
+          graph.insert_bi_edge(from, to, ()).unwrap();
+        } else {
+          graph.insert_edge(from, to, ()).unwrap();
+        }
+      }
+
+      if num_vertices > 2 {
+        graph.remove_vertex(v[random(num_vertices)]);
+      }
+
+      assert_eq!(graph.articulation_points_fast(), naive_articulation_points(&graph));
+    }
+
+    // Two triangles sharing vertex 2, with a pendant path 4 - 5 - 6 hanging off vertex 4
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..7).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for &(from, to) in &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (4, 5), (5, 6), (6, 5)] {
+      graph.insert_edge(v[from], v[to], ()).unwrap();
+    }
+
+    assert_eq!(graph.articulation_points_fast(), vec![v[2], v[4], v[5]]);
+    assert_eq!(FiniteGraph::<(), ()>::new().articulation_points_fast(), vec![]);
+  }
+
+  #[test]
+  fn articulation_points_fast_handles_large_graphs() {
+    // A path of 200000 vertices with a triangle at each end, which would take hours with the
+    // naive approach and overflow the stack with a recursive search
+    let n = 200_000;
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..n).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for pair in v.windows(2) {
+      graph.insert_edge(pair[1], pair[0], ()).unwrap();
+    }
+
+    graph.insert_edge(v[0], v[2], ()).unwrap();
+    graph.insert_edge(v[n - 3], v[n - 1], ()).unwrap();
+
+    let articulation_points = graph.articulation_points_fast();
+
+    assert_eq!(articulation_points, v[2..n - 2].to_vec());
+  }
+}
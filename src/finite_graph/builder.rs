@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::error::Error;
+use super::*;
+
+/// Determines how [`FiniteGraphBuilder`](struct.FiniteGraphBuilder.html) handles a vertex key
+/// that has been added before.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum DuplicateKeys {
+  /// Keeps the first value and reports the key when finishing.
+  #[default]
+  Error,
+  /// Merges the vertices, keeping the first value.
+  KeepFirst,
+  /// Merges the vertices, replacing the value by the last one.
+  KeepLast
+}
+
+/// The problems found by [`FiniteGraphBuilder::finish`](struct.FiniteGraphBuilder.html#method.finish).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuildError<K> {
+  /// The keys that have been added more than once, in the order the duplicates were added.
+  pub duplicate_keys: Vec<K>,
+  /// The `(from, to)` keys of edges with at least one endpoint that has never been added, in
+  /// the order the edges were added.
+  pub dangling_edges: Vec<(K, K)>
+}
+
+impl<K> fmt::Display for BuildError<K> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} duplicate vertex keys and {} edges with missing endpoints",
+      self.duplicate_keys.len(),
+      self.dangling_edges.len()
+    )
+  }
+}
+
+impl<K: fmt::Debug> Error for BuildError<K> {}
+
+/// The result of [`FiniteGraphBuilder::finish`](struct.FiniteGraphBuilder.html#method.finish).
+type BuildResult<K, V, E> = Result<(FiniteGraph<V, E>, HashMap<K, Id>), BuildError<K>>;
+
+/// Builds a [`FiniteGraph`](struct.FiniteGraph.html) from vertices and edges that are
+/// identified by keys of type `K` and may arrive in any order.
+///
+/// Edges whose endpoints haven't been added yet are buffered and inserted as soon as both
+/// endpoints are known, so every edge is buffered at most as long as necessary.
+///
+/// # Example
+///
+/// ```
+/// # use graph_iter::{FiniteGraphBuilder, Graph};
+/// let mut builder = FiniteGraphBuilder::new();
+///
+/// builder.add_edge("a", "b", 1);
+/// builder.add_vertex("a", 'a');
+/// builder.add_edge("b", "a", 2);
+/// assert_eq!(builder.pending_edges(), 2);
+///
+/// builder.add_vertex("b", 'b');
+/// assert_eq!(builder.pending_edges(), 0);
+///
+/// let (graph, ids) = builder.finish().unwrap();
+///
+/// assert_eq!(graph.len(), (2, 2));
+/// assert_eq!(graph.neighbors(&ids["a"]), vec![ids["b"]]);
+/// ```
+pub struct FiniteGraphBuilder<K, V, E> {
+  graph: FiniteGraph<V, E>,
+  duplicate_keys: DuplicateKeys,
+  id_map: HashMap<K, Id>,
+  // Buffered edges are stored under one of their missing endpoints, together with a sequence
+  // number to report them in order
+  pending_map: HashMap<K, Vec<(usize, K, K, E)>>,
+  pending_count: usize,
+  sequence: usize,
+  duplicates: Vec<K>
+}
+
+impl<K: Hash + Eq + Clone, V, E> Default for FiniteGraphBuilder<K, V, E> {
+  fn default() -> FiniteGraphBuilder<K, V, E> {
+    FiniteGraphBuilder::new()
+  }
+}
+
+impl<K: Hash + Eq + Clone, V, E> FiniteGraphBuilder<K, V, E> {
+  /// Creates an empty builder that reports duplicate keys as errors.
+  pub fn new() -> FiniteGraphBuilder<K, V, E> {
+    FiniteGraphBuilder::with_duplicate_keys(DuplicateKeys::Error)
+  }
+
+  /// Creates an empty builder that handles duplicate keys as specified.
+  pub fn with_duplicate_keys(duplicate_keys: DuplicateKeys) -> FiniteGraphBuilder<K, V, E> {
+    FiniteGraphBuilder {
+      graph: FiniteGraph::new(),
+      duplicate_keys,
+      id_map: HashMap::new(),
+      pending_map: HashMap::new(),
+      pending_count: 0,
+      sequence: 0,
+      duplicates: vec![]
+    }
+  }
+
+  /// Returns the number of buffered edges that are waiting for their endpoints.
+  pub fn pending_edges(&self) -> usize {
+    self.pending_count
+  }
+
+  /// Returns the vertex of `key` if it has been added already.
+  pub fn get_id(&self, key: &K) -> Option<Id> {
+    self.id_map.get(key).copied()
+  }
+
+  /// Adds a vertex and inserts all buffered edges that have been waiting for it, returning the
+  /// vertex. If `key` has been added before, the existing vertex is returned and handled
+  /// according to [`DuplicateKeys`](enum.DuplicateKeys.html).
+  pub fn add_vertex(&mut self, key: K, value: V) -> Id {
+    if let Some(&vertex) = self.id_map.get(&key) {
+      match self.duplicate_keys {
+        DuplicateKeys::Error => self.duplicates.push(key),
+        DuplicateKeys::KeepFirst => {},
+        DuplicateKeys::KeepLast => *self.graph.get_vertex_mut(vertex).unwrap() = value
+      }
+
+      return vertex;
+    }
+
+    let vertex = self.graph.insert_vertex(value);
+
+    self.id_map.insert(key.clone(), vertex);
+
+    for (sequence, from, to, value) in self.pending_map.remove(&key).unwrap_or_default() {
+      self.pending_count -= 1;
+      self.insert_or_buffer(sequence, from, to, value);
+    }
+
+    vertex
+  }
+
+  /// Adds an edge from the vertex of `from` to the vertex of `to`. Returns the edge if both
+  /// vertices have been added already, otherwise the edge is buffered and `None` is returned.
+  pub fn add_edge(&mut self, from: K, to: K, value: E) -> Option<Id> {
+    let sequence = self.sequence;
+
+    self.sequence += 1;
+    self.insert_or_buffer(sequence, from, to, value)
+  }
+
+  fn insert_or_buffer(&mut self, sequence: usize, from: K, to: K, value: E) -> Option<Id> {
+    let missing = match (self.id_map.get(&from), self.id_map.get(&to)) {
+      (Some(&from), Some(&to)) => return self.graph.insert_edge(from, to, value),
+      (None, _) => from.clone(),
+      (_, None) => to.clone()
+    };
+
+    self.pending_count += 1;
+    self.pending_map.entry(missing).or_default().push((sequence, from, to, value));
+
+    None
+  }
+
+  /// Returns the graph together with the vertices of all keys, or the duplicate keys and the
+  /// buffered edges if there are any.
+  pub fn finish(self) -> BuildResult<K, V, E> {
+    if self.duplicates.is_empty() && self.pending_count == 0 {
+      return Ok((self.graph, self.id_map));
+    }
+
+    let mut dangling_edges = self.pending_map.into_values()
+      .flatten()
+      .map(|(sequence, from, to, _)| (sequence, from, to))
+      .collect::<Vec<_>>();
+
+    dangling_edges.sort_by_key(|&(sequence, _, _)| sequence);
+
+    Err(BuildError {
+      duplicate_keys: self.duplicates,
+      dangling_edges: dangling_edges.into_iter().map(|(_, from, to)| (from, to)).collect()
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn builder_buffers_dangling_edges() {
+    let mut builder = FiniteGraphBuilder::new();
+
+    assert_eq!(builder.add_edge(1, 2, "1-2"), None);
+    assert_eq!(builder.add_edge(3, 3, "3-3"), None);
+    assert_eq!(builder.add_edge(2, 4, "2-4"), None);
+
+    let one = builder.add_vertex(1, 'a');
+
+    assert_eq!(builder.pending_edges(), 3);
+
+    builder.add_vertex(2, 'b');
+
+    assert_eq!(builder.pending_edges(), 2);
+    assert_eq!(builder.add_vertex(1, 'c'), one);
+    assert_eq!(builder.get_id(&3), None);
+    assert!(builder.add_edge(2, 1, "2-1").is_some());
+
+    let error = builder.finish().unwrap_err();
+
+    assert_eq!(error.duplicate_keys, vec![1]);
+    assert_eq!(error.dangling_edges, vec![(3, 3), (2, 4)]);
+    assert_eq!(error.to_string(), "1 duplicate vertex keys and 2 edges with missing endpoints");
+  }
+
+  #[test]
+  fn builder_merges_duplicate_keys() {
+    for &(duplicate_keys, value) in &[(DuplicateKeys::KeepFirst, 'a'), (DuplicateKeys::KeepLast, 'c')] {
+      let mut builder = FiniteGraphBuilder::with_duplicate_keys(duplicate_keys);
+
+      builder.add_edge("x", "x", ());
+      builder.add_vertex("x", 'a');
+      builder.add_vertex("y", 'b');
+      builder.add_vertex("x", 'c');
+      builder.add_edge("x", "y", ());
+
+      let (graph, ids) = builder.finish().unwrap();
+
+      assert_eq!(graph.len(), (2, 2));
+      assert_eq!(graph.get_vertex(ids["x"]), Some(&value));
+      assert_eq!(graph.out_degree(ids["x"]), 2);
+    }
+  }
+}
@@ -0,0 +1,360 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hasher;
+use super::*;
+
+/// The largest number of vertices
+/// [`FiniteGraph::canonical_form_exact`](struct.FiniteGraph.html#method.canonical_form_exact)
+/// accepts.
+pub const EXACT_CANONICAL_FORM_LIMIT: usize = 8;
+
+/// A representation of a graph that is equal for two graphs if and only if they are
+/// isomorphic, as returned by
+/// [`FiniteGraph::canonical_form_exact`](struct.FiniteGraph.html#method.canonical_form_exact).
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CanonicalForm<'a, V, E> {
+  /// The vertex values in canonical order.
+  pub vertices: Vec<&'a V>,
+  /// The edges as sorted `(from, to, bidirectional, value)` tuples, where `from` and `to` are
+  /// indices into `vertices`. Bidirectional edges are listed with `from <= to`.
+  pub edges: Vec<(usize, usize, bool, &'a E)>
+}
+
+fn hash_of(value: impl Hash) -> u64 {
+  let mut hasher = DefaultHasher::new();
+
+  value.hash(&mut hasher);
+  hasher.finish()
+}
+
+/// Calls `visit` with every ordering of the vertices which lists the classes one after another,
+/// in any order within each class.
+fn for_each_ordering(classes: &mut [Vec<Id>], order: &mut Vec<Id>, visit: &mut impl FnMut(&[Id])) {
+  let index = match classes.iter().position(|class| !class.is_empty()) {
+    Some(index) => index,
+    None => return visit(order)
+  };
+
+  for i in 0..classes[index].len() {
+    let vertex = classes[index].swap_remove(i);
+
+    order.push(vertex);
+    for_each_ordering(classes, order, visit);
+    order.pop();
+
+    // Undo the removal, restoring the original order of the class
+    let class = &mut classes[index];
+    let last = class.len();
+
+    class.push(vertex);
+    class.swap(i, last);
+  }
+}
+
+/// Bidirectional edges are encoded like this in the edge signatures.
+const BIDIRECTIONAL: u8 = 2;
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns the edges as `(from, to, bidirectional, key)` tuples.
+  fn keyed_edges(&self, edge_key: impl Fn(&E) -> u64) -> Vec<(Id, Id, bool, u64)> {
+    self.edges_map.iter()
+    .map(|(&edge, (data, from, to))| (*from, *to, self.is_bi_edge(edge, *from, *to), edge_key(data)))
+    .collect()
+  }
+
+  /// Assigns colors to the vertices by Weisfeiler-Lehman color refinement, i.e. starting with
+  /// the vertex keys, repeatedly recolors each vertex by its color and the multiset of its
+  /// incident edge keys, directions, and neighbor colors until the number of colors stops
+  /// growing. Colors don't depend on ids, so isomorphic graphs get the same colors.
+  fn refine_colors(&self, vertex_key: impl Fn(&V) -> u64, edges: &[(Id, Id, bool, u64)]) -> HashMap<Id, u64> {
+    let mut colors = self.vertices_map.iter()
+      .map(|(&vertex, value)| (vertex, hash_of(vertex_key(value))))
+      .collect::<HashMap<_, _>>();
+    let mut color_count = colors.values().collect::<HashSet<_>>().len();
+
+    for _ in 0..self.vertices_map.len() {
+      let mut signatures = self.vertices_map.keys()
+        .map(|&vertex| (vertex, vec![]))
+        .collect::<HashMap<_, _>>();
+
+      for &(from, to, bidirectional, key) in edges {
+        let (out_tag, in_tag) = if bidirectional { (BIDIRECTIONAL, BIDIRECTIONAL) } else { (0, 1) };
+
+        signatures.get_mut(&from).unwrap().push((out_tag, key, colors[&to]));
+        signatures.get_mut(&to).unwrap().push((in_tag, key, colors[&from]));
+      }
+
+      let refined = signatures.into_iter()
+        .map(|(vertex, mut signature)| {
+          signature.sort_unstable();
+          (vertex, hash_of((colors[&vertex], signature)))
+        })
+        .collect::<HashMap<_, _>>();
+      let refined_count = refined.values().collect::<HashSet<_>>().len();
+
+      if refined_count == color_count {
+        break;
+      }
+
+      colors = refined;
+      color_count = refined_count;
+    }
+
+    colors
+  }
+
+  /// Returns a hash of the graph structure that only depends on the vertex keys, edge keys,
+  /// and edge directions, but not on ids or insertion order, so isomorphic graphs always hash
+  /// equal. Bidirectional edges are distinguished from pairs of opposite edges.
+  ///
+  /// The hash is computed from the vertex colors of Weisfeiler-Lehman color refinement, which
+  /// takes `O((V + E) V log E)` time in the worst case, but usually stabilizes after a few
+  /// rounds. This is a strong heuristic rather than a canonical form: Besides hash collisions,
+  /// some non-isomorphic graphs, e.g. regular graphs with the same degree and size, can't be
+  /// told apart by color refinement and hash equal. Use
+  /// [`canonical_form_exact`](#method.canonical_form_exact) to compare small graphs exactly.
+  /// Hashes are not guaranteed to be stable across Rust versions.
+  pub fn canonical_hash(&self, vertex_key: impl Fn(&V) -> u64, edge_key: impl Fn(&E) -> u64) -> u64 {
+    let edges = self.keyed_edges(edge_key);
+    let colors = self.refine_colors(vertex_key, &edges);
+    let mut vertex_colors = colors.values().copied().collect::<Vec<_>>();
+    let mut edge_colors = edges.into_iter()
+      .map(|(from, to, bidirectional, key)| {
+        let (from, to) = (colors[&from], colors[&to]);
+        let (from, to) = if bidirectional { (from.min(to), from.max(to)) } else { (from, to) };
+
+        (from, to, bidirectional, key)
+      })
+      .collect::<Vec<_>>();
+
+    vertex_colors.sort_unstable();
+    edge_colors.sort_unstable();
+
+    hash_of((vertex_colors, edge_colors))
+  }
+
+  /// Returns the canonical form of the graph, which is equal for two graphs if and only if
+  /// there is a bijection between their vertices that preserves vertex values, edge values, and
+  /// edge directions. Returns `None` if the graph has more than
+  /// [`EXACT_CANONICAL_FORM_LIMIT`](constant.EXACT_CANONICAL_FORM_LIMIT.html) vertices.
+  ///
+  /// The canonical form is the smallest encoding among all vertex orderings that list the
+  /// color classes of [`canonical_hash`](#method.canonical_hash) in a fixed order, which takes
+  /// `O(V! E log E)` time in the worst case.
+  pub fn canonical_form_exact(&self) -> Option<CanonicalForm<'_, V, E>>
+  where V: Ord + Hash, E: Ord + Hash {
+    if self.vertices_map.len() > EXACT_CANONICAL_FORM_LIMIT {
+      return None;
+    }
+
+    let edges = self.keyed_edges(|edge| hash_of(edge));
+    let colors = self.refine_colors(|value| hash_of(value), &edges);
+    let mut class_map = HashMap::<u64, Vec<Id>>::new();
+
+    for (&vertex, &color) in &colors {
+      class_map.entry(color).or_default().push(vertex);
+    }
+
+    let mut classes = class_map.into_iter().collect::<Vec<_>>();
+
+    classes.sort_unstable_by_key(|&(color, _)| color);
+
+    let mut classes = classes.into_iter().map(|(_, class)| class).collect::<Vec<_>>();
+    let mut result = None::<CanonicalForm<'_, V, E>>;
+
+    for_each_ordering(&mut classes, &mut vec![], &mut |order| {
+      let position_map = order.iter()
+        .enumerate()
+        .map(|(position, &vertex)| (vertex, position))
+        .collect::<HashMap<_, _>>();
+      let mut edges = self.edges_map.iter()
+        .map(|(&edge, (data, from, to))| {
+          let (from, to) = (position_map[from], position_map[to]);
+          let bidirectional = self.is_bi_edge(edge, order[from], order[to]);
+          let (from, to) = if bidirectional { (from.min(to), from.max(to)) } else { (from, to) };
+
+          (from, to, bidirectional, data)
+        })
+        .collect::<Vec<_>>();
+
+      edges.sort_unstable();
+
+      let form = CanonicalForm {
+        vertices: order.iter().map(|vertex| &self.vertices_map[vertex]).collect(),
+        edges
+      };
+
+      if result.as_ref().is_none_or(|result| &form < result) {
+        result = Some(form);
+      }
+    });
+
+    result
+  }
+}
+
+/// Two graphs are equal if they have the same vertices and edges with the same ids, values,
+/// endpoints, and directions. The order of neighbors and the ids handed out next are ignored.
+impl<V: PartialEq, E: PartialEq> PartialEq for FiniteGraph<V, E> {
+  fn eq(&self, other: &FiniteGraph<V, E>) -> bool {
+    self.vertices_map == other.vertices_map
+    && self.edges_map == other.edges_map
+    && self.edges_map.iter().all(|(&edge, &(_, from, to))| {
+      self.is_bi_edge(edge, from, to) == other.is_bi_edge(edge, from, to)
+    })
+  }
+}
+
+impl<V: Eq, E: Eq> Eq for FiniteGraph<V, E> {}
+
+/// Hashes the sorted multisets of vertex values and of edges as values of their endpoints,
+/// so the hash doesn't depend on ids or insertion order and graphs that are equal according
+/// to `PartialEq` hash equal. Hashing takes `O((V + E) log(V + E))` time.
+impl<V: Hash, E: Hash> Hash for FiniteGraph<V, E> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    let vertex_hash_map = self.vertices_map.iter()
+      .map(|(&vertex, value)| (vertex, hash_of(value)))
+      .collect::<HashMap<_, _>>();
+    let mut vertex_hashes = vertex_hash_map.values().copied().collect::<Vec<_>>();
+    let mut edges = self.edges_map.iter()
+      .map(|(&edge, (value, from, to))| {
+        let bidirectional = self.is_bi_edge(edge, *from, *to);
+        let (from, to) = (vertex_hash_map[from], vertex_hash_map[to]);
+        let (from, to) = if bidirectional { (from.min(to), from.max(to)) } else { (from, to) };
+
+        (from, to, bidirectional, hash_of(value))
+      })
+      .collect::<Vec<_>>();
+
+    vertex_hashes.sort_unstable();
+    edges.sort_unstable();
+    vertex_hashes.hash(state);
+    edges.hash(state);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Builds a graph from the same structure, inserting vertices and edges in the order given
+  /// by `permutation` and `rotation`.
+  fn build(permutation: &[usize], rotation: usize) -> FiniteGraph<char, u32> {
+    let values = ['C', 'C', 'O', 'N', 'C', 'H'];
+    let edges = [(0, 1, 2, true), (1, 2, 1, true), (2, 3, 1, false), (3, 0, 1, false), (3, 4, 1, true), (4, 5, 1, true)];
+    let mut graph = FiniteGraph::new();
+    let mut ids = vec![None; values.len()];
+
+    for &i in permutation {
+      ids[i] = Some(graph.insert_vertex(values[i]));
+    }
+
+    for i in 0..edges.len() {
+      let (from, to, weight, bidirectional) = edges[(i + rotation) % edges.len()];
+      let (from, to) = (ids[from].unwrap(), ids[to].unwrap());
+
+      if bidirectional {
+        graph.insert_bi_edge(to, from, weight).unwrap();
+      } else {
+        graph.insert_edge(from, to, weight).unwrap();
+      }
+    }
+
+    graph
+  }
+
+  #[test]
+  fn canonical_hash_ignores_insertion_order() {
+    let graph = build(&[0, 1, 2, 3, 4, 5], 0);
+    let key = |&value: &char| value as u64;
+    let edge_key = |&weight: &u32| weight as u64;
+    let hash = graph.canonical_hash(key, edge_key);
+    let form = graph.canonical_form_exact().unwrap();
+
+    for (permutation, rotation) in &[([5, 4, 3, 2, 1, 0], 3), ([2, 0, 5, 1, 3, 4], 1), ([3, 5, 1, 4, 0, 2], 5)] {
+      let other = build(permutation, *rotation);
+
+      assert_eq!(other.canonical_hash(key, edge_key), hash);
+      assert_eq!(other.canonical_form_exact().unwrap(), form);
+    }
+
+    // Changing a direction, an edge value, or a vertex value changes both
+    let mut other = build(&[0, 1, 2, 3, 4, 5], 0);
+    let (edge, from, to) = other.edges_map.iter()
+      .map(|(&edge, &(_, from, to))| (edge, from, to))
+      .find(|&(_, from, to)| other.vertices_map[&from] == 'O' && other.vertices_map[&to] == 'N')
+      .unwrap();
+
+    other.remove_edge(edge);
+    other.insert_edge(to, from, 1).unwrap();
+
+    assert_ne!(other.canonical_hash(key, edge_key), hash);
+    assert_ne!(other.canonical_form_exact().unwrap(), form);
+
+    let mut other = build(&[0, 1, 2, 3, 4, 5], 0);
+
+    *other.get_vertex_mut(other.all_vertices_ordered().last().unwrap().0).unwrap() = 'O';
+
+    assert_ne!(other.canonical_hash(key, edge_key), hash);
+    assert_ne!(other.canonical_form_exact().unwrap(), form);
+    assert_ne!(graph.canonical_hash(key, |_| 0), hash);
+  }
+
+  #[test]
+  fn canonical_form_exact_distinguishes_regular_graphs() {
+    // Two triangles and a hexagon can't be told apart by color refinement
+    let mut triangles = FiniteGraph::<(), ()>::new();
+    let mut hexagon = FiniteGraph::<(), ()>::new();
+    let t = (0..6).map(|_| triangles.insert_vertex(())).collect::<Vec<_>>();
+    let h = (0..6).map(|_| hexagon.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..6 {
+      triangles.insert_bi_edge(t[i], t[i / 3 * 3 + (i + 1) % 3], ()).unwrap();
+      hexagon.insert_bi_edge(h[i], h[(i + 1) % 6], ()).unwrap();
+    }
+
+    assert_eq!(triangles.canonical_hash(|_| 0, |_| 0), hexagon.canonical_hash(|_| 0, |_| 0));
+    assert_ne!(triangles.canonical_form_exact(), hexagon.canonical_form_exact());
+
+    for _ in 0..3 {
+      hexagon.insert_vertex(());
+    }
+
+    assert_eq!(hexagon.canonical_form_exact(), None);
+  }
+
+  #[test]
+  fn graphs_as_hash_set_keys() {
+    let graph = build(&[0, 1, 2, 3, 4, 5], 0);
+    let mut set = HashSet::new();
+
+    set.insert(graph.clone());
+    set.insert(build(&[0, 1, 2, 3, 4, 5], 0));
+
+    assert_eq!(set.len(), 1);
+    assert!(set.contains(&graph));
+
+    // Different ids make graphs unequal, but they still hash equal
+    let reordered = build(&[5, 4, 3, 2, 1, 0], 3);
+
+    assert_ne!(reordered, graph);
+    assert_eq!(hash_of(&reordered), hash_of(&graph));
+
+    let mut other = graph.clone();
+    let (edge, from, to) = other.edges_map.iter()
+      .map(|(&edge, &(_, from, to))| (edge, from, to))
+      .find(|&(edge, from, to)| !other.is_bi_edge(edge, from, to))
+      .unwrap();
+
+    // Turning a directed edge into a bidirectional one
+    other.insert_edge_id(to, from, edge);
+
+    assert_ne!(other, graph);
+
+    other.remove_edge(edge);
+    *other.all_edges_mut().next().unwrap().1 += 1;
+    set.insert(other);
+
+    assert_eq!(set.len(), 2);
+  }
+}
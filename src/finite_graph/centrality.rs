@@ -0,0 +1,433 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+#[cfg(feature = "rand")]
+use rand::RngCore;
+use super::*;
+
+/// Centrality measures of a single vertex as computed by
+/// [`FiniteGraph::vertex_centrality_report`](struct.FiniteGraph.html#method.vertex_centrality_report).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct VertexCentrality {
+  /// The number of incoming and outgoing edges divided by `n - 1`, where `n` is the
+  /// number of vertices.
+  pub degree_centrality: f64,
+  /// The closeness of all other vertices that can reach the vertex, scaled by the fraction
+  /// of vertices that can reach it, so it is comparable across components.
+  pub closeness_centrality: f64,
+  /// The fraction of shortest paths between pairs of other vertices that pass through the
+  /// vertex, summed over all pairs and divided by `(n - 1) * (n - 2)`.
+  pub betweenness_centrality: f64
+}
+
+#[derive(Default)]
+struct CentralitySums {
+  reached_count: f64,
+  distance_sum: f64,
+  betweenness: f64
+}
+
+/// Runs a breadth-first search from `source` along `neighbors`, which yields neighbors
+/// together with the connecting edge, counts shortest paths and accumulates the dependencies
+/// of `source` as described by Brandes. Calls `on_vertex` for every reached vertex other than
+/// `source` with its distance and the dependency of `source` on it, and `on_edge` for every
+/// edge on a shortest path with its share of the dependencies.
+pub(crate) fn brandes_pass<I>(
+  source: Id,
+  neighbors: impl Fn(Id) -> I,
+  mut on_vertex: impl FnMut(Id, usize, f64),
+  mut on_edge: impl FnMut(Id, f64)
+) where I: IntoIterator<Item = (Id, Id)> {
+  let mut stack = vec![];
+  let mut queue = VecDeque::new();
+  let mut distance_map = HashMap::new();
+  let mut path_count_map = HashMap::new();
+  let mut predecessors_map = HashMap::<Id, Vec<(Id, Id)>>::new();
+
+  distance_map.insert(source, 0);
+  path_count_map.insert(source, 1.0);
+  queue.push_back(source);
+
+  while let Some(vertex) = queue.pop_front() {
+    stack.push(vertex);
+
+    let distance = distance_map[&vertex];
+    let path_count = path_count_map[&vertex];
+
+    for (neighbor, edge) in neighbors(vertex) {
+      if let hash_map::Entry::Vacant(entry) = distance_map.entry(neighbor) {
+        entry.insert(distance + 1);
+        queue.push_back(neighbor);
+      }
+
+      if distance_map[&neighbor] == distance + 1 {
+        *path_count_map.entry(neighbor).or_insert(0.0) += path_count;
+        predecessors_map.entry(neighbor).or_default().push((vertex, edge));
+      }
+    }
+  }
+
+  let mut dependency_map = HashMap::<Id, f64>::new();
+
+  while let Some(vertex) = stack.pop() {
+    let dependency = dependency_map.get(&vertex).copied().unwrap_or(0.0);
+
+    for &(predecessor, edge) in predecessors_map.get(&vertex).into_iter().flatten() {
+      let contribution = path_count_map[&predecessor] / path_count_map[&vertex] * (1.0 + dependency);
+
+      *dependency_map.entry(predecessor).or_insert(0.0) += contribution;
+      on_edge(edge, contribution);
+    }
+
+    if vertex != source {
+      on_vertex(vertex, distance_map[&vertex], dependency);
+    }
+  }
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Runs `brandes_pass` from `source` along the edges and adds the results to the sums of
+  /// every reached vertex.
+  fn accumulate_centrality_sums(&self, source: Id, sums_map: &mut HashMap<Id, CentralitySums>) {
+    brandes_pass(
+      source,
+      |vertex| self.neighbors_map.get(&vertex).into_iter().flatten().copied(),
+      |vertex, distance, dependency| {
+        let sums = sums_map.entry(vertex).or_default();
+
+        sums.reached_count += 1.0;
+        sums.distance_sum += distance as f64;
+        sums.betweenness += dependency;
+      },
+      |_, _| {}
+    );
+  }
+
+  /// Turns accumulated sums into centrality measures. `scale` is the factor that
+  /// extrapolates sums over the used sources to sums over all vertices.
+  fn centrality_report_from_sums(
+    &self,
+    sums_map: &HashMap<Id, CentralitySums>,
+    scale: impl Fn(Id) -> f64
+  ) -> HashMap<Id, VertexCentrality> {
+    let n = self.vertices_map.len() as f64;
+
+    self.vertices_map.keys()
+    .map(|&vertex| {
+      let mut centrality = VertexCentrality::default();
+
+      if n > 1.0 {
+        centrality.degree_centrality = (self.out_degree(vertex) + self.in_degree(vertex)) as f64 / (n - 1.0);
+      }
+
+      if let Some(sums) = sums_map.get(&vertex) {
+        let reached_count = sums.reached_count * scale(vertex);
+
+        if sums.distance_sum > 0.0 {
+          centrality.closeness_centrality = (reached_count / (n - 1.0)) * (sums.reached_count / sums.distance_sum);
+        }
+
+        if n > 2.0 {
+          centrality.betweenness_centrality = sums.betweenness * scale(vertex) / ((n - 1.0) * (n - 2.0));
+        }
+      }
+
+      (vertex, centrality)
+    })
+    .collect()
+  }
+
+  /// Computes degree, closeness, and betweenness centrality of all vertices at once, using a
+  /// single breadth-first search per vertex as in
+  /// [Brandes' algorithm](https://doi.org/10.1080/0022250X.2001.9990249).
+  ///
+  /// Edges are unweighted and followed in their direction, so the closeness of a vertex
+  /// is based on the distances from other vertices to it. Parallel edges count as distinct
+  /// shortest paths. This takes `O(V * E)` time.
+  pub fn vertex_centrality_report(&self) -> HashMap<Id, VertexCentrality> {
+    let mut sums_map = HashMap::new();
+
+    for &source in self.vertices_map.keys() {
+      self.accumulate_centrality_sums(source, &mut sums_map);
+    }
+
+    self.centrality_report_from_sums(&sums_map, |_| 1.0)
+  }
+
+  /// Approximates [`vertex_centrality_report`](#method.vertex_centrality_report) by only running
+  /// breadth-first searches from `sample_size` randomly chosen vertices and extrapolating the
+  /// results. Degree centrality is always exact.
+  #[cfg(feature = "rand")]
+  pub fn sample_vertex_centrality(&self, sample_size: usize, mut rng: impl RngCore) -> HashMap<Id, VertexCentrality> {
+    let vertices = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    let sample_size = sample_size.min(vertices.len());
+    let sample = rand::seq::index::sample(&mut rng, vertices.len(), sample_size)
+      .into_iter()
+      .map(|index| vertices[index])
+      .collect::<Vec<_>>();
+
+    let mut sums_map = HashMap::new();
+
+    for &source in &sample {
+      self.accumulate_centrality_sums(source, &mut sums_map);
+    }
+
+    let n = vertices.len() as f64;
+
+    self.centrality_report_from_sums(&sums_map, |vertex| {
+      let other_sources = if sample.contains(&vertex) { sample_size - 1 } else { sample_size };
+      (n - 1.0) / other_sources as f64
+    })
+  }
+}
+
+impl<V, E: WeightedEdge> FiniteGraph<V, E> {
+  /// Runs Dijkstra's algorithm from `source` and adds the dependencies of `source` on every
+  /// vertex to `betweenness_map`.
+  ///
+  /// Path counts are only propagated once the costs are final, in topological order of the
+  /// shortest path graph, since a zero-weight edge can add a predecessor to a vertex that has
+  /// the same cost and has therefore already been settled by Dijkstra's algorithm.
+  fn accumulate_weighted_betweenness(&self, source: Id, betweenness_map: &mut HashMap<Id, f64>) {
+    let mut settled = vec![];
+    let mut heap = BinaryHeap::new();
+    let mut cost_map = HashMap::new();
+
+    cost_map.insert(source, E::default());
+    heap.push((Reverse(E::default()), source));
+
+    while let Some((Reverse(cost), vertex)) = heap.pop() {
+      if cost_map[&vertex] < cost {
+        continue;
+      }
+
+      settled.push(vertex);
+
+      for &(neighbor, edge) in self.neighbors_map.get(&vertex).into_iter().flatten() {
+        let new_cost = cost.clone() + self.edges_map[&edge].0.clone();
+
+        if cost_map.get(&neighbor).is_none_or(|old_cost| &new_cost < old_cost) {
+          cost_map.insert(neighbor, new_cost.clone());
+          heap.push((Reverse(new_cost), neighbor));
+        }
+      }
+    }
+
+    // Costs are compared as `E` instead of `f64`, so equal sums are recognized as ties
+    let mut predecessors_map = HashMap::<Id, Vec<Id>>::new();
+    let mut successors_map = HashMap::<Id, Vec<Id>>::new();
+
+    for &vertex in &settled {
+      for &(neighbor, edge) in self.neighbors_map.get(&vertex).into_iter().flatten() {
+        if neighbor != vertex && cost_map[&vertex].clone() + self.edges_map[&edge].0.clone() == cost_map[&neighbor] {
+          predecessors_map.entry(neighbor).or_default().push(vertex);
+          successors_map.entry(vertex).or_default().push(neighbor);
+        }
+      }
+    }
+
+    let mut in_degree_map = predecessors_map.iter()
+      .map(|(&vertex, predecessors)| (vertex, predecessors.len()))
+      .collect::<HashMap<_, _>>();
+    let mut path_count_map = HashMap::new();
+    let mut stack = vec![source];
+    let mut index = 0;
+
+    path_count_map.insert(source, 1.0);
+
+    while let Some(&vertex) = stack.get(index) {
+      let path_count = path_count_map[&vertex];
+
+      for &successor in successors_map.get(&vertex).into_iter().flatten() {
+        *path_count_map.entry(successor).or_insert(0.0) += path_count;
+
+        let in_degree = in_degree_map.get_mut(&successor).unwrap();
+
+        *in_degree -= 1;
+
+        if *in_degree == 0 {
+          stack.push(successor);
+        }
+      }
+
+      index += 1;
+    }
+
+    debug_assert_eq!(stack.len(), settled.len(), "cycles of zero-weight edges are not supported");
+
+    let mut dependency_map = HashMap::<Id, f64>::new();
+
+    while let Some(vertex) = stack.pop() {
+      let dependency = dependency_map.get(&vertex).copied().unwrap_or(0.0);
+
+      for &predecessor in predecessors_map.get(&vertex).into_iter().flatten() {
+        *dependency_map.entry(predecessor).or_insert(0.0) +=
+          path_count_map[&predecessor] / path_count_map[&vertex] * (1.0 + dependency);
+      }
+
+      if vertex != source {
+        *betweenness_map.get_mut(&vertex).unwrap() += dependency;
+      }
+    }
+  }
+
+  /// Computes the betweenness centrality of all vertices like
+  /// [`vertex_centrality_report`](#method.vertex_centrality_report), but shortest paths are
+  /// paths with the smallest weight sum, using Dijkstra's algorithm from every vertex. Pairs
+  /// with multiple shortest paths split their contribution in proportion to the number of
+  /// shortest paths through each vertex. Like [`dijkstra`](trait.EdgedGraph.html#method.dijkstra),
+  /// this only supports non-negative weights, and zero-weight edges must not form a cycle, since
+  /// such a cycle makes the number of shortest paths infinite. It takes `O(V * E * log(V))` time.
+  pub fn betweenness_centrality_weighted(&self) -> HashMap<Id, f64> {
+    let mut betweenness_map = self.vertices_map.keys()
+      .map(|&vertex| (vertex, 0.0))
+      .collect::<HashMap<_, _>>();
+
+    for &source in self.vertices_map.keys() {
+      self.accumulate_weighted_betweenness(source, &mut betweenness_map);
+    }
+
+    let n = self.vertices_map.len() as f64;
+
+    for betweenness in betweenness_map.values_mut() {
+      *betweenness = if n > 2.0 { *betweenness / ((n - 1.0) * (n - 2.0)) } else { 0.0 };
+    }
+
+    betweenness_map
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+  }
+
+  fn path_graph() -> (FiniteGraph<(), ()>, Vec<Id>) {
+    let mut graph = FiniteGraph::new();
+    let vertices = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for pair in vertices.windows(2) {
+      graph.insert_bi_edge(pair[0], pair[1], ()).unwrap();
+    }
+
+    (graph, vertices)
+  }
+
+  #[test]
+  fn centrality_of_path_graph() {
+    let (graph, vertices) = path_graph();
+    let report = graph.vertex_centrality_report();
+
+    assert_close(report[&vertices[0]].degree_centrality, 2.0 / 3.0);
+    assert_close(report[&vertices[1]].degree_centrality, 4.0 / 3.0);
+
+    assert_close(report[&vertices[0]].closeness_centrality, 3.0 / 6.0);
+    assert_close(report[&vertices[1]].closeness_centrality, 3.0 / 4.0);
+
+    assert_close(report[&vertices[0]].betweenness_centrality, 0.0);
+    assert_close(report[&vertices[1]].betweenness_centrality, 4.0 / 6.0);
+    assert_close(report[&vertices[2]].betweenness_centrality, 4.0 / 6.0);
+  }
+
+  #[test]
+  fn betweenness_splits_between_shortest_paths() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let vertices = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_edge(vertices[0], vertices[1], ()).unwrap();
+    graph.insert_edge(vertices[0], vertices[2], ()).unwrap();
+    graph.insert_edge(vertices[1], vertices[3], ()).unwrap();
+    graph.insert_edge(vertices[2], vertices[3], ()).unwrap();
+
+    let report = graph.vertex_centrality_report();
+
+    assert_close(report[&vertices[1]].betweenness_centrality, 0.5 / 6.0);
+    assert_close(report[&vertices[2]].betweenness_centrality, 0.5 / 6.0);
+    assert_close(report[&vertices[0]].closeness_centrality, 0.0);
+  }
+
+  #[test]
+  fn weighted_betweenness_splits_ties() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..5).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+    let (a, b, c, d, e) = (v[0], v[1], v[2], v[3], v[4]);
+
+    // Both a-b-e and a-c-e cost 3, as do b-a-c and b-e-c
+    graph.insert_bi_edge(a, b, 1).unwrap();
+    graph.insert_bi_edge(b, e, 2).unwrap();
+    graph.insert_bi_edge(a, c, 2).unwrap();
+    graph.insert_bi_edge(c, e, 1).unwrap();
+    graph.insert_bi_edge(e, d, 1).unwrap();
+    graph.insert_bi_edge(a, d, 5).unwrap();
+
+    let betweenness = graph.betweenness_centrality_weighted();
+
+    assert_close(betweenness[&a], 1.0 / 12.0);
+    assert_close(betweenness[&b], 2.0 / 12.0);
+    assert_close(betweenness[&c], 2.0 / 12.0);
+    assert_close(betweenness[&d], 0.0);
+    assert_close(betweenness[&e], 7.0 / 12.0);
+
+    let mut unit = FiniteGraph::<(), u32>::new();
+    let v = (0..6).map(|_| unit.insert_vertex(())).collect::<Vec<_>>();
+
+    for &(from, to) in &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 0), (2, 5), (5, 5)] {
+      unit.insert_edge(v[from], v[to], 1).unwrap();
+    }
+
+    unit.insert_edge(v[1], v[3], 1).unwrap();
+
+    let report = unit.vertex_centrality_report();
+
+    for (vertex, betweenness) in unit.betweenness_centrality_weighted() {
+      assert_close(betweenness, report[&vertex].betweenness_centrality);
+    }
+  }
+
+  #[test]
+  fn weighted_betweenness_with_zero_weight_edges() {
+    // s -> a and s -> b -> a both cost 1, so b is on half of the shortest paths from s to a
+    // and to t, no matter whether a or b has the smaller id
+    for &a_first in &[true, false] {
+      let mut graph = FiniteGraph::<(), u32>::new();
+      let s = graph.insert_vertex(());
+      let (a, b) = if a_first {
+        let a = graph.insert_vertex(());
+        (a, graph.insert_vertex(()))
+      } else {
+        let b = graph.insert_vertex(());
+        (graph.insert_vertex(()), b)
+      };
+      let t = graph.insert_vertex(());
+
+      graph.insert_edge(s, a, 1).unwrap();
+      graph.insert_edge(s, b, 1).unwrap();
+      graph.insert_edge(b, a, 0).unwrap();
+      graph.insert_edge(a, t, 1).unwrap();
+
+      let betweenness = graph.betweenness_centrality_weighted();
+
+      assert_close(betweenness[&a], 2.0 / 6.0);
+      assert_close(betweenness[&b], 1.0 / 6.0);
+      assert_close(betweenness[&s], 0.0);
+    }
+  }
+
+  #[cfg(feature = "rand")]
+  #[test]
+  fn full_sample_equals_exact_centrality() {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    let (graph, vertices) = path_graph();
+    let report = graph.vertex_centrality_report();
+    let sampled = graph.sample_vertex_centrality(10, StdRng::seed_from_u64(42));
+
+    for vertex in vertices {
+      assert_close(sampled[&vertex].closeness_centrality, report[&vertex].closeness_centrality);
+      assert_close(sampled[&vertex].betweenness_centrality, report[&vertex].betweenness_centrality);
+    }
+  }
+}
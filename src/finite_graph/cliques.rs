@@ -0,0 +1,283 @@
+use std::collections::{HashMap, HashSet};
+use super::*;
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns the distinct vertices that are connected with `vertex` by an edge in any
+  /// direction, excluding `vertex` itself.
+  pub(crate) fn adjacent_set(&self, vertex: Id) -> HashSet<Id> {
+    self.undirected_neighbors(vertex)
+    .map(|(neighbor, _)| neighbor)
+    .filter(|&neighbor| neighbor != vertex)
+    .collect()
+  }
+
+  /// Returns all vertices other than `vertex` and `other` that are adjacent to both of them,
+  /// ignoring edge directions. The result is sorted.
+  pub fn common_neighbors(&self, vertex: Id, other: Id) -> Vec<Id> {
+    let other_neighbors = self.adjacent_set(other);
+    let mut result = self.adjacent_set(vertex).into_iter()
+      .filter(|neighbor| neighbor != &other && other_neighbors.contains(neighbor))
+      .collect::<Vec<_>>();
+
+    result.sort();
+    result
+  }
+
+  /// Returns all maximal cliques of the graph, i.e. sets of pairwise adjacent vertices that
+  /// can't be extended by another vertex, ignoring edge directions. Each clique is sorted and
+  /// the cliques are sorted as well.
+  ///
+  /// This uses the Bron-Kerbosch algorithm with pivoting, where the outermost level processes
+  /// vertices in [degeneracy order](https://en.wikipedia.org/wiki/Degeneracy_(graph_theory)).
+  /// Every clique of two vertices is then extended by their
+  /// [common neighbors](#method.common_neighbors). The number of maximal cliques, and
+  /// therefore the running time, can be exponential.
+  pub fn maximal_cliques(&self) -> Vec<Vec<Id>> {
+    let adjacency = self.vertices_map.keys()
+      .map(|&vertex| (vertex, self.adjacent_set(vertex)))
+      .collect::<HashMap<_, _>>();
+    let mut cliques = vec![];
+    let mut processed = HashSet::new();
+
+    for vertex in self.degeneracy_ordering() {
+      let neighbors = &adjacency[&vertex];
+      let mut candidates = neighbors.difference(&processed).copied().collect::<HashSet<_>>();
+      let mut excluded = neighbors.intersection(&processed).copied().collect::<HashSet<_>>();
+
+      if neighbors.is_empty() {
+        cliques.push(vec![vertex]);
+      }
+
+      for other in pivot_branches(&adjacency, &candidates, &excluded) {
+        // The common neighbors are exactly the neighbors of `vertex` adjacent to `other`, so
+        // they split into remaining candidates and excluded vertices
+        let (next_candidates, next_excluded) = self.common_neighbors(vertex, other).into_iter()
+          .partition(|neighbor| candidates.contains(neighbor));
+
+        bron_kerbosch(&adjacency, &mut vec![vertex, other], next_candidates, next_excluded, &mut cliques);
+
+        candidates.remove(&other);
+        excluded.insert(other);
+      }
+
+      processed.insert(vertex);
+    }
+
+    for clique in &mut cliques {
+      clique.sort();
+    }
+
+    cliques.sort();
+    cliques
+  }
+
+  /// Returns all cliques with exactly `k` vertices, ignoring edge directions. Each clique is
+  /// sorted and the cliques are sorted as well. Returns an empty list if `k` is zero.
+  ///
+  /// The cliques are obtained from the [maximal cliques](#method.maximal_cliques) by taking
+  /// all of their subsets with `k` vertices.
+  pub fn k_cliques(&self, k: usize) -> Vec<Vec<Id>> {
+    if k == 0 {
+      return vec![];
+    }
+
+    let mut cliques = HashSet::new();
+
+    for maximal_clique in self.maximal_cliques() {
+      if maximal_clique.len() >= k {
+        collect_subsets(&maximal_clique, k, &mut vec![], &mut cliques);
+      }
+    }
+
+    let mut cliques = cliques.into_iter().collect::<Vec<_>>();
+    cliques.sort();
+    cliques
+  }
+
+  /// Returns a clique with the maximum number of vertices, ignoring edge directions. Among
+  /// multiple maximum cliques, the lexicographically smallest one is returned. The result is
+  /// sorted and empty if the graph has no vertices.
+  ///
+  /// Finding a maximum clique is NP-hard, so this may take exponential time.
+  pub fn max_clique(&self) -> Vec<Id> {
+    self.maximal_cliques().into_iter()
+    .fold(vec![], |best, clique| if clique.len() > best.len() { clique } else { best })
+  }
+
+  /// Returns `true` if the distinct existing `vertices` are adjacent exactly when they are
+  /// consecutive, where the last and the first vertex are consecutive if `cyclic` is `true`.
+  fn is_induced(&self, vertices: &[Id], cyclic: bool) -> bool {
+    let position_map = vertices.iter()
+      .enumerate()
+      .map(|(position, &vertex)| (vertex, position))
+      .collect::<HashMap<_, _>>();
+
+    if position_map.len() < vertices.len() || !vertices.iter().all(|&vertex| self.contains_vertex(vertex)) {
+      return false;
+    }
+
+    let n = vertices.len();
+    let consecutive = |i: usize, j: usize| {
+      i + 1 == j || j + 1 == i || cyclic && (i + 1) % n == j || cyclic && (j + 1) % n == i
+    };
+
+    vertices.iter().enumerate().all(|(i, &vertex)| {
+      let adjacent = self.adjacent_set(vertex).into_iter()
+        .filter_map(|neighbor| position_map.get(&neighbor).copied())
+        .collect::<HashSet<_>>();
+      let expected = if n == 1 { 0 } else if cyclic || (i > 0 && i + 1 < n) { 2 } else { 1 };
+
+      adjacent.len() == expected && adjacent.iter().all(|&j| consecutive(i, j))
+    })
+  }
+
+  /// Returns `true` if `path` is an induced path, i.e. its vertices are distinct and two of them
+  /// are adjacent if and only if they are consecutive in `path`. Edge directions and self-loops
+  /// are ignored. Returns `false` if `path` is empty or contains a vertex that doesn't exist.
+  pub fn is_induced_path(&self, path: &[Id]) -> bool {
+    !path.is_empty() && self.is_induced(path, false)
+  }
+
+  /// Returns `true` if `cycle` is an induced cycle, i.e. it has at least three distinct vertices
+  /// and two of them are adjacent if and only if they are consecutive in `cycle`, where the last
+  /// vertex is followed by the first. Edge directions and self-loops are ignored. Returns `false`
+  /// if `cycle` contains a vertex that doesn't exist.
+  pub fn is_induced_cycle(&self, cycle: &[Id]) -> bool {
+    cycle.len() >= 3 && self.is_induced(cycle, true)
+  }
+}
+
+/// Chooses the pivot with the most candidates among its neighbors and returns the remaining
+/// candidates in ascending order, which are the only ones that need to be branched on.
+fn pivot_branches(adjacency: &HashMap<Id, HashSet<Id>>, candidates: &HashSet<Id>, excluded: &HashSet<Id>) -> Vec<Id> {
+  let pivot = match candidates.union(excluded)
+    .copied()
+    .max_by_key(|vertex| (adjacency[vertex].intersection(candidates).count(), std::cmp::Reverse(*vertex)))
+  {
+    Some(pivot) => pivot,
+    None => return vec![]
+  };
+
+  let mut branches = candidates.difference(&adjacency[&pivot]).copied().collect::<Vec<_>>();
+  branches.sort();
+  branches
+}
+
+fn bron_kerbosch(
+  adjacency: &HashMap<Id, HashSet<Id>>,
+  clique: &mut Vec<Id>,
+  mut candidates: HashSet<Id>,
+  mut excluded: HashSet<Id>,
+  cliques: &mut Vec<Vec<Id>>
+) {
+  if candidates.is_empty() {
+    if excluded.is_empty() {
+      cliques.push(clique.clone());
+    }
+
+    return;
+  }
+
+  for vertex in pivot_branches(adjacency, &candidates, &excluded) {
+    let neighbors = &adjacency[&vertex];
+
+    clique.push(vertex);
+    bron_kerbosch(
+      adjacency,
+      clique,
+      candidates.intersection(neighbors).copied().collect(),
+      excluded.intersection(neighbors).copied().collect(),
+      cliques
+    );
+    clique.pop();
+
+    candidates.remove(&vertex);
+    excluded.insert(vertex);
+  }
+}
+
+fn collect_subsets(set: &[Id], k: usize, subset: &mut Vec<Id>, result: &mut HashSet<Vec<Id>>) {
+  if subset.len() == k {
+    result.insert(subset.clone());
+    return;
+  }
+
+  for (i, &vertex) in set.iter().enumerate() {
+    if set.len() - i < k - subset.len() {
+      break;
+    }
+
+    subset.push(vertex);
+    collect_subsets(&set[i + 1..], k, subset, result);
+    subset.pop();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cliques_of_small_graph() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..5).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    // The vertices 0 to 3 form a clique of size 4, connected by edges of mixed directions
+    for &(from, to) in &[(0, 1), (0, 2), (3, 0), (1, 2), (1, 3), (2, 3), (3, 4)] {
+      graph.insert_edge(v[from], v[to], ()).unwrap();
+    }
+
+    graph.insert_bi_edge(v[4], v[3], ()).unwrap();
+    graph.insert_edge(v[4], v[4], ()).unwrap();
+
+    assert_eq!(graph.max_clique(), vec![v[0], v[1], v[2], v[3]]);
+    assert_eq!(graph.maximal_cliques(), vec![vec![v[0], v[1], v[2], v[3]], vec![v[3], v[4]]]);
+    assert_eq!(graph.k_cliques(0), Vec::<Vec<Id>>::new());
+    assert_eq!(graph.k_cliques(1).len(), 5);
+    assert_eq!(graph.k_cliques(2).len(), 7);
+    assert_eq!(graph.k_cliques(3), vec![
+      vec![v[0], v[1], v[2]],
+      vec![v[0], v[1], v[3]],
+      vec![v[0], v[2], v[3]],
+      vec![v[1], v[2], v[3]]
+    ]);
+    assert_eq!(graph.k_cliques(4), vec![vec![v[0], v[1], v[2], v[3]]]);
+    assert_eq!(graph.k_cliques(5), Vec::<Vec<Id>>::new());
+    assert_eq!(FiniteGraph::<(), ()>::new().max_clique(), vec![]);
+
+    assert_eq!(graph.common_neighbors(v[0], v[1]), vec![v[2], v[3]]);
+    assert_eq!(graph.common_neighbors(v[0], v[4]), vec![v[3]]);
+
+    let isolated = graph.insert_vertex(());
+
+    graph.insert_edge(isolated, isolated, ()).unwrap();
+    assert_eq!(graph.maximal_cliques().last(), Some(&vec![isolated]));
+  }
+
+  #[test]
+  fn induced_paths_and_cycles() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..6).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    // A five-cycle with mixed directions and the chord 0 - 2, plus a pendant vertex 5
+    for &(from, to) in &[(0, 1), (2, 1), (2, 3), (3, 4), (4, 0), (0, 2), (4, 5)] {
+      graph.insert_edge(v[from], v[to], ()).unwrap();
+    }
+
+    graph.insert_edge(v[3], v[3], ()).unwrap();
+
+    assert!(graph.is_induced_path(&[v[1], v[2], v[3], v[4], v[5]]));
+    assert!(graph.is_induced_path(&[v[5], v[4], v[3]]));
+    assert!(graph.is_induced_path(&[v[3]]));
+    assert!(!graph.is_induced_path(&[v[0], v[1], v[2]]));
+    assert!(!graph.is_induced_path(&[v[1], v[3]]));
+    assert!(!graph.is_induced_path(&[v[4], v[5], v[4]]));
+    assert!(!graph.is_induced_path(&[]));
+
+    assert!(graph.is_induced_cycle(&[v[0], v[1], v[2]]));
+    assert!(graph.is_induced_cycle(&[v[0], v[2], v[3], v[4]]));
+    assert!(!graph.is_induced_cycle(&[v[0], v[1], v[2], v[3], v[4]]));
+    assert!(!graph.is_induced_cycle(&[v[0], v[2], v[4], v[3]]));
+    assert!(!graph.is_induced_cycle(&[v[4], v[5]]));
+  }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use edge::WeightedEdge;
+use super::*;
+
+/// Determines how [`FiniteGraph::coarsen`](struct.FiniteGraph.html#method.coarsen) pairs up
+/// vertices on each level. Vertices are visited in ascending order of their ids and paired with
+/// a neighbor that hasn't been paired yet.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MatchStrategy {
+  /// Pairs each vertex with the neighbor it shares the largest total edge weight with.
+  HeavyEdge,
+  /// Pairs each vertex with the neighbor with the smallest id.
+  FirstNeighbor
+}
+
+/// A level of the hierarchy computed by
+/// [`FiniteGraph::coarsen`](struct.FiniteGraph.html#method.coarsen).
+#[derive(Clone, Debug)]
+pub struct CoarseLevel<V, E> {
+  /// The coarse graph, where each supervertex holds the values of all original vertices that
+  /// have been merged into it. Edges are bidirectional and carry the weight sum of all edges
+  /// between the merged vertices.
+  pub graph: FiniteGraph<Vec<V>, E>,
+  /// Maps every vertex of the next finer level, i.e. of the original graph for the first level,
+  /// to the supervertex of `graph` it has been merged into.
+  pub parent_map: HashMap<Id, Id>
+}
+
+impl<V, E> CoarseLevel<V, E> {
+  /// Projects a solution on the vertices of this level down to the vertices of the next finer
+  /// level, where each vertex inherits the value of its supervertex.
+  pub fn project<T: Clone>(&self, solution: &HashMap<Id, T>) -> HashMap<Id, T> {
+    self.parent_map.iter()
+    .filter_map(|(&vertex, parent)| solution.get(parent).map(|value| (vertex, value.clone())))
+    .collect()
+  }
+
+  /// Projects a solution on the vertices of the coarsest level, i.e. the last of `levels`, all
+  /// the way down to the vertices of the original graph.
+  pub fn uncoarsen<T: Clone>(levels: &[CoarseLevel<V, E>], solution: HashMap<Id, T>) -> HashMap<Id, T> {
+    levels.iter().rev().fold(solution, |solution, level| level.project(&solution))
+  }
+}
+
+/// Computes a single coarsening step of `graph`, where `values` returns the original vertex
+/// values of a vertex. Returns `None` if no two vertices can be paired.
+fn coarsen_once<W, V, E: WeightedEdge>(
+  graph: &FiniteGraph<W, E>,
+  strategy: MatchStrategy,
+  values: impl Fn(Id) -> Vec<V>
+) -> Option<CoarseLevel<V, E>> {
+  // Edge directions are ignored and parallel edges are summed up
+  let mut weight_map = HashMap::<(Id, Id), E>::new();
+
+  for (data, from, to) in graph.edges_map.values() {
+    if from != to {
+      let weight = weight_map.entry((*from.min(to), *from.max(to))).or_default();
+      *weight = weight.clone() + data.clone();
+    }
+  }
+
+  let mut adjacency_map = HashMap::<Id, Vec<(Id, E)>>::new();
+
+  for (&(vertex, other), weight) in &weight_map {
+    adjacency_map.entry(vertex).or_default().push((other, weight.clone()));
+    adjacency_map.entry(other).or_default().push((vertex, weight.clone()));
+  }
+
+  let mut vertices = graph.vertices_map.keys().copied().collect::<Vec<_>>();
+  vertices.sort();
+
+  let mut mate_map = HashMap::new();
+
+  for &vertex in &vertices {
+    if mate_map.contains_key(&vertex) {
+      continue;
+    }
+
+    let candidates = adjacency_map.get(&vertex).into_iter()
+      .flatten()
+      .filter(|(neighbor, _)| !mate_map.contains_key(neighbor));
+    let mate = match strategy {
+      MatchStrategy::HeavyEdge => candidates.max_by(|(neighbor, weight), (other, other_weight)| {
+        weight.cmp(other_weight).then(other.cmp(neighbor))
+      }),
+      MatchStrategy::FirstNeighbor => candidates.min_by_key(|(neighbor, _)| *neighbor)
+    };
+
+    if let Some(&(mate, _)) = mate {
+      mate_map.insert(vertex, mate);
+      mate_map.insert(mate, vertex);
+    }
+  }
+
+  if mate_map.is_empty() {
+    return None;
+  }
+
+  let mut coarse_graph = FiniteGraph::with_capacity(vertices.len() - mate_map.len() / 2, weight_map.len());
+  let mut parent_map = HashMap::new();
+
+  for &vertex in &vertices {
+    if parent_map.contains_key(&vertex) {
+      continue;
+    }
+
+    let mut value = values(vertex);
+    let mate = mate_map.get(&vertex).copied();
+
+    if let Some(mate) = mate {
+      value.extend(values(mate));
+    }
+
+    let parent = coarse_graph.insert_vertex(value);
+    parent_map.insert(vertex, parent);
+
+    if let Some(mate) = mate {
+      parent_map.insert(mate, parent);
+    }
+  }
+
+  let mut coarse_weight_map = HashMap::<(Id, Id), E>::new();
+
+  for ((vertex, other), weight) in weight_map {
+    let (parent, other_parent) = (parent_map[&vertex], parent_map[&other]);
+
+    if parent != other_parent {
+      let sum = coarse_weight_map.entry((parent.min(other_parent), parent.max(other_parent))).or_default();
+      *sum = sum.clone() + weight;
+    }
+  }
+
+  let mut coarse_edges = coarse_weight_map.into_iter().collect::<Vec<_>>();
+  coarse_edges.sort_by_key(|&(endpoints, _)| endpoints);
+
+  for ((vertex, other), weight) in coarse_edges {
+    coarse_graph.insert_bi_edge(vertex, other, weight);
+  }
+
+  Some(CoarseLevel { graph: coarse_graph, parent_map })
+}
+
+impl<V: Clone, E: WeightedEdge> FiniteGraph<V, E> {
+  /// Builds a hierarchy of up to `levels` successively coarser graphs for multilevel
+  /// partitioning and layout algorithms, finest level first.
+  ///
+  /// On each level, vertices are paired with a neighbor according to `strategy` and each pair
+  /// is merged into a supervertex. Edge directions are ignored, self-loops and edges inside a
+  /// supervertex are dropped, and the weights of all edges between two supervertices are summed
+  /// up into one bidirectional edge. A solution computed on the coarsest graph can be projected
+  /// back onto the original vertices with [`CoarseLevel::uncoarsen`](struct.CoarseLevel.html#method.uncoarsen).
+  ///
+  /// Stops early once no two vertices can be paired anymore, so fewer than `levels` levels are
+  /// returned if the graph becomes edgeless.
+  pub fn coarsen(&self, levels: usize, strategy: MatchStrategy) -> Vec<CoarseLevel<V, E>> {
+    let mut result = Vec::<CoarseLevel<V, E>>::new();
+
+    while result.len() < levels {
+      let level = match result.last() {
+        None => coarsen_once(self, strategy, |vertex| vec![self.vertices_map[&vertex].clone()]),
+        Some(last) => coarsen_once(&last.graph, strategy, |vertex| last.graph.vertices_map[&vertex].clone())
+      };
+
+      match level {
+        Some(level) => result.push(level),
+        None => break
+      }
+    }
+
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn coarsen_merges_heavy_edges() {
+    let mut graph = FiniteGraph::<char, u32>::new();
+    let v = "abcde".chars().map(|name| graph.insert_vertex(name)).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], 1).unwrap();
+    graph.insert_edge(v[0], v[2], 4).unwrap();
+    graph.insert_edge(v[1], v[3], 2).unwrap();
+    graph.insert_edge(v[3], v[1], 3).unwrap();
+    graph.insert_bi_edge(v[2], v[3], 1).unwrap();
+    graph.insert_edge(v[3], v[4], 1).unwrap();
+    graph.insert_edge(v[4], v[4], 7).unwrap();
+
+    let levels = graph.coarsen(5, MatchStrategy::HeavyEdge);
+
+    assert_eq!(levels.len(), 3);
+
+    let first = &levels[0].graph;
+    let mut supervertices = first.all_vertices_ordered()
+      .map(|(_, value)| value.clone())
+      .collect::<Vec<_>>();
+    supervertices.sort();
+
+    assert_eq!(supervertices, vec![vec!['a', 'c'], vec!['b', 'd'], vec!['e']]);
+    assert_eq!(levels[0].parent_map[&v[0]], levels[0].parent_map[&v[2]]);
+
+    // The edges a - b and c - d are merged, while the edges between b and d are dropped
+    let mut weights = first.all_edges().map(|(_, &weight)| weight).collect::<Vec<_>>();
+    weights.sort();
+
+    assert_eq!(weights, vec![1, 2]);
+    assert_eq!(levels[2].graph.len(), (1, 0));
+
+    let top = levels[2].graph.all_vertices().next().unwrap().0;
+    let solution = CoarseLevel::uncoarsen(&levels, vec![(top, 0)].into_iter().collect());
+
+    assert_eq!(solution.len(), 5);
+    assert!(v.iter().all(|vertex| solution[vertex] == 0));
+  }
+
+  #[test]
+  fn coarsen_with_first_neighbor_matching() {
+    let mut graph = FiniteGraph::<u32, u32>::new();
+    let v = (0..4).map(|i| graph.insert_vertex(i)).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], 1).unwrap();
+    graph.insert_edge(v[0], v[2], 9).unwrap();
+    graph.insert_edge(v[2], v[3], 1).unwrap();
+
+    let levels = graph.coarsen(1, MatchStrategy::FirstNeighbor);
+
+    assert_eq!(levels.len(), 1);
+    assert_eq!(levels[0].graph.len(), (2, 1));
+    assert_eq!(levels[0].parent_map[&v[0]], levels[0].parent_map[&v[1]]);
+    assert_eq!(levels[0].graph.all_edges().next().unwrap().1, &9);
+
+    let solution = levels[0].graph.all_vertices()
+      .map(|(vertex, value)| (vertex, value.len()))
+      .collect::<HashMap<_, _>>();
+
+    assert_eq!(levels[0].project(&solution).len(), 4);
+    assert!(FiniteGraph::<u32, u32>::new().coarsen(3, MatchStrategy::HeavyEdge).is_empty());
+  }
+}
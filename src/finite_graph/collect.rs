@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::iter::FromIterator;
+#[cfg(feature = "rayon")]
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelIterator};
+use super::*;
+
+impl<V: Hash + Eq + Clone, E> FiniteGraph<V, E> {
+  /// Maps every vertex value to its vertex. If multiple vertices share the same value,
+  /// the vertex with the smallest id is used.
+  fn value_index(&self) -> HashMap<V, Id> {
+    let mut index = HashMap::with_capacity(self.vertices_map.len());
+
+    for (&vertex, value) in &self.vertices_map {
+      let entry = index.entry(value.clone()).or_insert(vertex);
+      *entry = (*entry).min(vertex);
+    }
+
+    index
+  }
+
+  /// Returns the vertex with the given value according to `index`, inserting a new vertex
+  /// if there is none.
+  fn vertex_by_value(&mut self, index: &mut HashMap<V, Id>, value: V) -> Id {
+    if let Some(&vertex) = index.get(&value) {
+      return vertex;
+    }
+
+    let vertex = self.insert_vertex(value.clone());
+    index.insert(value, vertex);
+
+    vertex
+  }
+
+  fn insert_valued_edge(&mut self, index: &mut HashMap<V, Id>, (from, to, edge): (V, V, E)) {
+    let from = self.vertex_by_value(index, from);
+    let to = self.vertex_by_value(index, to);
+
+    self.insert_edge(from, to, edge);
+  }
+
+  /// Moves all vertices and edges of `other` into the graph, unifying vertices with the same
+  /// value according to `index`. `other` is expected to not contain bidirectional edges.
+  #[cfg(feature = "rayon")]
+  fn absorb(&mut self, index: &mut HashMap<V, Id>, other: FiniteGraph<V, E>) {
+    let id_map = other.vertices_map.into_iter()
+      .map(|(vertex, value)| (vertex, self.vertex_by_value(index, value)))
+      .collect::<HashMap<_, _>>();
+
+    self.reserve_edges(other.edges_map.len());
+
+    for (_, (edge, from, to)) in other.edges_map {
+      self.insert_edge(id_map[&from], id_map[&to], edge);
+    }
+  }
+}
+
+/// Inserts the `(from, to, edge)` triples as directed edges. Endpoints are unified by value,
+/// i.e. a new vertex is only inserted if there is no vertex with the same value yet.
+impl<V: Hash + Eq + Clone, E> Extend<(V, V, E)> for FiniteGraph<V, E> {
+  fn extend<I: IntoIterator<Item = (V, V, E)>>(&mut self, iter: I) {
+    let mut index = self.value_index();
+
+    for triple in iter {
+      self.insert_valued_edge(&mut index, triple);
+    }
+  }
+}
+
+impl<V: Hash + Eq + Clone, E> FromIterator<(V, V, E)> for FiniteGraph<V, E> {
+  fn from_iter<I: IntoIterator<Item = (V, V, E)>>(iter: I) -> FiniteGraph<V, E> {
+    let mut graph = FiniteGraph::new();
+    graph.extend(iter);
+
+    graph
+  }
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Inserts the `(from, to, edge)` triples as directed edges between existing vertices,
+  /// skipping triples whose endpoints don't exist, like [`insert_edge`](#method.insert_edge).
+  ///
+  /// This can't be an `Extend<(Id, Id, E)>` implementation, since it would overlap with the
+  /// implementation that unifies endpoints by value when the vertex values are ids themselves.
+  pub fn extend_edges<I: IntoIterator<Item = (Id, Id, E)>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+
+    self.reserve_edges(iter.size_hint().0);
+
+    for (from, to, edge) in iter {
+      self.insert_edge(from, to, edge);
+    }
+  }
+}
+
+/// Inserts the `(from, to)` pairs as directed edges between existing vertices, skipping pairs
+/// whose endpoints don't exist.
+impl<V> Extend<(Id, Id)> for FiniteGraph<V, ()> {
+  fn extend<I: IntoIterator<Item = (Id, Id)>>(&mut self, iter: I) {
+    self.extend_edges(iter.into_iter().map(|(from, to)| (from, to, ())));
+  }
+}
+
+/// Inserts a vertex with the default value for every id that isn't used by a vertex or an edge
+/// yet, e.g. to restore the vertices of another graph with the same ids. Later insertions get
+/// ids beyond all extended ids.
+impl<V: Default> Extend<Id> for FiniteGraph<V, ()> {
+  fn extend<I: IntoIterator<Item = Id>>(&mut self, iter: I) {
+    for vertex in iter {
+      if self.vertices_map.contains_key(&vertex) || self.edges_map.contains_key(&vertex) {
+        continue;
+      }
+
+      self.vertices_map.insert(vertex, V::default());
+      self.id = self.id.max(vertex);
+    }
+  }
+}
+
+/// Builds a graph like the `FromIterator` implementation, but inserts edges into separate
+/// graphs on worker threads, which are merged afterwards. The resulting graph contains the
+/// same vertex values and edges as the sequentially built graph, but ids may differ.
+#[cfg(feature = "rayon")]
+impl<V: Hash + Eq + Clone + Send, E: Send> FromParallelIterator<(V, V, E)> for FiniteGraph<V, E> {
+  fn from_par_iter<I: IntoParallelIterator<Item = (V, V, E)>>(iter: I) -> FiniteGraph<V, E> {
+    let empty = || (FiniteGraph::new(), HashMap::new());
+
+    iter.into_par_iter()
+    .fold(empty, |(mut graph, mut index), triple| {
+      graph.insert_valued_edge(&mut index, triple);
+      (graph, index)
+    })
+    .reduce(empty, |(mut graph, mut index), (other, other_index)| {
+      if graph.vertices_map.len() < other.vertices_map.len() {
+        let mut other_index = other_index;
+        let mut other = other;
+
+        other.absorb(&mut other_index, graph);
+        (other, other_index)
+      } else {
+        graph.absorb(&mut index, other);
+        (graph, index)
+      }
+    })
+    .0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sorted_triples<V: Clone + Ord, E: Ord>(graph: FiniteGraph<V, E>) -> Vec<(V, V, E)> {
+    let mut triples = graph.into_iter().collect::<Vec<_>>();
+    triples.sort();
+    triples
+  }
+
+  #[test]
+  fn extend_unifies_vertices_by_value() {
+    let mut graph = FiniteGraph::<&str, usize>::new();
+    let a = graph.insert_vertex("a");
+
+    graph.extend(vec![("a", "b", 1), ("b", "c", 2), ("c", "a", 3), ("a", "b", 4)]);
+
+    assert_eq!(graph.len(), (3, 4));
+    assert_eq!(graph.out_degree(a), 2);
+    assert_eq!(graph.in_degree(a), 1);
+    assert_eq!(
+      sorted_triples(graph),
+      vec![("a", "b", 1), ("a", "b", 4), ("b", "c", 2), ("c", "a", 3)]
+    );
+  }
+
+  #[test]
+  fn extend_by_ids() {
+    let mut other = FiniteGraph::<(), ()>::new();
+    let v = (0..4).map(|_| other.insert_vertex(())).collect::<Vec<_>>();
+    let edges = [(v[0], v[1]), (v[1], v[2]), (v[2], v[3]), (v[3], v[0])];
+
+    let mut graph = FiniteGraph::<usize, ()>::new();
+
+    graph.extend(v.iter().copied());
+    graph.extend(edges.iter().copied());
+    graph.extend((0..2).map(|_| (v[0], other.insert_vertex(()))));
+
+    assert_eq!(graph.len(), (4, 4));
+    assert_eq!(graph.get_vertex(v[2]), Some(&0));
+    assert!(edges.iter().all(|&(from, to)| graph.neighbors(&from).contains(&to)));
+
+    graph.extend(v.iter().copied());
+
+    let vertex = graph.insert_vertex(5);
+
+    assert!(!v.contains(&vertex));
+    assert_eq!(graph.len(), (5, 4));
+
+    let mut weighted = FiniteGraph::<(), usize>::new();
+    let w = (0..3).map(|_| weighted.insert_vertex(())).collect::<Vec<_>>();
+
+    weighted.extend_edges(vec![(w[0], w[1], 1), (w[1], w[2], 2), (w[2], vertex, 3)]);
+
+    assert_eq!(weighted.len(), (3, 2));
+    assert_eq!(weighted.out_degree(w[1]), 1);
+  }
+
+  #[cfg(feature = "rayon")]
+  #[test]
+  fn parallel_construction_matches_sequential_construction() {
+    let triples = (0..10_000usize)
+      .map(|i| (i % 97, (i * 31) % 89, i))
+      .collect::<Vec<_>>();
+
+    let sequential = triples.iter().copied().collect::<FiniteGraph<_, _>>();
+    let parallel = triples.into_par_iter().collect::<FiniteGraph<_, _>>();
+
+    assert_eq!(sequential.len(), parallel.len());
+    assert_eq!(sorted_triples(sequential), sorted_triples(parallel));
+  }
+}
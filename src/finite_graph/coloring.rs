@@ -0,0 +1,202 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use super::*;
+
+/// The error returned by [`FiniteGraph::check_coloring`](struct.FiniteGraph.html#method.check_coloring).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ColoringError {
+  /// The vertex has no color.
+  MissingColor(Id),
+  /// The vertices are connected by an edge from the first to the second vertex,
+  /// but have the same color.
+  AdjacentSameColor(Id, Id)
+}
+
+impl fmt::Display for ColoringError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ColoringError::MissingColor(vertex) => write!(f, "vertex {:?} has no color", vertex),
+      ColoringError::AdjacentSameColor(vertex, other) => {
+        write!(f, "adjacent vertices {:?} and {:?} have the same color", vertex, other)
+      }
+    }
+  }
+}
+
+impl Error for ColoringError {}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns the degeneracy ordering together with the degeneracy of the graph.
+  fn degeneracy_ordering_with_degeneracy(&self) -> (Vec<Id>, usize) {
+    let mut degree_map = self.vertices_map.keys()
+      .map(|&vertex| (vertex, self.adjacent_set(vertex).len()))
+      .collect::<HashMap<_, _>>();
+    let mut buckets = degree_map.iter()
+      .map(|(&vertex, &degree)| (degree, vertex))
+      .collect::<BTreeSet<_>>();
+    let mut order = Vec::with_capacity(degree_map.len());
+    let mut degeneracy = 0;
+
+    while let Some((degree, vertex)) = buckets.pop_first() {
+      degree_map.remove(&vertex);
+      order.push(vertex);
+      degeneracy = degeneracy.max(degree);
+
+      for neighbor in self.adjacent_set(vertex) {
+        if let Some(degree) = degree_map.get_mut(&neighbor) {
+          buckets.remove(&(*degree, neighbor));
+          *degree -= 1;
+          buckets.insert((*degree, neighbor));
+        }
+      }
+    }
+
+    (order, degeneracy)
+  }
+
+  /// Orders the vertices by repeatedly removing a vertex of minimum degree, ignoring edge
+  /// directions, self-loops, and parallel edges. Ties are broken by the smallest id.
+  ///
+  /// Each vertex has at most [`degeneracy`](#method.degeneracy) neighbors that come
+  /// after it in this ordering.
+  pub fn degeneracy_ordering(&self) -> Vec<Id> {
+    self.degeneracy_ordering_with_degeneracy().0
+  }
+
+  /// Returns the degeneracy of the graph, i.e. the largest `k` for which the graph has a
+  /// non-empty `k`-core, a subgraph in which every vertex has at least `k` neighbors.
+  /// Edge directions, self-loops, and parallel edges are ignored.
+  ///
+  /// The chromatic number of the graph is at most the degeneracy plus one.
+  pub fn degeneracy(&self) -> usize {
+    self.degeneracy_ordering_with_degeneracy().1
+  }
+
+  /// Colors the vertices greedily in reverse [degeneracy ordering](#method.degeneracy_ordering),
+  /// such that no two adjacent vertices have the same color, ignoring edge directions and
+  /// self-loops. Colors are numbered from zero, and at most [`degeneracy`](#method.degeneracy)
+  /// plus one colors are used.
+  pub fn greedy_coloring_degeneracy(&self) -> HashMap<Id, usize> {
+    let mut coloring = HashMap::new();
+
+    for vertex in self.degeneracy_ordering().into_iter().rev() {
+      let used = self.adjacent_set(vertex).into_iter()
+        .filter_map(|neighbor| coloring.get(&neighbor).copied())
+        .collect::<HashSet<_>>();
+      let color = (0..).find(|color| !used.contains(color)).unwrap();
+
+      coloring.insert(vertex, color);
+    }
+
+    coloring
+  }
+
+  /// Checks whether `coloring` colors every vertex of the graph and no two vertices that are
+  /// connected by an edge have the same color. Self-loops are ignored. If there are multiple
+  /// violations, the one of the vertex or edge with the smallest id is returned.
+  pub fn check_coloring(&self, coloring: &HashMap<Id, usize>) -> Result<(), ColoringError> {
+    let mut vertices = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    vertices.sort();
+
+    if let Some(&vertex) = vertices.iter().find(|vertex| !coloring.contains_key(vertex)) {
+      return Err(ColoringError::MissingColor(vertex));
+    }
+
+    let mut edges = self.edges_map.iter()
+      .filter(|&(_, &(_, from, to))| from != to && coloring[&from] == coloring[&to])
+      .map(|(&edge, &(_, from, to))| (edge, from, to))
+      .collect::<Vec<_>>();
+    edges.sort();
+
+    match edges.first() {
+      Some(&(_, from, to)) => Err(ColoringError::AdjacentSameColor(from, to)),
+      None => Ok(())
+    }
+  }
+
+  /// Returns `true` if `coloring` is a valid coloring of the graph, see
+  /// [`check_coloring`](#method.check_coloring).
+  pub fn is_valid_coloring(&self, coloring: &HashMap<Id, usize>) -> bool {
+    self.check_coloring(coloring).is_ok()
+  }
+
+  /// Returns the number of distinct colors that `coloring` assigns to vertices of the graph.
+  /// Colors of vertices that don't exist are ignored.
+  pub fn coloring_chromatic_number(&self, coloring: &HashMap<Id, usize>) -> usize {
+    coloring.iter()
+    .filter(|(vertex, _)| self.contains_vertex(**vertex))
+    .map(|(_, color)| color)
+    .collect::<HashSet<_>>()
+    .len()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn degeneracy_coloring_of_wheel() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let center = graph.insert_vertex(());
+    let rim = (0..5).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..5 {
+      graph.insert_edge(center, rim[i], ()).unwrap();
+      graph.insert_bi_edge(rim[i], rim[(i + 1) % 5], ()).unwrap();
+    }
+
+    assert_eq!(graph.degeneracy(), 3);
+
+    let ordering = graph.degeneracy_ordering();
+
+    assert_eq!(ordering.len(), 6);
+
+    for (i, vertex) in ordering.iter().enumerate() {
+      let later_neighbors = graph.adjacent_set(*vertex).into_iter()
+        .filter(|neighbor| ordering[i + 1..].contains(neighbor))
+        .count();
+
+      assert!(later_neighbors <= 3);
+    }
+
+    let coloring = graph.greedy_coloring_degeneracy();
+
+    // An odd wheel needs 4 colors
+    assert_eq!(graph.coloring_chromatic_number(&coloring), 4);
+    assert!(graph.is_valid_coloring(&coloring));
+
+    assert_eq!(FiniteGraph::<(), ()>::new().degeneracy(), 0);
+  }
+
+  #[test]
+  fn check_invalid_colorings() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+
+    graph.insert_edge(a, b, ()).unwrap();
+    graph.insert_edge(c, b, ()).unwrap();
+    graph.insert_edge(c, c, ()).unwrap();
+
+    let mut coloring = vec![(a, 0), (b, 1)].into_iter().collect::<HashMap<_, _>>();
+
+    assert_eq!(graph.check_coloring(&coloring), Err(ColoringError::MissingColor(c)));
+
+    coloring.insert(c, 1);
+
+    assert_eq!(graph.check_coloring(&coloring), Err(ColoringError::AdjacentSameColor(c, b)));
+    assert!(!graph.is_valid_coloring(&coloring));
+
+    coloring.insert(c, 0);
+
+    assert_eq!(graph.check_coloring(&coloring), Ok(()));
+    assert_eq!(graph.coloring_chromatic_number(&coloring), 2);
+
+    coloring.insert(Id(100), 5);
+
+    assert_eq!(graph.coloring_chromatic_number(&coloring), 2);
+  }
+}
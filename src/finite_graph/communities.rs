@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use super::*;
+use super::centrality::brandes_pass;
+use super::components::connected_components;
+
+type UndirectedAdjacency = HashMap<Id, Vec<(Id, Id)>>;
+
+/// Computes the betweenness of every edge of `adjacency`, i.e. the number of shortest paths
+/// between pairs of vertices passing through it, where pairs with multiple shortest paths
+/// contribute fractionally.
+fn edge_betweenness(vertices: &[Id], adjacency: &UndirectedAdjacency) -> HashMap<Id, f64> {
+  let mut betweenness_map = HashMap::new();
+
+  for &source in vertices {
+    brandes_pass(
+      source,
+      |vertex| adjacency[&vertex].iter().copied(),
+      |_, _, _| {},
+      |edge, contribution| *betweenness_map.entry(edge).or_insert(0.0) += contribution
+    );
+  }
+
+  betweenness_map
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Splits the vertices into at least `num_communities` communities using the
+  /// [Girvan-Newman algorithm](https://doi.org/10.1073/pnas.122653799), ignoring edge
+  /// directions and self-loops. Each community is sorted and communities are returned in
+  /// ascending order of their smallest vertex. If the graph already has at least
+  /// `num_communities` connected components, these are returned; if there are fewer
+  /// vertices than `num_communities`, every vertex ends up in its own community.
+  ///
+  /// The edge with the highest betweenness, i.e. the edge most shortest paths pass through,
+  /// is removed repeatedly until the graph falls apart into enough connected components.
+  /// Ties are broken by the smallest id. Edge betweenness is recomputed with a breadth-first
+  /// search from every vertex after each removal, so this takes `O(V * E^2)` time.
+  pub fn girvan_newman_communities(&self, num_communities: usize) -> Vec<Vec<Id>> {
+    let vertices = self.all_vertices_ordered().map(|(vertex, _)| vertex).collect::<Vec<_>>();
+    let mut adjacency = vertices.iter()
+      .map(|&vertex| (vertex, vec![]))
+      .collect::<UndirectedAdjacency>();
+
+    for (&edge, &(_, from, to)) in &self.edges_map {
+      if from != to {
+        adjacency.get_mut(&from).unwrap().push((to, edge));
+        adjacency.get_mut(&to).unwrap().push((from, edge));
+      }
+    }
+
+    loop {
+      let communities = connected_components(&vertices, |vertex| adjacency[&vertex].iter().map(|&(neighbor, _)| neighbor));
+
+      if communities.len() >= num_communities {
+        return communities;
+      }
+
+      let betweenness_map = edge_betweenness(&vertices, &adjacency);
+      let edge = match betweenness_map.into_iter().max_by(|(edge, betweenness), (other, other_betweenness)| {
+        betweenness.total_cmp(other_betweenness).then(other.cmp(edge))
+      }) {
+        Some((edge, _)) => edge,
+        None => return communities
+      };
+
+      let (_, from, to) = self.edges_map[&edge];
+
+      for vertex in &[from, to] {
+        adjacency.get_mut(vertex).unwrap().retain(|&(_, other)| other != edge);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn girvan_newman_splits_karate_club() {
+    // Zachary's karate club with vertices numbered from 1
+    let neighbors: &[(usize, &[usize])] = &[
+      (1, &[2, 3, 4, 5, 6, 7, 8, 9, 11, 12, 13, 14, 18, 20, 22, 32]),
+      (2, &[3, 4, 8, 14, 18, 20, 22, 31]),
+      (3, &[4, 8, 9, 10, 14, 28, 29, 33]),
+      (4, &[8, 13, 14]),
+      (5, &[7, 11]),
+      (6, &[7, 11, 17]),
+      (7, &[17]),
+      (9, &[31, 33, 34]),
+      (10, &[34]),
+      (14, &[34]),
+      (15, &[33, 34]),
+      (16, &[33, 34]),
+      (19, &[33, 34]),
+      (20, &[34]),
+      (21, &[33, 34]),
+      (23, &[33, 34]),
+      (24, &[26, 28, 30, 33, 34]),
+      (25, &[26, 28, 32]),
+      (26, &[32]),
+      (27, &[30, 34]),
+      (28, &[34]),
+      (29, &[32, 34]),
+      (30, &[33, 34]),
+      (31, &[33, 34]),
+      (32, &[33, 34]),
+      (33, &[34])
+    ];
+
+    let mut graph = FiniteGraph::<usize, ()>::new();
+    let v = (0..=34).map(|i| graph.insert_vertex(i)).collect::<Vec<_>>();
+
+    graph.remove_vertex(v[0]);
+
+    for &(vertex, others) in neighbors {
+      for &other in others {
+        graph.insert_bi_edge(v[vertex], v[other], ()).unwrap();
+      }
+    }
+
+    assert_eq!(graph.len(), (34, 78));
+
+    let communities = graph.girvan_newman_communities(2);
+    let community_of = |vertex: usize| communities.iter().position(|community| community.contains(&v[vertex]));
+
+    assert_eq!(communities.len(), 2);
+    assert_eq!(communities.iter().map(Vec::len).sum::<usize>(), 34);
+    assert_ne!(community_of(1), community_of(34));
+    assert_eq!(community_of(1), community_of(2));
+    assert_eq!(community_of(33), community_of(34));
+  }
+
+  #[test]
+  fn girvan_newman_removes_bridges_first() {
+    // Three directed 4-cycles with chords and self-loops, connected in a row by single edges
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..12).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for offset in (0..12).step_by(4) {
+      for i in 0..4 {
+        graph.insert_edge(v[offset + i], v[offset + (i + 1) % 4], ()).unwrap();
+      }
+
+      graph.insert_edge(v[offset], v[offset + 2], ()).unwrap();
+      graph.insert_edge(v[offset + 1], v[offset + 1], ()).unwrap();
+    }
+
+    graph.insert_edge(v[4], v[3], ()).unwrap();
+    graph.insert_edge(v[7], v[8], ()).unwrap();
+
+    assert_eq!(graph.girvan_newman_communities(1), vec![v.clone()]);
+    assert_eq!(graph.girvan_newman_communities(2).len(), 2);
+    assert_eq!(graph.girvan_newman_communities(3), vec![v[0..4].to_vec(), v[4..8].to_vec(), v[8..12].to_vec()]);
+    assert_eq!(graph.girvan_newman_communities(20).len(), 12);
+    assert_eq!(FiniteGraph::<(), ()>::new().girvan_newman_communities(2), Vec::<Vec<Id>>::new());
+  }
+}
@@ -0,0 +1,298 @@
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use super::*;
+
+/// A component extracted as a graph of its own, together with the map from its vertex and
+/// edge ids to the ids in the original graph.
+type ComponentGraph<V, E> = (FiniteGraph<V, E>, HashMap<Id, Id>);
+
+/// Returns the connected components of the graph given by `vertices` and the `neighbors`
+/// function. Each component is sorted and the components are returned in ascending order of
+/// their smallest vertex.
+pub(crate) fn connected_components<I>(vertices: &[Id], neighbors: impl Fn(Id) -> I) -> Vec<Vec<Id>>
+where I: IntoIterator<Item = Id> {
+  let mut vertices = vertices.to_vec();
+  let mut visited = HashSet::new();
+  let mut result = vec![];
+
+  vertices.sort();
+
+  for start in vertices {
+    if !visited.insert(start) {
+      continue;
+    }
+
+    let mut component = vec![start];
+    let mut index = 0;
+
+    while let Some(&vertex) = component.get(index) {
+      for neighbor in neighbors(vertex) {
+        if visited.insert(neighbor) {
+          component.push(neighbor);
+        }
+      }
+
+      index += 1;
+    }
+
+    component.sort();
+    result.push(component);
+  }
+
+  result
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns the weakly connected components, i.e. the connected components when ignoring
+  /// edge directions. Each component is sorted and the components are returned in ascending
+  /// order of their smallest vertex.
+  pub fn weakly_connected_components(&self) -> Vec<Vec<Id>> {
+    let vertices = self.vertices_map.keys().copied().collect::<Vec<_>>();
+
+    connected_components(&vertices, |vertex| self.undirected_neighbors(vertex).map(|(neighbor, _)| neighbor))
+  }
+
+  /// Moves all vertices and edges into a new graph with fresh ids, keeping the relative order
+  /// of ids as well as bidirectional edges, and returns it with the map from the new ids to
+  /// the ids of this graph.
+  fn into_renumbered(self) -> ComponentGraph<V, E> {
+    let mut edges = self.edges_map.iter()
+      .map(|(&edge, &(_, from, to))| (edge, self.is_bi_edge(edge, from, to)))
+      .collect::<Vec<_>>();
+    let mut vertices = self.vertices_map.into_iter().collect::<Vec<_>>();
+    let mut edges_map = self.edges_map;
+    let mut graph = FiniteGraph::new();
+    let mut original_map = HashMap::new();
+
+    vertices.sort_by_key(|&(vertex, _)| vertex);
+    edges.sort();
+
+    let id_map = vertices.into_iter()
+      .map(|(vertex, value)| {
+        let id = graph.insert_vertex(value);
+
+        original_map.insert(id, vertex);
+        (vertex, id)
+      })
+      .collect::<HashMap<_, _>>();
+
+    for (edge, bidirectional) in edges {
+      let (data, from, to) = edges_map.remove(&edge).unwrap();
+      let (new_from, new_to) = (id_map[&from], id_map[&to]);
+      let id = if bidirectional {
+        graph.insert_bi_edge(new_from, new_to, data)
+      } else {
+        graph.insert_edge(new_from, new_to, data)
+      };
+
+      original_map.insert(id.unwrap(), edge);
+    }
+
+    (graph, original_map)
+  }
+}
+
+impl<V: Clone, E: Clone> FiniteGraph<V, E> {
+  /// Returns the [weakly connected components](#method.weakly_connected_components) as
+  /// [induced subgraphs](#method.induced_subgraph) with fresh ids, see
+  /// [`for_each_component`](#method.for_each_component).
+  fn component_graphs(&self) -> Vec<ComponentGraph<V, E>> {
+    self.weakly_connected_components().into_iter()
+    .map(|component| self.induced_subgraph(&component).into_renumbered())
+    .collect()
+  }
+
+  /// Extracts every weakly connected component as a graph of its own and calls `f` with it
+  /// and the map from its vertex and edge ids to the corresponding ids of this graph. Returns
+  /// the results in ascending order of the smallest vertex in each component.
+  ///
+  /// Components keep the relative order of vertex and edge ids as well as bidirectional
+  /// edges, so order-dependent algorithms behave the same on a component as on the whole graph.
+  /// See [`par_for_each_component`](#method.par_for_each_component) for a parallel version.
+  pub fn for_each_component<R: Send>(&self, f: impl Fn(&FiniteGraph<V, E>, &HashMap<Id, Id>) -> R + Sync) -> Vec<R> {
+    self.component_graphs().into_iter()
+    .map(|(graph, original_map)| f(&graph, &original_map))
+    .collect()
+  }
+
+  /// Like [`for_each_component`](#method.for_each_component), but applies `f` to the
+  /// components in parallel. The results are in the same order.
+  #[cfg(feature = "rayon")]
+  pub fn par_for_each_component<R: Send>(&self, f: impl Fn(&FiniteGraph<V, E>, &HashMap<Id, Id>) -> R + Sync) -> Vec<R>
+  where V: Send, E: Send {
+    self.component_graphs().into_par_iter()
+    .map(|(graph, original_map)| f(&graph, &original_map))
+    .collect()
+  }
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns all vertices that can be reached from `vertex` along the edges if `forward` is
+  /// `true`, or against them otherwise, including `vertex` itself.
+  fn reachable_vertices(&self, vertex: Id, forward: bool) -> HashSet<Id> {
+    let map = if forward { &self.neighbors_map } else { &self.reverse_neighbors_map };
+    let mut visited = HashSet::new();
+    let mut stack = vec![vertex];
+
+    visited.insert(vertex);
+
+    while let Some(vertex) = stack.pop() {
+      for &(neighbor, _) in map.get(&vertex).into_iter().flatten() {
+        if visited.insert(neighbor) {
+          stack.push(neighbor);
+        }
+      }
+    }
+
+    visited
+  }
+
+  /// Returns the strongly connected component of `vertex` in ascending order, i.e. all
+  /// vertices that can both be reached from `vertex` and reach `vertex`, including `vertex`
+  /// itself. Returns an empty vector if `vertex` doesn't exist.
+  ///
+  /// This only runs two searches from `vertex`, so it takes `O(V + E)` time regardless of the
+  /// number of components.
+  pub fn scc_of_vertex(&self, vertex: Id) -> Vec<Id> {
+    if !self.contains_vertex(vertex) {
+      return vec![];
+    }
+
+    let backward = self.reachable_vertices(vertex, false);
+    let mut component = self.reachable_vertices(vertex, true).into_iter()
+      .filter(|vertex| backward.contains(vertex))
+      .collect::<Vec<_>>();
+
+    component.sort();
+    component
+  }
+}
+
+impl<V: Clone, E: Clone> FiniteGraph<V, E> {
+  /// Returns the subgraph induced by `vertices`, i.e. clones of the given vertices and of all
+  /// edges between them. Vertices and edges keep their ids, bidirectional edges stay
+  /// bidirectional, and vertices that don't exist are ignored.
+  pub fn induced_subgraph(&self, vertices: &[Id]) -> FiniteGraph<V, E> {
+    let vertex_set = vertices.iter()
+      .copied()
+      .filter(|&vertex| self.contains_vertex(vertex))
+      .collect::<HashSet<_>>();
+    let restrict = |map: &HashMap<Id, Vec<(Id, Id)>>| {
+      vertex_set.iter()
+      .filter_map(|vertex| map.get(vertex).map(|neighbors| (*vertex, neighbors)))
+      .map(|(vertex, neighbors)| {
+        (vertex, neighbors.iter().copied().filter(|(neighbor, _)| vertex_set.contains(neighbor)).collect())
+      })
+      .collect()
+    };
+
+    FiniteGraph {
+      id: self.id,
+      vertices_map: vertex_set.iter().map(|vertex| (*vertex, self.vertices_map[vertex].clone())).collect(),
+      edges_map: self.edges_map.iter()
+        .filter(|(_, (_, from, to))| vertex_set.contains(from) && vertex_set.contains(to))
+        .map(|(&edge, value)| (edge, value.clone()))
+        .collect(),
+      neighbors_map: restrict(&self.neighbors_map),
+      reverse_neighbors_map: restrict(&self.reverse_neighbors_map)
+    }
+  }
+
+  /// Returns the subgraph induced by the [strongly connected component](#method.scc_of_vertex)
+  /// of `vertex`, keeping all ids, or `None` if `vertex` doesn't exist.
+  pub fn strongly_connected_component_subgraph(&self, vertex: Id) -> Option<FiniteGraph<V, E>> {
+    if !self.contains_vertex(vertex) {
+      return None;
+    }
+
+    Some(self.induced_subgraph(&self.scc_of_vertex(vertex)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn for_each_component_maps_back_to_original_ids() {
+    let mut graph = FiniteGraph::<char, u32>::new();
+    let v = "abcdefg".chars().map(|name| graph.insert_vertex(name)).collect::<Vec<_>>();
+
+    graph.insert_edge(v[4], v[0], 1).unwrap();
+    graph.insert_bi_edge(v[0], v[2], 2).unwrap();
+    graph.insert_edge(v[1], v[3], 3).unwrap();
+    graph.insert_edge(v[5], v[1], 4).unwrap();
+    graph.insert_edge(v[2], v[2], 5).unwrap();
+    graph.remove_vertex(v[6]);
+
+    let components = graph.for_each_component(|component, original_map| {
+      let vertices = component.all_vertices_ordered()
+        .map(|(vertex, &name)| {
+          assert_eq!(graph.get_vertex(original_map[&vertex]), Some(&name));
+          name
+        })
+        .collect::<String>();
+
+      for (edge, &weight) in component.all_edges_ordered() {
+        let (_, from, to) = component.edges_map[&edge];
+        let original = original_map[&edge];
+
+        assert_eq!(graph.get_edge(original), Some(&weight));
+        assert_eq!(graph.edges_map[&original].1, original_map[&from]);
+        assert_eq!(graph.edges_map[&original].2, original_map[&to]);
+        assert_eq!(component.is_bi_edge(edge, from, to), graph.is_bi_edge(original, original_map[&from], original_map[&to]));
+      }
+
+      (vertices, component.len().1)
+    });
+
+    assert_eq!(components, vec![("ace".to_string(), 3), ("bdf".to_string(), 2)]);
+    assert_eq!(graph.weakly_connected_components(), vec![vec![v[0], v[2], v[4]], vec![v[1], v[3], v[5]]]);
+
+    #[cfg(feature = "rayon")]
+    assert_eq!(graph.par_for_each_component(|component, _| component.len()), vec![(3, 3), (3, 2)]);
+    assert_eq!(FiniteGraph::<(), ()>::new().for_each_component(|_, _| ()), vec![]);
+  }
+
+  #[test]
+  fn strongly_connected_component_subgraph_keeps_ids() {
+    let mut graph = FiniteGraph::<char, u32>::new();
+    let v = "abcdef".chars().map(|name| graph.insert_vertex(name)).collect::<Vec<_>>();
+
+    // a -> b -> c -> a is a component, d <-> e another one, f is on its own
+    let ab = graph.insert_edge(v[0], v[1], 1).unwrap();
+    graph.insert_edge(v[1], v[2], 2).unwrap();
+    graph.insert_edge(v[2], v[0], 3).unwrap();
+    graph.insert_edge(v[2], v[2], 4).unwrap();
+    graph.insert_edge(v[2], v[3], 5).unwrap();
+    let de = graph.insert_bi_edge(v[3], v[4], 6).unwrap();
+    graph.insert_edge(v[4], v[5], 7).unwrap();
+
+    assert_eq!(graph.scc_of_vertex(v[1]), v[0..3].to_vec());
+    assert_eq!(graph.scc_of_vertex(v[4]), v[3..5].to_vec());
+    assert_eq!(graph.scc_of_vertex(v[5]), vec![v[5]]);
+    assert_eq!(graph.scc_of_vertex(Id(100)), vec![]);
+
+    let component = graph.strongly_connected_component_subgraph(v[0]).unwrap();
+
+    assert_eq!(component.len(), (3, 4));
+    assert_eq!(component.get_edge(ab), Some(&1));
+    assert_eq!(component.out_degree(v[2]), 2);
+    assert_eq!(component.in_degree(v[0]), 1);
+
+    let component = graph.strongly_connected_component_subgraph(v[3]).unwrap();
+
+    assert_eq!(component.len(), (2, 1));
+    assert_eq!(component.parallel_edges_between(v[4], v[3]), vec![(de, &6)]);
+    assert_eq!(component.out_degree(v[4]), 1);
+    assert!(graph.strongly_connected_component_subgraph(Id(100)).is_none());
+
+    let mut component = graph.induced_subgraph(&[v[5], v[4]]);
+
+    assert_eq!(component.len(), (2, 1));
+    let edge = component.insert_edge(v[5], v[4], 8).unwrap();
+
+    assert!(!graph.contains_edge(edge) && !graph.contains_vertex(edge));
+  }
+}
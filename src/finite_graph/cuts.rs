@@ -0,0 +1,314 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use edge::WeightedEdge;
+use super::*;
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns a smallest set of vertices, other than `source` and `sink`, whose removal leaves
+  /// no path from `source` to `sink`, sorted by id. The set is empty if there is no such path
+  /// to begin with.
+  ///
+  /// Each vertex is split into an incoming and an outgoing node connected by a unit capacity
+  /// arc, so by Menger's theorem the cut has the size of
+  /// [`vertex_disjoint_paths`](#method.vertex_disjoint_paths) and is read off the residual
+  /// network of a maximum flow. Returns `None` if `source` and `sink` are equal, one of them
+  /// doesn't exist, or an edge leads from `source` to `sink`, since no vertex cut exists then.
+  /// This is an `Option` instead of a plain `Vec`, because an empty `Vec` already means that
+  /// nothing needs to be removed and couldn't tell these cases apart.
+  pub fn min_vertex_cut(&self, source: Id, sink: Id) -> Option<Vec<Id>> {
+    if source == sink || !self.contains_vertex(source) || !self.contains_vertex(sink)
+      || self.neighbors_map.get(&source).into_iter().flatten().any(|&(neighbor, _)| neighbor == sink)
+    {
+      return None;
+    }
+
+    let (mut network, index_map) = self.vertex_split_flow_network();
+    let (source_node, sink_node) = (2 * index_map[&source] + 1, 2 * index_map[&sink]);
+
+    network.max_flow(source_node, sink_node, usize::MAX);
+
+    let mut reachable = vec![false; network.node_count()];
+    let mut queue = VecDeque::new();
+
+    reachable[source_node] = true;
+    queue.push_back(source_node);
+
+    while let Some(node) = queue.pop_front() {
+      for arc in network.arcs(node) {
+        if arc.capacity > 0 && !reachable[arc.to] {
+          reachable[arc.to] = true;
+          queue.push_back(arc.to);
+        }
+      }
+    }
+
+    // Every saturated arc leaving the reachable nodes is replaced by a vertex it passes
+    // through, which is the split vertex itself or, for edge arcs, the endpoint that is
+    // neither the source nor the sink
+    let vertex_map = index_map.iter()
+      .map(|(&vertex, &index)| (index, vertex))
+      .collect::<HashMap<_, _>>();
+    let mut cut = vec![];
+
+    for node in (0..network.node_count()).filter(|&node| reachable[node]) {
+      for (index, arc) in network.arcs(node).iter().enumerate() {
+        if arc.label.is_none() || reachable[arc.to] || network.flow(node, index) == 0 {
+          continue;
+        }
+
+        let vertex = match arc.label {
+          Some(None) => vertex_map[&(node / 2)],
+          _ if arc.to == sink_node => vertex_map[&(node / 2)],
+          _ => vertex_map[&(arc.to / 2)]
+        };
+
+        cut.push(vertex);
+      }
+    }
+
+    cut.sort();
+    cut.dedup();
+
+    Some(cut)
+  }
+
+  /// Returns a smallest vertex separator of `source` and `sink` like
+  /// [`min_vertex_cut`](#method.min_vertex_cut), but returns an empty vector if no separator
+  /// exists, which can be told apart from vertices that are already separated with
+  /// [`is_vertex_separator`](#method.is_vertex_separator).
+  pub fn min_vertex_separator(&self, source: Id, sink: Id) -> Vec<Id> {
+    self.min_vertex_cut(source, sink).unwrap_or_default()
+  }
+
+  /// Returns `true` if `separator` contains neither `source` nor `sink` and every path from
+  /// `source` to `sink` passes through a vertex of `separator`.
+  pub fn is_vertex_separator(&self, separator: &[Id], source: Id, sink: Id) -> bool {
+    if separator.contains(&source) || separator.contains(&sink) {
+      return false;
+    }
+
+    let mut visited = separator.iter().copied().collect::<HashSet<_>>();
+    let mut queue = VecDeque::new();
+
+    visited.insert(source);
+    queue.push_back(source);
+
+    while let Some(vertex) = queue.pop_front() {
+      if vertex == sink {
+        return false;
+      }
+
+      for &(neighbor, _) in self.neighbors_map.get(&vertex).into_iter().flatten() {
+        if visited.insert(neighbor) {
+          queue.push_back(neighbor);
+        }
+      }
+    }
+
+    true
+  }
+}
+
+impl<V, E: WeightedEdge> FiniteGraph<V, E> {
+  /// Computes a global minimum cut of the graph, i.e. a bipartition of all vertices into two
+  /// non-empty sets, such that the weight sum of all edges between them is minimal. Returns the
+  /// cut weight and both vertex sets, each sorted by id.
+  ///
+  /// Edge directions are ignored, bidirectional edges are counted once, and parallel edges are
+  /// counted individually. The
+  /// [Stoer-Wagner algorithm](https://en.wikipedia.org/wiki/Stoer%E2%80%93Wagner_algorithm)
+  /// runs `V - 1` minimum cut phases, each of which orders the vertices by maximum adjacency
+  /// and [contracts](#method.contract_vertices) the last two vertices, which takes `O(V³)` time.
+  ///
+  /// Graphs with fewer than two vertices have the cut weight `E::default()` and all vertices
+  /// in the first set.
+  pub fn global_min_cut(&self) -> (E, Vec<Id>, Vec<Id>) {
+    let mut vertices = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    vertices.sort();
+
+    // Each vertex of the working graph holds the original vertices it has been merged from
+    let mut working_graph = FiniteGraph::<Vec<Id>, E>::with_capacity(vertices.len(), self.edges_map.len());
+    let id_map = vertices.iter()
+      .map(|&vertex| (vertex, working_graph.insert_vertex(vec![vertex])))
+      .collect::<HashMap<_, _>>();
+
+    for (data, from, to) in self.edges_map.values() {
+      if from != to {
+        working_graph.insert_edge(id_map[from], id_map[to], data.clone());
+      }
+    }
+
+    let mut best = None::<(E, Vec<Id>)>;
+
+    while working_graph.vertices_map.len() > 1 {
+      let mut remaining = working_graph.vertices_map.keys().copied().collect::<Vec<_>>();
+      remaining.sort();
+
+      let mut weight_map = HashMap::<Id, E>::new();
+      let mut order = vec![];
+      let mut last_weight = E::default();
+
+      while !remaining.is_empty() {
+        let index = (0..remaining.len())
+          .min_by(|&i, &j| {
+            let weight = |k: usize| weight_map.get(&remaining[k]).cloned().unwrap_or_default();
+            weight(j).cmp(&weight(i)).then(i.cmp(&j))
+          })
+          .unwrap();
+        let vertex = remaining.remove(index);
+
+        last_weight = weight_map.remove(&vertex).unwrap_or_default();
+        order.push(vertex);
+
+        for (neighbor, edge) in working_graph.undirected_neighbors(vertex) {
+          if remaining.contains(&neighbor) {
+            let weight = weight_map.entry(neighbor).or_default();
+            *weight = weight.clone() + working_graph.edges_map[&edge].0.clone();
+          }
+        }
+      }
+
+      let t = order.pop().unwrap();
+      let s = order.pop().unwrap();
+
+      if best.as_ref().is_none_or(|(weight, _)| &last_weight < weight) {
+        best = Some((last_weight, working_graph.vertices_map[&t].clone()));
+      }
+
+      let merged = working_graph.contract_vertices(s, t).unwrap();
+      working_graph.get_vertex_mut(s).unwrap().extend(merged);
+    }
+
+    match best {
+      None => (E::default(), vertices, vec![]),
+      Some((weight, mut cut)) => {
+        cut.sort();
+
+        let rest = vertices.into_iter()
+          .filter(|vertex| cut.binary_search(vertex).is_err())
+          .collect();
+
+        (weight, cut, rest)
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn min_vertex_cut_isolates_sink() {
+    // Two routes from `a` that meet in `d` and `e`, both of which lead to `f`
+    let mut graph = FiniteGraph::<char, ()>::new();
+    let ids = "abcdefg".chars()
+      .map(|name| (name, graph.insert_vertex(name)))
+      .collect::<HashMap<_, _>>();
+
+    for (from, to) in &[('a', 'b'), ('a', 'c'), ('b', 'd'), ('c', 'd'), ('c', 'e'), ('d', 'f'), ('e', 'f')] {
+      graph.insert_bi_edge(ids[from], ids[to], ()).unwrap();
+    }
+
+    let (a, f) = (ids[&'a'], ids[&'f']);
+    let cut = graph.min_vertex_cut(a, f).unwrap();
+
+    assert_eq!(cut.len(), graph.vertex_disjoint_paths(a, f).0);
+    assert!([['b', 'c'], ['c', 'd'], ['d', 'e']].iter().any(|names| cut == [ids[&names[0]], ids[&names[1]]]));
+    assert_eq!(graph.min_vertex_cut(a, ids[&'g']), Some(vec![]));
+    assert_eq!(graph.min_vertex_cut(a, ids[&'b']), None);
+    assert_eq!(graph.min_vertex_cut(ids[&'b'], a), None);
+    assert_eq!(graph.min_vertex_cut(a, a), None);
+
+    let mut directed = FiniteGraph::<(), ()>::new();
+    let v = (0..3).map(|_| directed.insert_vertex(())).collect::<Vec<_>>();
+
+    directed.insert_edge(v[0], v[1], ()).unwrap();
+    directed.insert_edge(v[1], v[2], ()).unwrap();
+    directed.insert_edge(v[2], v[0], ()).unwrap();
+
+    assert_eq!(directed.min_vertex_cut(v[0], v[2]), Some(vec![v[1]]));
+    assert_eq!(directed.min_vertex_cut(v[2], v[0]), None);
+  }
+
+  #[test]
+  fn min_vertex_cut_from_vertex_without_outgoing_edges() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+
+    graph.insert_edge(b, a, ()).unwrap();
+
+    assert_eq!(graph.min_vertex_cut(a, b), Some(vec![]));
+    assert_eq!(graph.min_vertex_separator(a, b), vec![]);
+    assert_eq!(graph.min_vertex_separator(b, a), vec![]);
+  }
+
+  #[test]
+  fn min_vertex_separator_is_vertex_separator() {
+    // A 4x4 grid, where the corners are separated by their two neighbors
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..16).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..16 {
+      if i % 4 < 3 {
+        graph.insert_bi_edge(v[i], v[i + 1], ()).unwrap();
+      }
+
+      if i < 12 {
+        graph.insert_bi_edge(v[i], v[i + 4], ()).unwrap();
+      }
+    }
+
+    let separator = graph.min_vertex_separator(v[0], v[15]);
+
+    assert_eq!(separator.len(), 2);
+    assert!(graph.is_vertex_separator(&separator, v[0], v[15]));
+    assert!(!graph.is_vertex_separator(&separator[..1], v[0], v[15]));
+    assert!(!graph.is_vertex_separator(&[v[0], v[1], v[4]], v[0], v[15]));
+    assert!(graph.is_vertex_separator(&[v[3], v[6], v[9], v[12]], v[0], v[15]));
+    assert_eq!(graph.min_vertex_separator(v[0], v[1]), vec![]);
+    assert!(!graph.is_vertex_separator(&[], v[0], v[1]));
+  }
+
+  #[test]
+  fn global_min_cut_of_stoer_wagner_example() {
+    // The example graph of the original paper, with a minimum cut of weight 4
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..8).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for &(from, to, weight) in &[
+      (0, 1, 2), (0, 4, 3), (1, 2, 3), (1, 4, 2), (1, 5, 2), (2, 3, 4), (2, 6, 2),
+      (3, 6, 2), (3, 7, 2), (4, 5, 3), (5, 6, 1), (6, 7, 3)
+    ] {
+      graph.insert_bi_edge(v[from], v[to], weight).unwrap();
+    }
+
+    let (weight, a, b) = graph.global_min_cut();
+    let mut sides = vec![a, b];
+    sides.sort();
+
+    assert_eq!(weight, 4);
+    assert_eq!(sides, vec![vec![v[0], v[1], v[4], v[5]], vec![v[2], v[3], v[6], v[7]]]);
+  }
+
+  #[test]
+  fn global_min_cut_of_small_graphs() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+
+    assert_eq!(graph.global_min_cut(), (0, vec![], vec![]));
+
+    let a = graph.insert_vertex(());
+
+    assert_eq!(graph.global_min_cut(), (0, vec![a], vec![]));
+
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+
+    graph.insert_edge(a, b, 5).unwrap();
+    graph.insert_edge(b, a, 5).unwrap();
+    graph.insert_edge(b, c, 3).unwrap();
+    graph.insert_edge(c, c, 1).unwrap();
+
+    assert_eq!(graph.global_min_cut(), (3, vec![c], vec![a, b]));
+  }
+}
@@ -0,0 +1,289 @@
+use std::collections::{HashMap, HashSet};
+use edge::WeightedEdge;
+use super::*;
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Groups all vertices into topological generations, or returns `None` if the graph
+  /// contains a cycle.
+  ///
+  /// The first generation consists of all vertices without incoming edges, and each following
+  /// generation consists of all vertices whose incoming edges all come from previous generations.
+  /// No vertex depends on another vertex of the same generation. Each generation is sorted by id.
+  pub fn topological_generations(&self) -> Option<Vec<Vec<Id>>> {
+    let mut in_degree_map = self.vertices_map.keys()
+      .map(|&vertex| (vertex, self.in_degree(vertex)))
+      .collect::<HashMap<_, _>>();
+
+    let mut generation = in_degree_map.iter()
+      .filter(|&(_, &in_degree)| in_degree == 0)
+      .map(|(&vertex, _)| vertex)
+      .collect::<Vec<_>>();
+
+    let mut generations = vec![];
+    let mut visited_count = 0;
+
+    while !generation.is_empty() {
+      generation.sort();
+      visited_count += generation.len();
+
+      let mut next_generation = vec![];
+
+      for &vertex in &generation {
+        for neighbor in self.neighbors(&vertex) {
+          let in_degree = in_degree_map.get_mut(&neighbor).unwrap();
+          *in_degree -= 1;
+
+          if *in_degree == 0 {
+            next_generation.push(neighbor);
+          }
+        }
+      }
+
+      generations.push(generation);
+      generation = next_generation;
+    }
+
+    if visited_count == self.vertices_map.len() {
+      Some(generations)
+    } else {
+      None
+    }
+  }
+
+  fn longest_path_in_dag_by<W: WeightedEdge>(&self, weight: impl Fn(&E) -> W) -> Option<(Vec<Id>, W)> {
+    let order = self.topological_generations()?.into_iter().flatten().collect::<Vec<_>>();
+    let mut distance_map = HashMap::<Id, (W, Option<Id>)>::new();
+    let mut last = None::<(Id, W)>;
+
+    for vertex in order {
+      let mut best = (W::default(), None);
+
+      for &(predecessor, edge) in self.reverse_neighbors_map.get(&vertex).into_iter().flatten() {
+        let distance = distance_map[&predecessor].0.clone() + weight(&self.edges_map[&edge].0);
+
+        if best.1.is_none() || distance > best.0 {
+          best = (distance, Some(predecessor));
+        }
+      }
+
+      if last.as_ref().is_none_or(|(_, distance)| &best.0 > distance) {
+        last = Some((vertex, best.0.clone()));
+      }
+
+      distance_map.insert(vertex, best);
+    }
+
+    let (end, distance) = match last {
+      Some(last) => last,
+      None => return Some((vec![], W::default()))
+    };
+
+    let mut path = vec![end];
+
+    while let Some(predecessor) = distance_map[path.last().unwrap()].1 {
+      path.push(predecessor);
+    }
+
+    path.reverse();
+    Some((path, distance))
+  }
+
+  /// Returns a longest path of the graph in terms of number of edges, or `None` if the graph
+  /// contains a cycle.
+  ///
+  /// This is known as the critical path of a directed acyclic graph. Returns an empty path
+  /// if the graph has no vertices.
+  pub fn longest_path_in_dag(&self) -> Option<Vec<Id>> {
+    self.longest_path_in_dag_by(|_| 1usize).map(|(path, _)| path)
+  }
+
+  /// Returns a path of the graph with the largest sum of edge weights together with its weight,
+  /// or `None` if the graph contains a cycle.
+  ///
+  /// This is known as the critical path of a directed acyclic graph. If there are parallel
+  /// edges, the heaviest one is taken. Returns an empty path with weight `E::default()` if the
+  /// graph has no vertices.
+  pub fn longest_path_in_dag_weighted(&self) -> Option<(Vec<Id>, E)> where E: WeightedEdge {
+    self.longest_path_in_dag_by(E::clone)
+  }
+
+  /// Returns the edges that are kept by the transitive reduction, sorted by id, or `None` if
+  /// the graph contains a cycle.
+  fn transitive_reduction_edges(&self) -> Option<Vec<Id>> {
+    let order = self.topological_generations()?.into_iter().flatten().collect::<Vec<_>>();
+    let position_map = order.iter().enumerate()
+      .map(|(position, &vertex)| (vertex, position))
+      .collect::<HashMap<_, _>>();
+    let mut reachable_map = HashMap::<Id, HashSet<Id>>::new();
+    let mut kept = vec![];
+
+    for &vertex in order.iter().rev() {
+      let mut neighbors = self.neighbors_map.get(&vertex).cloned().unwrap_or_default();
+      let mut reachable = HashSet::new();
+
+      // A neighbor can only be reached through neighbors that come before it topologically,
+      // and among parallel edges the one with the smallest id is kept
+      neighbors.sort_by_key(|&(neighbor, edge)| (position_map[&neighbor], edge));
+
+      for (neighbor, edge) in neighbors {
+        if reachable.insert(neighbor) {
+          reachable.extend(reachable_map[&neighbor].iter().copied());
+          kept.push(edge);
+        }
+      }
+
+      reachable_map.insert(vertex, reachable);
+    }
+
+    kept.sort();
+    Some(kept)
+  }
+
+  /// Returns the transitive reduction of the graph, or `None` if the graph contains a cycle.
+  ///
+  /// The transitive reduction of a directed acyclic graph is the unique graph with the fewest
+  /// edges that has the same reachability relation. It keeps all vertices with their ids and an
+  /// edge from `u` to `v` if and only if the graph has an edge from `u` to `v`, but no other
+  /// path from `u` to `v`. Parallel edges are merged into a single edge.
+  ///
+  /// This takes `O(V * (V + E))` time and `O(V^2)` space.
+  pub fn transitive_reduction(&self) -> Option<FiniteGraph<V, ()>> where V: Clone {
+    let kept = self.transitive_reduction_edges()?;
+    let mut result = self.clone_vertices();
+
+    for edge in kept {
+      let &(_, from, to) = &self.edges_map[&edge];
+      result.insert_edge(from, to, ());
+    }
+
+    Some(result)
+  }
+
+  /// Replaces the graph with its [transitive reduction](#method.transitive_reduction) by
+  /// removing redundant edges, where the edge with the smallest id is kept among parallel
+  /// edges. Returns `false` and leaves the graph unchanged if it contains a cycle.
+  pub fn reduce_transitively(&mut self) -> bool {
+    let kept = match self.transitive_reduction_edges() {
+      Some(kept) => kept.into_iter().collect::<HashSet<_>>(),
+      None => return false
+    };
+
+    let redundant = self.edges_map.keys()
+      .copied()
+      .filter(|edge| !kept.contains(edge))
+      .collect::<Vec<_>>();
+
+    for edge in redundant {
+      self.remove_edge(edge);
+    }
+
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn topological_generations_of_dag() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+
+    assert_eq!(graph.topological_generations(), Some(vec![]));
+
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+    let d = graph.insert_vertex(());
+    let e = graph.insert_vertex(());
+
+    graph.insert_edge(a, c, ()).unwrap();
+    graph.insert_edge(b, c, ()).unwrap();
+    graph.insert_edge(c, d, ()).unwrap();
+    graph.insert_edge(a, d, ()).unwrap();
+    graph.insert_edge(a, d, ()).unwrap();
+
+    assert_eq!(graph.topological_generations(), Some(vec![vec![a, b, e], vec![c], vec![d]]));
+
+    graph.insert_edge(d, b, ()).unwrap();
+
+    assert_eq!(graph.topological_generations(), None);
+  }
+
+  #[test]
+  fn bi_edges_and_self_loops_are_cycles() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let e = graph.insert_bi_edge(a, b, ()).unwrap();
+
+    assert_eq!(graph.topological_generations(), None);
+
+    graph.remove_edge(e);
+    graph.insert_edge(a, a, ()).unwrap();
+
+    assert_eq!(graph.topological_generations(), None);
+  }
+
+  #[test]
+  fn critical_path_of_dag() {
+    let mut graph = FiniteGraph::<&str, u32>::new();
+
+    assert_eq!(graph.longest_path_in_dag(), Some(vec![]));
+    assert_eq!(graph.longest_path_in_dag_weighted(), Some((vec![], 0)));
+
+    let a = graph.insert_vertex("a");
+    let b = graph.insert_vertex("b");
+    let c = graph.insert_vertex("c");
+    let d = graph.insert_vertex("d");
+    let e = graph.insert_vertex("e");
+
+    graph.insert_edge(a, b, 3).unwrap();
+    graph.insert_edge(a, c, 2).unwrap();
+    graph.insert_edge(b, d, 4).unwrap();
+    graph.insert_edge(c, d, 7).unwrap();
+    graph.insert_edge(c, e, 1).unwrap();
+    graph.insert_edge(d, e, 2).unwrap();
+    graph.insert_edge(b, e, 1).unwrap();
+
+    assert_eq!(graph.longest_path_in_dag_weighted(), Some((vec![a, c, d, e], 11)));
+    assert_eq!(graph.longest_path_in_dag().map(|path| path.len()), Some(4));
+
+    graph.insert_edge(e, a, 1).unwrap();
+
+    assert_eq!(graph.longest_path_in_dag(), None);
+    assert_eq!(graph.longest_path_in_dag_weighted(), None);
+  }
+
+  #[test]
+  fn transitive_reduction_removes_shortcuts() {
+    let mut graph = FiniteGraph::<&str, u32>::new();
+    let a = graph.insert_vertex("a");
+    let b = graph.insert_vertex("b");
+    let c = graph.insert_vertex("c");
+    let d = graph.insert_vertex("d");
+
+    let e1 = graph.insert_edge(a, b, 1).unwrap();
+    graph.insert_edge(a, c, 2).unwrap();
+    let e3 = graph.insert_edge(b, c, 3).unwrap();
+    graph.insert_edge(a, d, 4).unwrap();
+    let e5 = graph.insert_edge(c, d, 5).unwrap();
+    graph.insert_edge(b, c, 6).unwrap();
+
+    let reduction = graph.transitive_reduction().unwrap();
+    let mut edges = reduction.into_iter().map(|(from, to, _)| (from, to)).collect::<Vec<_>>();
+    edges.sort();
+
+    assert_eq!(edges, vec![("a", "b"), ("b", "c"), ("c", "d")]);
+    assert!(graph.reduce_transitively());
+    assert_eq!(graph.len(), (4, 3));
+    assert!(graph.contains_edge(e1) && graph.contains_edge(e3) && graph.contains_edge(e5));
+
+    graph.insert_edge(d, a, 7).unwrap();
+
+    assert!(graph.transitive_reduction().is_none());
+    assert!(!graph.reduce_transitively());
+    assert_eq!(graph.len(), (4, 4));
+  }
+}
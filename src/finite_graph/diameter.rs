@@ -0,0 +1,208 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use rand::RngCore;
+use edge::WeightedEdge;
+use super::*;
+
+/// Returns the vertex with the largest distance together with the distance, where ties are
+/// broken by the smallest id.
+fn farthest<W: WeightedEdge>(distance_map: &HashMap<Id, W>) -> (Id, W) {
+  distance_map.iter()
+  .max_by(|(vertex, distance), (other, other_distance)| distance.cmp(other_distance).then(other.cmp(vertex)))
+  .map(|(&vertex, distance)| (vertex, distance.clone()))
+  .unwrap()
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns the smallest weight sums from `start` to all vertices of its component together
+  /// with the shortest path tree as a parent map, ignoring edge directions.
+  fn undirected_distances<W: WeightedEdge>(&self, start: Id, weight: &impl Fn(Id) -> W) -> (HashMap<Id, W>, HashMap<Id, Id>) {
+    let mut distance_map = HashMap::new();
+    let mut parent_map = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    queue.push(Reverse((W::default(), start, None)));
+
+    while let Some(Reverse((distance, vertex, parent))) = queue.pop() {
+      if distance_map.contains_key(&vertex) {
+        continue;
+      }
+
+      if let Some(parent) = parent {
+        parent_map.insert(vertex, parent);
+      }
+
+      distance_map.insert(vertex, distance.clone());
+
+      for (neighbor, edge) in self.undirected_neighbors(vertex) {
+        if !distance_map.contains_key(&neighbor) {
+          queue.push(Reverse((distance.clone() + weight(edge), neighbor, Some(vertex))));
+        }
+      }
+    }
+
+    (distance_map, parent_map)
+  }
+
+  /// See [`approx_diameter`](#method.approx_diameter).
+  fn approx_diameter_with<W: WeightedEdge>(&self, samples: usize, mut rng: impl RngCore, weight: impl Fn(Id) -> W) -> (W, W) {
+    let mut visited = HashSet::new();
+    let mut largest_component = vec![];
+
+    for (vertex, _) in self.all_vertices_ordered() {
+      if visited.contains(&vertex) {
+        continue;
+      }
+
+      let mut component = self.undirected_distances(vertex, &|_| 1usize).0.into_keys().collect::<Vec<_>>();
+
+      visited.extend(component.iter().copied());
+
+      if component.len() > largest_component.len() {
+        component.sort();
+        largest_component = component;
+      }
+    }
+
+    if largest_component.is_empty() {
+      return (W::default(), W::default());
+    }
+
+    let sample_size = samples.clamp(1, largest_component.len());
+    let mut lower = W::default();
+    let mut upper = None::<W>;
+    let mut tighten_upper = |eccentricity: W| {
+      let bound = eccentricity.clone() + eccentricity;
+
+      if upper.as_ref().is_none_or(|upper| &bound < upper) {
+        upper = Some(bound);
+      }
+    };
+
+    for index in rand::seq::index::sample(&mut rng, largest_component.len(), sample_size) {
+      // The first sweep finds a vertex far away from the start, the second sweep from there
+      // finds a long shortest path
+      let (start_distances, _) = self.undirected_distances(largest_component[index], &weight);
+      let (far, start_eccentricity) = farthest(&start_distances);
+      let (far_distances, far_parents) = self.undirected_distances(far, &weight);
+      let (other_far, far_eccentricity) = farthest(&far_distances);
+
+      tighten_upper(start_eccentricity);
+      tighten_upper(far_eccentricity.clone());
+
+      // The vertex halfway along that path is a good center candidate
+      let mut center = other_far;
+
+      while let Some(&parent) = far_parents.get(&center) {
+        if far_distances[&parent].clone() + far_distances[&parent].clone() < far_eccentricity {
+          break;
+        }
+
+        center = parent;
+      }
+
+      tighten_upper(farthest(&self.undirected_distances(center, &weight).0).1);
+
+      if far_eccentricity > lower {
+        lower = far_eccentricity;
+      }
+    }
+
+    (lower, upper.unwrap())
+  }
+
+  /// Returns a lower and an upper bound on the diameter, i.e. the largest number of edges on a
+  /// shortest path between two vertices, of the largest connected component, ignoring edge
+  /// directions. Among components of the same size, the one with the smallest id is chosen, and
+  /// `(0, 0)` is returned if the graph has no vertices.
+  ///
+  /// For each of `samples` randomly chosen start vertices, but at least one, a breadth-first
+  /// search finds a vertex far away from the start, and a second search from there finds a long
+  /// shortest path, whose length is a lower bound. Since every vertex is at most its
+  /// eccentricity away from all other vertices, twice the eccentricity of any vertex is an upper
+  /// bound, which is computed for the start vertices, the far vertices, and the vertices halfway
+  /// along the long paths. Each sample takes `O(V + E)` time, and the bounds are often tight.
+  pub fn approx_diameter(&self, samples: usize, rng: impl RngCore) -> (usize, usize) {
+    self.approx_diameter_with(samples, rng, |_| 1)
+  }
+
+  /// Like [`approx_diameter`](#method.approx_diameter), but returns bounds on the largest
+  /// weight sum of a shortest path between two vertices of the largest connected component,
+  /// which takes `O(E log V)` time per sample. Like
+  /// [`EdgedGraph::dijkstra`](trait.EdgedGraph.html#method.dijkstra), only non-negative weights
+  /// are supported.
+  pub fn approx_weighted_diameter(&self, samples: usize, rng: impl RngCore) -> (E, E) where E: WeightedEdge {
+    self.approx_diameter_with(samples, rng, |edge| self.edges_map[&edge].0.clone())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rand::SeedableRng;
+  use rand::rngs::StdRng;
+
+  /// Computes the exact diameter of the largest component by searching from every vertex.
+  fn exact_diameter<V, E, W: WeightedEdge>(graph: &FiniteGraph<V, E>, weight: impl Fn(Id) -> W) -> W {
+    let mut components = graph.all_vertices_ordered()
+      .map(|(vertex, _)| graph.undirected_distances(vertex, &weight).0)
+      .collect::<Vec<_>>();
+
+    components.sort_by_key(|distance_map| Reverse(distance_map.len()));
+
+    let size = components.first().map_or(0, HashMap::len);
+
+    components.into_iter()
+    .take_while(|distance_map| distance_map.len() == size)
+    .flat_map(|distance_map| distance_map.into_values())
+    .max()
+    .unwrap_or_default()
+  }
+
+  #[test]
+  fn approx_diameter_brackets_exact_diameter() {
+    // A cycle of length 9 with a path of length 4 attached, and a separate triangle
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..16).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..9 {
+      graph.insert_edge(v[i], v[(i + 1) % 9], i as u32 + 1).unwrap();
+    }
+
+    for i in 8..12 {
+      graph.insert_bi_edge(v[i + 1], v[i], 3).unwrap();
+    }
+
+    for i in 13..16 {
+      graph.insert_edge(v[i], v[13 + (i - 12) % 3], 100).unwrap();
+    }
+
+    graph.insert_edge(v[0], v[0], 1).unwrap();
+
+    let exact = exact_diameter(&graph, |_| 1usize);
+    let exact_weighted = exact_diameter(&graph, |edge| *graph.get_edge(edge).unwrap());
+
+    assert_eq!(exact, 8);
+
+    for seed in 0..10 {
+      let (lower, upper) = graph.approx_diameter(2, StdRng::seed_from_u64(seed));
+
+      assert!(lower <= exact && exact <= upper, "{} <= {} <= {}", lower, exact, upper);
+
+      let (lower, upper) = graph.approx_weighted_diameter(2, StdRng::seed_from_u64(seed));
+
+      assert!(lower <= exact_weighted && exact_weighted <= upper);
+    }
+
+    // On a path, a double sweep always finds the diameter
+    let mut path = FiniteGraph::<(), ()>::new();
+    let v = (0..6).map(|_| path.insert_vertex(())).collect::<Vec<_>>();
+
+    for pair in v.windows(2) {
+      path.insert_edge(pair[0], pair[1], ()).unwrap();
+    }
+
+    assert_eq!(path.approx_diameter(1, StdRng::seed_from_u64(0)).0, 5);
+    assert_eq!(FiniteGraph::<(), ()>::new().approx_diameter(3, StdRng::seed_from_u64(0)), (0, 0));
+  }
+}
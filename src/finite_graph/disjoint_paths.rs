@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use flow_network::FlowNetwork;
+use super::*;
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Builds a unit capacity flow network with an arc for every edge direction, labeled by the
+  /// edge, between the nodes given by `tail` and `head` of the index of each vertex, which are
+  /// passed sorted by id. Self-loops are left out.
+  fn edge_flow_network(
+    &self,
+    node_count: usize,
+    tail: impl Fn(usize) -> usize,
+    head: impl Fn(usize) -> usize
+  ) -> (FlowNetwork<Option<Id>>, HashMap<Id, usize>) {
+    let mut vertices = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    vertices.sort();
+
+    let index_map = vertices.into_iter()
+      .enumerate()
+      .map(|(index, vertex)| (vertex, index))
+      .collect::<HashMap<_, _>>();
+    let mut network = FlowNetwork::new(node_count);
+
+    for (&edge, &(_, from, to)) in self.edges_ordered() {
+      if from == to {
+        continue;
+      }
+
+      let (from_index, to_index) = (index_map[&from], index_map[&to]);
+      network.add_arc(tail(from_index), head(to_index), 1, Some(edge));
+
+      if self.is_bi_edge(edge, from, to) {
+        network.add_arc(tail(to_index), head(from_index), 1, Some(edge));
+      }
+    }
+
+    (network, index_map)
+  }
+
+  /// Iterates over all edges in ascending order of their ids.
+  fn edges_ordered(&self) -> impl Iterator<Item = (&Id, &(E, Id, Id))> {
+    let mut edges = self.edges_map.iter().collect::<Vec<_>>();
+    edges.sort_by_key(|&(&edge, _)| edge);
+    edges.into_iter()
+  }
+
+  /// Decomposes the flow of `network` from `source` to `sink`, which has value `count`, into
+  /// `count` paths given by the edges along them. Flow along both directions of a
+  /// bidirectional edge cancels out and flow cycles are dropped.
+  fn decompose_flow(network: &FlowNetwork<Option<Id>>, source: usize, sink: usize, count: usize) -> Vec<Vec<Id>> {
+    let mut used_arcs = vec![];
+    let mut direction_map = HashMap::<Id, Vec<(usize, usize)>>::new();
+
+    for node in 0..network.node_count() {
+      for (index, arc) in network.arcs(node).iter().enumerate() {
+        if let Some(label) = arc.label {
+          if network.flow(node, index) > 0 {
+            used_arcs.push((node, arc.to, label));
+
+            if let Some(edge) = label {
+              direction_map.entry(edge).or_default().push((node, arc.to));
+            }
+          }
+        }
+      }
+    }
+
+    let mut remaining = HashMap::<usize, Vec<(usize, Option<Id>)>>::new();
+
+    for (from, to, label) in used_arcs.into_iter().rev() {
+      let cancelled = label.is_some_and(|edge| direction_map[&edge].len() > 1);
+
+      if !cancelled {
+        remaining.entry(from).or_default().push((to, label));
+      }
+    }
+
+    (0..count).map(|_| {
+      let mut nodes = vec![source];
+      let mut labels = vec![];
+
+      while *nodes.last().unwrap() != sink {
+        let (to, label) = remaining.get_mut(nodes.last().unwrap()).unwrap().pop().unwrap();
+
+        if let Some(position) = nodes.iter().position(|&node| node == to) {
+          nodes.truncate(position + 1);
+          labels.truncate(position);
+        } else {
+          nodes.push(to);
+          labels.push(label);
+        }
+      }
+
+      labels.into_iter().flatten().collect()
+    })
+    .collect()
+  }
+
+  /// Returns the maximum number of `source`-`sink` paths that don't share any edges together
+  /// with such paths, each given by the ids of the edges along it. Bidirectional edges can be
+  /// used in either direction, but only by one path. Self-loops are never used and the paths
+  /// contain no cycles.
+  ///
+  /// By Menger's theorem, the number of paths equals the smallest number of edges whose removal
+  /// disconnects `sink` from `source`. This computes a maximum flow with unit capacities and
+  /// decomposes it into paths, which takes `O(k * (V + E))` time for `k` paths. Returns no paths
+  /// if `source` and `sink` are equal or one of them doesn't exist.
+  pub fn edge_disjoint_paths(&self, source: Id, sink: Id) -> (usize, Vec<Vec<Id>>) {
+    if source == sink || !self.contains_vertex(source) || !self.contains_vertex(sink) {
+      return (0, vec![]);
+    }
+
+    let (mut network, index_map) = self.edge_flow_network(self.vertices_map.len(), |i| i, |i| i);
+    let (source, sink) = (index_map[&source], index_map[&sink]);
+    let count = network.max_flow(source, sink, usize::MAX);
+
+    (count, FiniteGraph::<V, E>::decompose_flow(&network, source, sink, count))
+  }
+
+  /// Returns the maximum number of `source`-`sink` paths that don't share any vertices other
+  /// than `source` and `sink` together with such paths, each given by the ids of the edges
+  /// along it. The paths don't share edges either, so parallel edges from `source` to `sink`
+  /// form separate paths. Self-loops are never used and the paths contain no cycles.
+  ///
+  /// By Menger's theorem, the number of paths equals the smallest number of vertices whose
+  /// removal disconnects `sink` from `source`, if there is no edge from `source` to `sink`.
+  /// This splits every vertex into an incoming and an outgoing copy connected by an edge with
+  /// unit capacity and proceeds like [`edge_disjoint_paths`](#method.edge_disjoint_paths).
+  pub fn vertex_disjoint_paths(&self, source: Id, sink: Id) -> (usize, Vec<Vec<Id>>) {
+    if source == sink || !self.contains_vertex(source) || !self.contains_vertex(sink) {
+      return (0, vec![]);
+    }
+
+    let (mut network, index_map) = self.vertex_split_flow_network();
+    let (source, sink) = (2 * index_map[&source] + 1, 2 * index_map[&sink]);
+    let count = network.max_flow(source, sink, usize::MAX);
+
+    (count, FiniteGraph::<V, E>::decompose_flow(&network, source, sink, count))
+  }
+
+  /// Returns the maximum number of `source`-`sink` paths that don't share any vertices other
+  /// than `source` and `sink`, without constructing the paths like
+  /// [`vertex_disjoint_paths`](#method.vertex_disjoint_paths) does. By Menger's theorem, this
+  /// is the size of a [`min_vertex_cut`](#method.min_vertex_cut) unless an edge leads from
+  /// `source` to `sink`, in which case no vertex cut exists and `min_vertex_cut` returns `None`.
+  pub fn max_vertex_disjoint_paths(&self, source: Id, sink: Id) -> usize {
+    if source == sink || !self.contains_vertex(source) || !self.contains_vertex(sink) {
+      return 0;
+    }
+
+    let (mut network, index_map) = self.vertex_split_flow_network();
+
+    network.max_flow(2 * index_map[&source] + 1, 2 * index_map[&sink], usize::MAX)
+  }
+
+  /// Builds the unit capacity flow network where vertex `i` is split into an incoming node `2i`
+  /// and an outgoing node `2i + 1`, which are connected by an unlabeled arc.
+  pub(crate) fn vertex_split_flow_network(&self) -> (FlowNetwork<Option<Id>>, HashMap<Id, usize>) {
+    let n = self.vertices_map.len();
+    let (mut network, index_map) = self.edge_flow_network(2 * n, |i| 2 * i + 1, |i| 2 * i);
+
+    for i in 0..n {
+      network.add_arc(2 * i, 2 * i + 1, 1, None);
+    }
+
+    (network, index_map)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashSet;
+
+  /// Returns `true` if all paths lead from `source` to `sink` and no two paths share an edge
+  /// or, if `vertex_disjoint` is `true`, an inner vertex.
+  fn are_disjoint_paths<V, E>(
+    graph: &FiniteGraph<V, E>,
+    source: Id,
+    sink: Id,
+    paths: &[Vec<Id>],
+    vertex_disjoint: bool
+  ) -> bool {
+    let mut used_edges = HashSet::new();
+    let mut used_vertices = HashSet::new();
+
+    paths.iter().all(|path| {
+      let mut vertex = source;
+
+      path.iter().all(|&edge| {
+        let &(_, from, to) = &graph.edges_map[&edge];
+
+        if from == vertex {
+          vertex = to;
+        } else if to == vertex && graph.is_bi_edge(edge, from, to) {
+          vertex = from;
+        } else {
+          return false;
+        }
+
+        used_edges.insert(edge) && (!vertex_disjoint || vertex == sink || used_vertices.insert(vertex))
+      }) && vertex == sink
+    })
+  }
+
+  /// Two parallel routes `a - b - d` and `a - c - d` that meet in the bottleneck `d` and
+  /// continue to `f` via `d - e - f` and `d - f`.
+  fn bottleneck_graph() -> (FiniteGraph<char, ()>, HashMap<char, Id>) {
+    let mut graph = FiniteGraph::new();
+    let ids = "abcdef".chars()
+      .map(|name| (name, graph.insert_vertex(name)))
+      .collect::<HashMap<_, _>>();
+
+    for (from, to) in &[('a', 'b'), ('a', 'c'), ('b', 'd'), ('c', 'd'), ('d', 'e'), ('e', 'f'), ('d', 'f')] {
+      graph.insert_edge(ids[from], ids[to], ()).unwrap();
+    }
+
+    (graph, ids)
+  }
+
+  #[test]
+  fn disjoint_paths_match_mengers_theorem() {
+    let (mut graph, ids) = bottleneck_graph();
+    let (a, d, f) = (ids[&'a'], ids[&'d'], ids[&'f']);
+
+    let (count, paths) = graph.edge_disjoint_paths(a, f);
+
+    assert_eq!(count, 2);
+    assert_eq!(paths.len(), 2);
+    assert!(are_disjoint_paths(&graph, a, f, &paths, false));
+
+    let (count, paths) = graph.vertex_disjoint_paths(a, f);
+
+    assert_eq!(count, 1);
+    assert!(are_disjoint_paths(&graph, a, f, &paths, true));
+    assert_eq!(graph.vertex_disjoint_paths(a, d).0, 2);
+    assert_eq!(graph.max_vertex_disjoint_paths(a, f), 1);
+    assert_eq!(graph.min_vertex_cut(a, f), Some(vec![d]));
+
+    graph.insert_edge(a, f, ()).unwrap();
+    graph.insert_edge(a, f, ()).unwrap();
+    let back = graph.insert_edge(f, a, ()).unwrap();
+
+    assert_eq!(graph.vertex_disjoint_paths(a, f).0, 3);
+    assert_eq!(graph.max_vertex_disjoint_paths(a, f), 3);
+    assert_eq!(graph.max_vertex_disjoint_paths(a, a), 0);
+    assert_eq!(graph.edge_disjoint_paths(a, f).0, 4);
+    assert_eq!(graph.edge_disjoint_paths(f, a), (1, vec![vec![back]]));
+    assert_eq!(graph.edge_disjoint_paths(a, a), (0, vec![]));
+  }
+
+  #[test]
+  fn max_vertex_disjoint_paths_equals_min_vertex_cut() {
+    // s reaches t via a - x, b - x, a - y and c - y, so all paths pass through x or y and at
+    // most two of them are vertex-disjoint, e.g. s - b - x - t and s - c - y - t
+    let mut graph = FiniteGraph::new();
+    let ids = "sabcxyt".chars()
+      .map(|name| (name, graph.insert_vertex(name)))
+      .collect::<HashMap<_, _>>();
+
+    for (from, to) in &[
+      ('s', 'a'), ('s', 'b'), ('s', 'c'), ('a', 'x'), ('b', 'x'), ('a', 'y'), ('c', 'y'),
+      ('x', 't'), ('y', 't')
+    ] {
+      graph.insert_edge(ids[from], ids[to], ()).unwrap();
+    }
+
+    let (s, t) = (ids[&'s'], ids[&'t']);
+
+    assert_eq!(graph.max_vertex_disjoint_paths(s, t), 2);
+    assert_eq!(graph.min_vertex_cut(s, t), Some(vec![ids[&'x'], ids[&'y']]));
+
+    // Without y, the vertex a can still reach t, but only through x
+    let mut without_y = graph.clone();
+
+    without_y.remove_vertex(ids[&'y']);
+
+    assert_eq!(without_y.max_vertex_disjoint_paths(s, t), 1);
+    assert_eq!(without_y.min_vertex_cut(s, t), Some(vec![ids[&'x']]));
+
+    // Edges point away from s, so there is nothing to cut in the other direction
+    assert_eq!(graph.max_vertex_disjoint_paths(t, s), 0);
+    assert_eq!(graph.min_vertex_cut(t, s), Some(vec![]));
+
+    // The edge s - t is a path without inner vertices, so no vertex cut exists, although the
+    // number of paths grows by one
+    graph.insert_edge(s, t, ()).unwrap();
+
+    assert_eq!(graph.max_vertex_disjoint_paths(s, t), 3);
+    assert_eq!(graph.min_vertex_cut(s, t), None);
+  }
+
+  #[test]
+  fn disjoint_paths_use_bi_edges_once() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], ()).unwrap();
+    graph.insert_edge(v[0], v[2], ()).unwrap();
+    graph.insert_bi_edge(v[1], v[2], ()).unwrap();
+    graph.insert_edge(v[1], v[3], ()).unwrap();
+    graph.insert_edge(v[2], v[3], ()).unwrap();
+    graph.insert_edge(v[3], v[3], ()).unwrap();
+
+    for &vertex_disjoint in &[false, true] {
+      let (count, paths) = if vertex_disjoint {
+        graph.vertex_disjoint_paths(v[0], v[3])
+      } else {
+        graph.edge_disjoint_paths(v[0], v[3])
+      };
+
+      assert_eq!(count, 2);
+      assert!(paths.iter().all(|path| path.len() == 2));
+      assert!(are_disjoint_paths(&graph, v[0], v[3], &paths, vertex_disjoint));
+    }
+  }
+}
@@ -0,0 +1,139 @@
+use super::*;
+
+/// Writes `value` with control characters like line breaks escaped, so it stays on one line.
+fn write_escaped(f: &mut fmt::Formatter<'_>, value: &impl fmt::Display) -> fmt::Result {
+  for c in value.to_string().chars() {
+    if c.is_control() {
+      write!(f, "{}", c.escape_default())?;
+    } else {
+      write!(f, "{}", c)?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Formats a [`FiniteGraph`](struct.FiniteGraph.html) with one line per vertex, see
+/// [`FiniteGraph::display_compact`](struct.FiniteGraph.html#method.display_compact).
+#[derive(Copy, Clone)]
+pub struct CompactDisplay<'a, V, E> {
+  graph: &'a FiniteGraph<V, E>,
+  max_vertices: Option<usize>,
+  max_neighbors: Option<usize>
+}
+
+impl<'a, V, E> CompactDisplay<'a, V, E> {
+  /// Only prints the first `max_vertices` vertices, followed by a line with the number of
+  /// omitted vertices.
+  pub fn max_vertices(mut self, max_vertices: usize) -> CompactDisplay<'a, V, E> {
+    self.max_vertices = Some(max_vertices);
+    self
+  }
+
+  /// Only prints the first `max_neighbors` edges of each vertex, followed by the number of
+  /// omitted edges.
+  pub fn max_neighbors(mut self, max_neighbors: usize) -> CompactDisplay<'a, V, E> {
+    self.max_neighbors = Some(max_neighbors);
+    self
+  }
+}
+
+impl<'a, V: fmt::Display, E: fmt::Display> fmt::Display for CompactDisplay<'a, V, E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let vertices = self.graph.all_vertices_ordered().collect::<Vec<_>>();
+    let max_vertices = self.max_vertices.unwrap_or(vertices.len()).min(vertices.len());
+
+    for &(vertex, value) in &vertices[..max_vertices] {
+      let mut edges = self.graph.neighbors_map.get(&vertex).into_iter().flatten()
+        .copied()
+        .collect::<Vec<_>>();
+
+      edges.sort();
+      // Bidirectional self-loops appear twice in the neighbors of their vertex
+      edges.dedup();
+
+      let max_neighbors = self.max_neighbors.unwrap_or(edges.len()).min(edges.len());
+
+      write!(f, "{}: ", vertex.0)?;
+      write_escaped(f, value)?;
+
+      for (i, &(neighbor, edge)) in edges[..max_neighbors].iter().enumerate() {
+        let arrow = if self.graph.is_bi_edge(edge, vertex, neighbor) { "<->" } else { "->" };
+
+        write!(f, "{} {} {} [w=", if i == 0 { "" } else { "," }, arrow, neighbor.0)?;
+        write_escaped(f, &self.graph.edges_map[&edge].0)?;
+        write!(f, "]")?;
+      }
+
+      if max_neighbors < edges.len() {
+        write!(f, "{} ... ({} more)", if max_neighbors == 0 { "" } else { "," }, edges.len() - max_neighbors)?;
+      }
+
+      writeln!(f)?;
+    }
+
+    if max_vertices < vertices.len() {
+      writeln!(f, "... ({} more vertices)", vertices.len() - max_vertices)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns a value that formats the graph with one line per vertex in ascending order of
+  /// ids, like `3: a -> 5 [w=2], <-> 7 [w=4]`. Each line lists the outgoing edges of the vertex
+  /// in ascending order of the neighbor, where bidirectional edges are marked with `<->` and
+  /// appear on the lines of both endpoints. Control characters in vertex and edge values are
+  /// escaped, so every vertex takes exactly one line.
+  ///
+  /// Use [`max_vertices`](struct.CompactDisplay.html#method.max_vertices) and
+  /// [`max_neighbors`](struct.CompactDisplay.html#method.max_neighbors) to truncate the output
+  /// for large graphs.
+  pub fn display_compact(&self) -> CompactDisplay<'_, V, E> {
+    CompactDisplay {
+      graph: self,
+      max_vertices: None,
+      max_neighbors: None
+    }
+  }
+}
+
+/// Formats the graph like [`display_compact`](struct.FiniteGraph.html#method.display_compact)
+/// without truncation.
+impl<V: fmt::Display, E: fmt::Display> fmt::Display for FiniteGraph<V, E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.display_compact().fmt(f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display_compact_golden_output() {
+    let mut graph = FiniteGraph::<&str, u32>::new();
+    let a = graph.insert_vertex("a");
+    let b = graph.insert_vertex("b\nc");
+    let c = graph.insert_vertex("c");
+
+    graph.insert_edge(a, c, 4).unwrap();
+    graph.insert_bi_edge(a, b, 2).unwrap();
+    graph.insert_edge(a, c, 1).unwrap();
+    graph.insert_bi_edge(c, c, 5).unwrap();
+
+    assert_eq!(graph.to_string(), "\
+      1: a <-> 2 [w=2], -> 3 [w=4], -> 3 [w=1]\n\
+      2: b\\nc <-> 1 [w=2]\n\
+      3: c <-> 3 [w=5]\n\
+    ");
+    assert_eq!(graph.display_compact().max_vertices(2).max_neighbors(1).to_string(), "\
+      1: a <-> 2 [w=2], ... (2 more)\n\
+      2: b\\nc <-> 1 [w=2]\n\
+      ... (1 more vertices)\n\
+    ");
+    assert_eq!(graph.display_compact().max_vertices(1).max_neighbors(0).to_string(), "1: a ... (3 more)\n... (2 more vertices)\n");
+    assert_eq!(FiniteGraph::<&str, u32>::new().display_compact().max_vertices(0).to_string(), "");
+  }
+}
@@ -0,0 +1,310 @@
+use std::collections::{HashMap, HashSet};
+use edge::WeightedEdge;
+use vertex_container::AstarContainer;
+use vertex_traverser::VertexTraverser;
+use super::*;
+
+/// Maintains the smallest weight sums from a source vertex to all vertices of a
+/// [`FiniteGraph`](struct.FiniteGraph.html) while edge weights change.
+///
+/// Instead of recomputing all shortest paths after each weight update, only the region of the
+/// shortest path tree that is affected by the update is repaired, in the spirit of the
+/// algorithm by Ramalingam and Reps. Like [`EdgedGraph::dijkstra`](trait.EdgedGraph.html#method.dijkstra),
+/// only non-negative weights are supported.
+///
+/// # Example
+///
+/// ```
+/// use graph_iter::{FiniteGraph, DynamicSssp};
+///
+/// let mut graph = FiniteGraph::<(), u32>::new();
+/// let a = graph.insert_vertex(());
+/// let b = graph.insert_vertex(());
+/// let c = graph.insert_vertex(());
+///
+/// let ab = graph.insert_edge(a, b, 1).unwrap();
+/// graph.insert_edge(a, c, 5).unwrap();
+/// graph.insert_edge(b, c, 1).unwrap();
+///
+/// let mut sssp = DynamicSssp::new(graph, a);
+/// assert_eq!(sssp.path(c), Some(vec![a, b, c]));
+///
+/// sssp.increase_edge(ab, 10);
+/// assert_eq!(sssp.distance(c), Some(&5));
+/// assert_eq!(sssp.path(c), Some(vec![a, c]));
+/// ```
+#[derive(Clone)]
+pub struct DynamicSssp<V, E> {
+  graph: FiniteGraph<V, E>,
+  source: Id,
+  distance_map: HashMap<Id, E>,
+  parent_map: HashMap<Id, (Id, Id)>
+}
+
+impl<V, E: WeightedEdge> DynamicSssp<V, E> {
+  /// Takes ownership of `graph` and computes the smallest weight sums from `source`
+  /// to all vertices.
+  pub fn new(graph: FiniteGraph<V, E>, source: Id) -> DynamicSssp<V, E> {
+    let mut result = DynamicSssp {
+      graph,
+      source,
+      distance_map: HashMap::new(),
+      parent_map: HashMap::new()
+    };
+
+    let mut queue = AstarContainer::new();
+
+    result.distance_map.insert(source, E::default());
+    queue.push(source, E::default());
+    result.propagate(queue);
+
+    result
+  }
+
+  /// Returns the underlying graph.
+  pub fn graph(&self) -> &FiniteGraph<V, E> {
+    &self.graph
+  }
+
+  /// Returns the underlying graph with the updated edge weights.
+  pub fn into_graph(self) -> FiniteGraph<V, E> {
+    self.graph
+  }
+
+  /// Returns the source vertex.
+  pub fn source(&self) -> Id {
+    self.source
+  }
+
+  /// Returns the smallest weight sum of a path from the source vertex to `vertex`,
+  /// or `None` if `vertex` can't be reached.
+  pub fn distance(&self, vertex: Id) -> Option<&E> {
+    self.distance_map.get(&vertex)
+  }
+
+  /// Returns a smallest path from the source vertex to `vertex`, or `None` if `vertex`
+  /// can't be reached.
+  pub fn path(&self, vertex: Id) -> Option<Vec<Id>> {
+    if !self.distance_map.contains_key(&vertex) {
+      return None;
+    }
+
+    let mut path = vec![vertex];
+
+    while let Some(&(parent, _)) = self.parent_map.get(path.last().unwrap()) {
+      path.push(parent);
+    }
+
+    path.reverse();
+    Some(path)
+  }
+
+  /// Sets the weight of `edge` to `new_weight`, which must not be smaller than the
+  /// current weight, and repairs all affected smallest weight sums.
+  pub fn increase_edge(&mut self, edge: Id, new_weight: E) {
+    debug_assert!(self.graph.get_edge(edge).is_none_or(|weight| weight <= &new_weight));
+    self.update_edges(Some((edge, new_weight)));
+  }
+
+  /// Sets the weight of `edge` to `new_weight`, which must not be greater than the
+  /// current weight, and repairs all affected smallest weight sums.
+  pub fn decrease_edge(&mut self, edge: Id, new_weight: E) {
+    debug_assert!(self.graph.get_edge(edge).is_none_or(|weight| weight >= &new_weight));
+    self.update_edges(Some((edge, new_weight)));
+  }
+
+  /// Sets the weights of multiple edges at once, each of which may increase or decrease,
+  /// and repairs all affected smallest weight sums in a single pass. Unknown edges are ignored.
+  pub fn update_edges<I: IntoIterator<Item = (Id, E)>>(&mut self, updates: I) {
+    let mut affected_roots = vec![];
+    let mut decreased_arcs = vec![];
+
+    for (edge, new_weight) in updates {
+      let (old_weight, from, to) = match self.graph.edges_map.get_mut(&edge) {
+        Some((weight, from, to)) => (std::mem::replace(weight, new_weight.clone()), *from, *to),
+        None => continue
+      };
+
+      let mut arcs = vec![(from, to)];
+
+      if self.graph.is_bi_edge(edge, from, to) {
+        arcs.push((to, from));
+      }
+
+      for (from, to) in arcs {
+        if new_weight > old_weight && self.parent_map.get(&to) == Some(&(from, edge)) {
+          affected_roots.push(to);
+        } else if new_weight < old_weight {
+          decreased_arcs.push((from, to, edge));
+        }
+      }
+    }
+
+    // Invalidate all vertices whose smallest path uses an increased edge
+    let mut children_map = HashMap::<Id, Vec<Id>>::new();
+
+    if !affected_roots.is_empty() {
+      for (&vertex, &(parent, _)) in &self.parent_map {
+        children_map.entry(parent).or_default().push(vertex);
+      }
+    }
+
+    let mut affected = HashSet::new();
+
+    while let Some(vertex) = affected_roots.pop() {
+      if affected.insert(vertex) {
+        self.distance_map.remove(&vertex);
+        self.parent_map.remove(&vertex);
+        affected_roots.extend(children_map.get(&vertex).into_iter().flatten().copied());
+      }
+    }
+
+    // Seed affected vertices with their best unaffected predecessors and relax decreased edges
+    let mut queue = AstarContainer::new();
+    let seeds = affected.iter()
+      .flat_map(|&vertex| {
+        self.graph.reverse_neighbors_map.get(&vertex).into_iter().flatten()
+        .map(move |&(predecessor, edge)| (predecessor, vertex, edge))
+      })
+      .chain(decreased_arcs)
+      .collect::<Vec<_>>();
+
+    for (from, to, edge) in seeds {
+      self.relax(&mut queue, from, to, edge);
+    }
+
+    self.propagate(queue);
+  }
+
+  /// Updates the smallest weight sum of `to` if traveling along `edge` from `from` is shorter.
+  fn relax(&mut self, queue: &mut AstarContainer<Id, E>, from: Id, to: Id, edge: Id) {
+    let candidate = match self.distance_map.get(&from) {
+      Some(distance) => distance.clone() + self.graph.edges_map[&edge].0.clone(),
+      None => return
+    };
+
+    if self.distance_map.get(&to).is_none_or(|distance| &candidate < distance) {
+      self.distance_map.insert(to, candidate.clone());
+      self.parent_map.insert(to, (from, edge));
+      queue.push(to, candidate);
+    }
+  }
+
+  /// Runs Dijkstra's algorithm starting with the given queue of tentative smallest weight sums.
+  fn propagate(&mut self, mut queue: AstarContainer<Id, E>) {
+    while let Some((vertex, distance)) = queue.pop() {
+      if self.distance_map.get(&vertex) != Some(&distance) {
+        continue;
+      }
+
+      let arcs = self.graph.neighbors_map.get(&vertex).cloned().unwrap_or_default();
+
+      for (neighbor, edge) in arcs {
+        self.relax(&mut queue, vertex, neighbor, edge);
+      }
+    }
+  }
+
+  /// Returns `true` if the maintained smallest weight sums equal the ones computed by a fresh
+  /// Dijkstra traversal, and if all maintained paths are valid and have the maintained weight sums.
+  pub fn is_consistent(&self) -> bool {
+    let mut traverser = self.graph.dijkstra(&self.source);
+    let mut count = 0;
+
+    while let Some(vertex) = traverser.next() {
+      count += 1;
+
+      if self.distance_map.get(&vertex) != traverser.cost_to(&vertex) {
+        return false;
+      }
+    }
+
+    count == self.distance_map.len()
+    && self.parent_map.iter().all(|(vertex, &(parent, edge))| {
+      self.graph.neighbors_map.get(&parent).is_some_and(|arcs| arcs.contains(&(*vertex, edge)))
+      && self.distance_map.get(&parent).map(|distance| {
+        distance.clone() + self.graph.edges_map[&edge].0.clone()
+      }).as_ref() == self.distance_map.get(vertex)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn grid_graph(size: usize) -> (FiniteGraph<(), u32>, Vec<Id>, Vec<Id>) {
+    let mut graph = FiniteGraph::new();
+    let vertices = (0..size * size).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+    let mut edges = vec![];
+
+    for i in 0..size {
+      for j in 0..size {
+        let vertex = vertices[i * size + j];
+        let weight = ((i * 7 + j * 3) % 5 + 1) as u32;
+
+        if j + 1 < size {
+          edges.push(graph.insert_bi_edge(vertex, vertices[i * size + j + 1], weight).unwrap());
+        }
+
+        if i + 1 < size {
+          edges.push(graph.insert_edge(vertex, vertices[(i + 1) * size + j], weight + 1).unwrap());
+        }
+      }
+    }
+
+    (graph, vertices, edges)
+  }
+
+  #[test]
+  fn dynamic_sssp_stays_consistent_under_updates() {
+    let (graph, vertices, edges) = grid_graph(6);
+    let mut sssp = DynamicSssp::new(graph, vertices[0]);
+
+    assert!(sssp.is_consistent());
+    assert_eq!(sssp.distance(vertices[0]), Some(&0));
+
+    for (i, &edge) in edges.iter().enumerate() {
+      let weight = *sssp.graph().get_edge(edge).unwrap();
+
+      if i % 3 == 0 {
+        sssp.increase_edge(edge, weight + (i % 7) as u32 + 1);
+      } else if weight > 1 {
+        sssp.decrease_edge(edge, weight - 1);
+      }
+
+      assert!(sssp.is_consistent());
+    }
+
+    let updates = edges.iter()
+      .enumerate()
+      .map(|(i, &edge)| (edge, (i % 4) as u32))
+      .collect::<Vec<_>>();
+
+    sssp.update_edges(updates);
+    assert!(sssp.is_consistent());
+
+    let path = sssp.path(vertices[35]).unwrap();
+
+    assert_eq!(path.first(), Some(&vertices[0]));
+    assert_eq!(sssp.graph().path_cost(&path).as_ref(), sssp.distance(vertices[35]));
+  }
+
+  #[test]
+  fn dynamic_sssp_handles_unreachable_vertices() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+    let e = graph.insert_edge(b, c, 1).unwrap();
+
+    let mut sssp = DynamicSssp::new(graph, a);
+
+    sssp.decrease_edge(e, 0);
+
+    assert_eq!(sssp.distance(c), None);
+    assert_eq!(sssp.path(c), None);
+    assert_eq!(sssp.path(a), Some(vec![a]));
+    assert!(sssp.is_consistent());
+  }
+}
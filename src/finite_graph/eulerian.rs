@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use super::*;
+
+/// The result of [`FiniteGraph::eulerian_type`](struct.FiniteGraph.html#method.eulerian_type).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EulerianType {
+  /// The graph has no path that travels along every edge exactly once.
+  NotEulerian,
+  /// The graph has a cycle that travels along every edge exactly once.
+  EulerianCircuit,
+  /// The graph has a path from the first to the second vertex that travels along every edge
+  /// exactly once, but no such cycle.
+  EulerianPath(Id, Id)
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Determines whether the graph contains an Eulerian circuit or an Eulerian path.
+  ///
+  /// A graph has an Eulerian circuit, if every vertex has the same in-degree as out-degree,
+  /// and an Eulerian path, if there is exactly one vertex with one more outgoing edge
+  /// than incoming edges (the start vertex), and exactly one vertex with one more incoming
+  /// edge than outgoing edges (the end vertex). In both cases, all vertices with edges need
+  /// to be connected. Graphs without edges have an Eulerian circuit.
+  ///
+  /// Bidirectional edges count as an outgoing and an incoming edge of both of their vertices.
+  pub fn eulerian_type(&self) -> EulerianType {
+    let mut start = None;
+    let mut end = None;
+
+    for &vertex in self.vertices_map.keys() {
+      let (out_degree, in_degree) = (self.out_degree(vertex), self.in_degree(vertex));
+
+      if out_degree == in_degree + 1 && start.is_none() {
+        start = Some(vertex);
+      } else if in_degree == out_degree + 1 && end.is_none() {
+        end = Some(vertex);
+      } else if in_degree != out_degree {
+        return EulerianType::NotEulerian;
+      }
+    }
+
+    let first = self.vertices_map.keys().copied().find(|&vertex| self.out_degree(vertex) > 0);
+    let mut visited = first.into_iter().collect::<HashSet<_>>();
+    let mut stack = first.into_iter().collect::<Vec<_>>();
+
+    while let Some(vertex) = stack.pop() {
+      for (neighbor, _) in self.undirected_neighbors(vertex) {
+        if visited.insert(neighbor) {
+          stack.push(neighbor);
+        }
+      }
+    }
+
+    let connected = self.vertices_map.keys()
+      .all(|&vertex| visited.contains(&vertex) || self.out_degree(vertex) + self.in_degree(vertex) == 0);
+
+    match (connected, start, end) {
+      (false, _, _) => EulerianType::NotEulerian,
+      (true, None, None) => EulerianType::EulerianCircuit,
+      (true, Some(start), Some(end)) => EulerianType::EulerianPath(start, end),
+      _ => EulerianType::NotEulerian
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn classify_eulerian_graphs() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+
+    assert_eq!(graph.eulerian_type(), EulerianType::EulerianCircuit);
+
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+    let d = graph.insert_vertex(());
+
+    graph.insert_edge(a, b, ()).unwrap();
+    graph.insert_edge(b, c, ()).unwrap();
+    graph.insert_edge(c, a, ()).unwrap();
+
+    assert_eq!(graph.eulerian_type(), EulerianType::EulerianCircuit);
+
+    graph.insert_edge(c, d, ()).unwrap();
+
+    assert_eq!(graph.eulerian_type(), EulerianType::EulerianPath(c, d));
+
+    let e = graph.insert_edge(a, d, ()).unwrap();
+
+    assert_eq!(graph.eulerian_type(), EulerianType::NotEulerian);
+
+    graph.remove_edge(e);
+    graph.insert_edge(d, c, ()).unwrap();
+
+    let x = graph.insert_vertex(());
+    let y = graph.insert_vertex(());
+
+    assert_eq!(graph.eulerian_type(), EulerianType::EulerianCircuit);
+
+    graph.insert_bi_edge(x, y, ()).unwrap();
+
+    assert_eq!(graph.eulerian_type(), EulerianType::NotEulerian);
+  }
+}
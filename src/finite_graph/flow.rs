@@ -0,0 +1,768 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Sub;
+use edge::WeightedEdge;
+use vertex_container::AstarContainer;
+use super::*;
+
+/// The residual capacities of a flow network, keyed by `(from, to)` vertex pairs.
+pub(crate) type ResidualMap<E> = HashMap<(Id, Id), E>;
+
+/// A directed arc of a min-cost flow network. Bidirectional edges yield two arcs.
+struct FlowArc<E> {
+  edge: Id,
+  from: Id,
+  to: Id,
+  capacity: E,
+  cost: E,
+  flow: E
+}
+
+impl<V, E: WeightedEdge + Sub<Output = E>> FiniteGraph<V, E> {
+  /// Builds the residual graph of the zero flow, where each edge weight is interpreted as
+  /// capacity. Parallel edges add up their capacities and bidirectional edges have their
+  /// capacity in both directions.
+  fn initial_residual_map(&self) -> (ResidualMap<E>, HashMap<Id, Vec<Id>>) {
+    let mut residual_map = ResidualMap::<E>::new();
+    let mut adjacency = HashMap::<Id, Vec<Id>>::new();
+
+    for (&vertex, neighbors) in &self.neighbors_map {
+      for &(neighbor, edge) in neighbors {
+        let capacity = self.edges_map[&edge].0.clone();
+        let residual = residual_map.entry((vertex, neighbor)).or_default();
+
+        *residual = residual.clone() + capacity;
+        residual_map.entry((neighbor, vertex)).or_default();
+
+        adjacency.entry(vertex).or_default().push(neighbor);
+        adjacency.entry(neighbor).or_default().push(vertex);
+      }
+    }
+
+    for neighbors in adjacency.values_mut() {
+      neighbors.sort();
+      neighbors.dedup();
+    }
+
+    (residual_map, adjacency)
+  }
+
+  /// Finds a path from `source` to `sink` with positive residual capacities using a
+  /// depth-first search.
+  fn augmenting_path_dfs(
+    source: Id,
+    sink: Id,
+    residual_map: &ResidualMap<E>,
+    adjacency: &HashMap<Id, Vec<Id>>
+  ) -> Option<Vec<Id>> {
+    let mut predecessor_map = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![source];
+
+    visited.insert(source);
+
+    while let Some(vertex) = stack.pop() {
+      if vertex == sink {
+        let mut path = vec![sink];
+
+        while let Some(&predecessor) = predecessor_map.get(path.last().unwrap()) {
+          path.push(predecessor);
+        }
+
+        path.reverse();
+        return Some(path);
+      }
+
+      for &neighbor in adjacency.get(&vertex).into_iter().flatten() {
+        if residual_map[&(vertex, neighbor)] > E::default() && visited.insert(neighbor) {
+          predecessor_map.insert(neighbor, vertex);
+          stack.push(neighbor);
+        }
+      }
+    }
+
+    None
+  }
+
+  /// Computes a maximum flow from `source` to `sink` and returns its value together with
+  /// the final residual graph.
+  pub(crate) fn max_flow_residual(&self, source: Id, sink: Id) -> (E, ResidualMap<E>) {
+    let (mut residual_map, adjacency) = self.initial_residual_map();
+    let mut flow = E::default();
+
+    if source == sink {
+      return (flow, residual_map);
+    }
+
+    while let Some(path) = FiniteGraph::<V, E>::augmenting_path_dfs(source, sink, &residual_map, &adjacency) {
+      let bottleneck = path.windows(2)
+        .map(|pair| residual_map[&(pair[0], pair[1])].clone())
+        .min()
+        .unwrap();
+
+      for pair in path.windows(2) {
+        let forward = residual_map.get_mut(&(pair[0], pair[1])).unwrap();
+        *forward = forward.clone() - bottleneck.clone();
+
+        let backward = residual_map.get_mut(&(pair[1], pair[0])).unwrap();
+        *backward = backward.clone() + bottleneck.clone();
+      }
+
+      flow = flow + bottleneck;
+    }
+
+    (flow, residual_map)
+  }
+
+  /// Computes the value of a maximum flow from `source` to `sink` using the
+  /// [Ford-Fulkerson method](https://en.wikipedia.org/wiki/Ford%E2%80%93Fulkerson_algorithm),
+  /// where edge weights are interpreted as capacities.
+  ///
+  /// Augmenting paths are found with a depth-first search in the residual graph, so this
+  /// takes `O(E * f)` time, where `f` is the value of the maximum flow, and may not terminate
+  /// for irrational capacities.
+  pub fn ford_fulkerson(&self, source: Id, sink: Id) -> E {
+    self.max_flow_residual(source, sink).0
+  }
+
+  /// Computes a minimum cut separating `source` from `sink`, i.e. a set of edges with the
+  /// smallest capacity sum whose removal disconnects `sink` from `source`. Returns the edges
+  /// sorted by id together with their capacity sum.
+  ///
+  /// By the max-flow min-cut theorem, the capacity of the cut equals the value of the
+  /// [maximum flow](#method.ford_fulkerson). The cut consists of all edges that go from a vertex
+  /// reachable from `source` in the residual graph of a maximum flow to a vertex that isn't.
+  pub fn minimum_st_cut(&self, source: Id, sink: Id) -> (Vec<Id>, E) {
+    let (_, residual_map) = self.max_flow_residual(source, sink);
+    let mut reachable = HashSet::new();
+    let mut stack = vec![source];
+
+    reachable.insert(source);
+
+    while let Some(vertex) = stack.pop() {
+      for (neighbor, _) in self.undirected_neighbors(vertex) {
+        if residual_map[&(vertex, neighbor)] > E::default() && reachable.insert(neighbor) {
+          stack.push(neighbor);
+        }
+      }
+    }
+
+    let mut cut = self.edges_map.iter()
+      .filter(|&(edge, &(_, from, to))| {
+        (reachable.contains(&from) && !reachable.contains(&to))
+        || (reachable.contains(&to) && !reachable.contains(&from)
+          && self.is_bi_edge(*edge, from, to))
+      })
+      .map(|(&edge, _)| edge)
+      .collect::<Vec<_>>();
+
+    cut.sort();
+
+    let capacity = cut.iter()
+      .fold(E::default(), |sum, edge| sum + self.edges_map[edge].0.clone());
+
+    (cut, capacity)
+  }
+
+  /// Finds a cheapest path from `source` to `sink` in the residual network of `arcs` using
+  /// Dijkstra's algorithm on costs reduced by `potentials`. Each path step is the index of the
+  /// arc together with `true` if the arc is used forwards. Afterwards, the potentials of all
+  /// reached vertices are increased by their distance.
+  fn cheapest_residual_path(
+    source: Id,
+    sink: Id,
+    arcs: &[FlowArc<E>],
+    incident_arcs: &HashMap<Id, Vec<usize>>,
+    potentials: &mut HashMap<Id, E>
+  ) -> Option<Vec<(usize, bool)>> {
+    let mut distance_map = HashMap::new();
+    let mut predecessor_map = HashMap::<Id, (usize, bool)>::new();
+    let mut done = HashSet::new();
+    let mut queue = AstarContainer::new();
+
+    distance_map.insert(source, E::default());
+    queue.push(source, E::default());
+
+    while let Some((vertex, distance)) = queue.pop() {
+      if !done.insert(vertex) {
+        continue;
+      }
+
+      for &index in incident_arcs.get(&vertex).into_iter().flatten() {
+        let arc = &arcs[index];
+        let step = if arc.from == vertex && arc.flow < arc.capacity {
+          // Reduced costs of residual arcs are non-negative, so this can't underflow
+          let reduced = arc.cost.clone() + potentials[&arc.from].clone() - potentials[&arc.to].clone();
+          Some((arc.to, reduced, true))
+        } else if arc.to == vertex && arc.flow > E::default() {
+          let reduced = potentials[&arc.to].clone() - (arc.cost.clone() + potentials[&arc.from].clone());
+          Some((arc.from, reduced, false))
+        } else {
+          None
+        };
+
+        if let Some((neighbor, reduced, forward)) = step {
+          let new_distance = distance.clone() + reduced;
+
+          if !done.contains(&neighbor)
+          && distance_map.get(&neighbor).is_none_or(|current| &new_distance < current) {
+            distance_map.insert(neighbor, new_distance.clone());
+            predecessor_map.insert(neighbor, (index, forward));
+            queue.push(neighbor, new_distance);
+          }
+        }
+      }
+    }
+
+    if !done.contains(&sink) {
+      return None;
+    }
+
+    for (vertex, distance) in distance_map {
+      let potential = potentials.get_mut(&vertex).unwrap();
+      *potential = potential.clone() + distance;
+    }
+
+    let mut path = vec![];
+    let mut vertex = sink;
+
+    while let Some(&(index, forward)) = predecessor_map.get(&vertex) {
+      path.push((index, forward));
+      vertex = if forward { arcs[index].from } else { arcs[index].to };
+    }
+
+    path.reverse();
+    Some(path)
+  }
+
+  /// Computes a flow of value `supply` from `source` to `sink` with minimum total cost, where
+  /// edge weights are interpreted as capacities and `cost_fn` maps edges to the cost per unit of
+  /// flow. Returns the flow of every edge, or `None` if `supply` exceeds the
+  /// [maximum flow](#method.ford_fulkerson) or some cost is negative. The maximum flow from a
+  /// vertex to itself is zero, so only a zero `supply` can be sent if `source` equals `sink`.
+  ///
+  /// This uses successive shortest augmenting paths, which are found by Dijkstra's algorithm
+  /// on costs reduced by vertex potentials, so the residual costs never become negative.
+  /// Parallel edges are kept apart, since they may have different costs. Bidirectional edges
+  /// can carry flow in either direction, but never in both at once.
+  pub fn min_cost_flow(
+    &self,
+    source: Id,
+    sink: Id,
+    supply: E,
+    cost_fn: impl Fn(Id) -> E
+  ) -> Option<HashMap<Id, E>> {
+    if source == sink && supply > E::default() {
+      return None;
+    }
+
+    let mut arcs = vec![];
+
+    for (&edge, &(ref capacity, from, to)) in &self.edges_map {
+      let cost = cost_fn(edge);
+
+      if cost < E::default() {
+        return None;
+      }
+
+      let mut directions = vec![(from, to)];
+
+      if from != to && self.is_bi_edge(edge, from, to) {
+        directions.push((to, from));
+      }
+
+      for (from, to) in directions {
+        arcs.push(FlowArc {
+          edge,
+          from,
+          to,
+          capacity: capacity.clone(),
+          cost: cost.clone(),
+          flow: E::default()
+        });
+      }
+    }
+
+    arcs.sort_by_key(|arc| (arc.edge, arc.from));
+
+    let mut incident_arcs = HashMap::<Id, Vec<usize>>::new();
+
+    for (index, arc) in arcs.iter().enumerate() {
+      incident_arcs.entry(arc.from).or_default().push(index);
+
+      if arc.to != arc.from {
+        incident_arcs.entry(arc.to).or_default().push(index);
+      }
+    }
+
+    let mut potentials = self.vertices_map.keys()
+      .map(|&vertex| (vertex, E::default()))
+      .collect::<HashMap<_, _>>();
+    let mut remaining = supply;
+
+    while remaining > E::default() {
+      let path = FiniteGraph::<V, E>::cheapest_residual_path(
+        source, sink, &arcs, &incident_arcs, &mut potentials
+      )?;
+
+      let bottleneck = path.iter()
+        .map(|&(index, forward)| {
+          let arc = &arcs[index];
+          if forward { arc.capacity.clone() - arc.flow.clone() } else { arc.flow.clone() }
+        })
+        .fold(remaining.clone(), |min, residual| if residual < min { residual } else { min });
+
+      for (index, forward) in path {
+        let arc = &mut arcs[index];
+
+        arc.flow = if forward {
+          arc.flow.clone() + bottleneck.clone()
+        } else {
+          arc.flow.clone() - bottleneck.clone()
+        };
+      }
+
+      remaining = remaining - bottleneck;
+    }
+
+    let mut flow_map = self.edges_map.keys()
+      .map(|&edge| (edge, E::default()))
+      .collect::<HashMap<_, _>>();
+
+    for pair in arcs.chunk_by(|arc, other| arc.edge == other.edge) {
+      // Opposite flows on a bidirectional edge cancel out without increasing the cost
+      let flows = pair.iter().map(|arc| arc.flow.clone()).collect::<Vec<_>>();
+      let flow = match &flows[..] {
+        [flow] => flow.clone(),
+        [flow, other] if flow > other => flow.clone() - other.clone(),
+        [flow, other] => other.clone() - flow.clone(),
+        _ => unreachable!()
+      };
+
+      flow_map.insert(pair[0].edge, flow);
+    }
+
+    Some(flow_map)
+  }
+
+  /// Searches depth-first along edges with remaining flow from `start` for `stop` or a vertex
+  /// on the current walk, backtracking from dead ends, and returns the visited vertices and the
+  /// `(from, to, edge)` hops of the walk. Bidirectional edges without an orientation can be
+  /// followed in either direction, but not twice. If neither is found, only `start` is returned.
+  fn trace_flow(
+    &self,
+    start: Id,
+    stop: Option<Id>,
+    remaining: &HashMap<Id, E>,
+    orientation: &HashMap<Id, (Id, Id)>
+  ) -> (Vec<Id>, Vec<(Id, Id, Id)>) {
+    let candidates = |vertex: Id| {
+      let mut candidates = self.undirected_neighbors(vertex)
+        .filter(|&(neighbor, edge)| {
+          remaining.contains_key(&edge)
+          && orientation.get(&edge).is_none_or(|&direction| direction == (vertex, neighbor))
+        })
+        .collect::<Vec<_>>();
+
+      candidates.sort_by_key(|&(neighbor, edge)| (edge, neighbor));
+      candidates.dedup();
+      candidates
+    };
+
+    let mut stack = vec![(start, candidates(start), 0)];
+    let mut hops = vec![];
+    let mut on_stack = HashSet::new();
+    let mut used_edges = HashSet::new();
+    let mut dead_ends = HashSet::new();
+
+    on_stack.insert(start);
+
+    while let Some((vertex, candidates_of_vertex, index)) = stack.last_mut() {
+      let vertex = *vertex;
+      let next = candidates_of_vertex.get(*index).copied();
+
+      *index += 1;
+
+      match next {
+        Some((neighbor, edge)) => {
+          if used_edges.contains(&edge) || dead_ends.contains(&neighbor) {
+            continue;
+          }
+
+          hops.push((vertex, neighbor, edge));
+
+          if Some(neighbor) == stop || on_stack.contains(&neighbor) {
+            let mut vertices = stack.iter().map(|&(vertex, _, _)| vertex).collect::<Vec<_>>();
+
+            vertices.push(neighbor);
+            return (vertices, hops);
+          }
+
+          used_edges.insert(edge);
+          on_stack.insert(neighbor);
+          stack.push((neighbor, candidates(neighbor), 0));
+        },
+        None => {
+          stack.pop();
+          on_stack.remove(&vertex);
+          dead_ends.insert(vertex);
+
+          if let Some((_, _, edge)) = hops.pop() {
+            used_edges.remove(&edge);
+          }
+        }
+      }
+    }
+
+    (vec![start], vec![])
+  }
+
+  /// Fixes the orientation of bidirectional edges with remaining flow wherever flow
+  /// conservation leaves only one choice, i.e. at vertices other than `source` and `sink`
+  /// with a single such edge whose orientation is unknown.
+  fn orient_by_conservation(
+    &self,
+    source: Id,
+    sink: Id,
+    remaining: &HashMap<Id, E>,
+    orientation: &mut HashMap<Id, (Id, Id)>
+  ) {
+    let mut queue = self.all_vertices_ordered()
+      .map(|(vertex, _)| vertex)
+      .filter(|&vertex| vertex != source && vertex != sink)
+      .collect::<VecDeque<_>>();
+
+    while let Some(vertex) = queue.pop_front() {
+      let mut incoming = E::default();
+      let mut outgoing = E::default();
+      let mut unknown = vec![];
+      let mut seen = HashSet::new();
+
+      for (neighbor, edge) in self.undirected_neighbors(vertex) {
+        let value = match remaining.get(&edge) {
+          Some(value) if neighbor != vertex && seen.insert(edge) => value.clone(),
+          _ => continue
+        };
+
+        match orientation.get(&edge) {
+          Some(&(from, _)) if from == vertex => outgoing = outgoing + value,
+          Some(_) => incoming = incoming + value,
+          None => unknown.push((neighbor, edge))
+        }
+      }
+
+      if let [(neighbor, edge)] = unknown[..] {
+        let direction = match incoming.cmp(&outgoing) {
+          Ordering::Greater => (vertex, neighbor),
+          Ordering::Less => (neighbor, vertex),
+          Ordering::Equal => continue
+        };
+
+        orientation.insert(edge, direction);
+
+        if neighbor != source && neighbor != sink {
+          queue.push_back(neighbor);
+        }
+      }
+    }
+  }
+
+  /// Subtracts the bottleneck of `hops` from the remaining flow, fixes the orientation of the
+  /// used edges, and returns the bottleneck.
+  fn subtract_flow(
+    hops: &[(Id, Id, Id)],
+    remaining: &mut HashMap<Id, E>,
+    orientation: &mut HashMap<Id, (Id, Id)>
+  ) -> E {
+    let bottleneck = hops.iter()
+      .map(|(_, _, edge)| &remaining[edge])
+      .min()
+      .unwrap()
+      .clone();
+
+    for &(from, to, edge) in hops {
+      let value = remaining.remove(&edge).unwrap() - bottleneck.clone();
+
+      if value > E::default() {
+        remaining.insert(edge, value);
+      }
+
+      orientation.insert(edge, (from, to));
+    }
+
+    bottleneck
+  }
+
+  /// Decomposes `flow`, which maps edges to the amount of flow they carry, e.g. as computed
+  /// by [`min_cost_flow`](#method.min_cost_flow), into paths from `source` to `sink` and cycles,
+  /// each with the amount of flow it carries, such that the flows of all paths and cycles
+  /// through an edge add up to its flow. Paths are listed first as vertex sequences, followed
+  /// by cycles, which start and end at the same vertex.
+  ///
+  /// Paths are traced by depth-first searches from `source` that prefer the edges with the
+  /// smallest ids carrying remaining flow and backtrack from dead ends, subtracting the
+  /// bottleneck of each discovered path or cycle. Since `flow` doesn't record the direction of
+  /// flow on bidirectional edges, it is derived from flow conservation where possible, and
+  /// otherwise fixed by the first path or cycle using the edge. Flow that violates flow
+  /// conservation or runs against the direction of an edge is ignored.
+  pub fn flow_decomposition(&self, source: Id, sink: Id, flow: &HashMap<Id, E>) -> Vec<(Vec<Id>, E)> {
+    let mut remaining = flow.iter()
+      .filter(|&(edge, value)| self.edges_map.contains_key(edge) && value > &E::default())
+      .map(|(&edge, value)| (edge, value.clone()))
+      .collect::<HashMap<_, _>>();
+    let mut orientation = self.edges_map.iter()
+      .filter(|&(&edge, &(_, from, to))| !self.is_bi_edge(edge, from, to))
+      .map(|(&edge, &(_, from, to))| (edge, (from, to)))
+      .collect::<HashMap<_, _>>();
+    let mut paths = vec![];
+    let mut cycles = vec![];
+
+    // Returns the index of the first visit of the last vertex if the walk ends in a cycle
+    let cycle_start = |vertices: &[Id]| vertices[..vertices.len() - 1].iter().position(|&v| Some(&v) == vertices.last());
+
+    self.orient_by_conservation(source, sink, &remaining, &mut orientation);
+
+    while source != sink && self.contains_vertex(source) {
+      let (vertices, hops) = self.trace_flow(source, Some(sink), &remaining, &orientation);
+
+      if vertices.last() == Some(&sink) {
+        let value = FiniteGraph::<V, E>::subtract_flow(&hops, &mut remaining, &mut orientation);
+
+        paths.push((vertices, value));
+      } else if let Some(index) = cycle_start(&vertices) {
+        let value = FiniteGraph::<V, E>::subtract_flow(&hops[index..], &mut remaining, &mut orientation);
+
+        cycles.push((vertices[index..].to_vec(), value));
+      } else {
+        break;
+      }
+
+      self.orient_by_conservation(source, sink, &remaining, &mut orientation);
+    }
+
+    // The remaining flow of a valid flow consists of cycles
+    while let Some(edge) = remaining.keys().min().copied() {
+      let start = orientation.get(&edge).map_or(self.edges_map[&edge].1, |&(from, _)| from);
+      let (vertices, hops) = self.trace_flow(start, None, &remaining, &orientation);
+
+      match cycle_start(&vertices) {
+        Some(index) => {
+          let value = FiniteGraph::<V, E>::subtract_flow(&hops[index..], &mut remaining, &mut orientation);
+
+          cycles.push((vertices[index..].to_vec(), value));
+        },
+        None => {
+          remaining.remove(&edge);
+        }
+      }
+    }
+
+    paths.extend(cycles);
+    paths
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// The flow network from CLRS, Figure 26.1, with a maximum flow of 23.
+  fn flow_network() -> (FiniteGraph<(), u32>, Vec<Id>) {
+    let mut graph = FiniteGraph::new();
+    let v = (0..6).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for &(from, to, capacity) in &[
+      (0, 1, 16), (0, 2, 13), (1, 3, 12), (2, 1, 4),
+      (2, 4, 14), (3, 2, 9), (3, 5, 20), (4, 3, 7), (4, 5, 4)
+    ] {
+      graph.insert_edge(v[from], v[to], capacity).unwrap();
+    }
+
+    (graph, v)
+  }
+
+  #[test]
+  fn ford_fulkerson_on_classic_network() {
+    let (mut graph, v) = flow_network();
+
+    assert_eq!(graph.ford_fulkerson(v[0], v[5]), 23);
+    assert_eq!(graph.ford_fulkerson(v[5], v[0]), 0);
+    assert_eq!(graph.ford_fulkerson(v[0], v[0]), 0);
+
+    graph.insert_bi_edge(v[0], v[5], 2).unwrap();
+
+    assert_eq!(graph.ford_fulkerson(v[0], v[5]), 25);
+    assert_eq!(graph.ford_fulkerson(v[5], v[0]), 2);
+  }
+
+  #[test]
+  fn minimum_st_cut_equals_maximum_flow() {
+    let (mut graph, v) = flow_network();
+    let (cut, capacity) = graph.minimum_st_cut(v[0], v[5]);
+
+    assert_eq!(capacity, graph.ford_fulkerson(v[0], v[5]));
+    assert_eq!(
+      cut.iter().map(|&edge| graph.get_edge(edge).copied().unwrap()).collect::<Vec<_>>(),
+      vec![12, 7, 4]
+    );
+
+    let edge = graph.insert_bi_edge(v[5], v[0], 2).unwrap();
+    let (cut, capacity) = graph.minimum_st_cut(v[0], v[5]);
+
+    assert_eq!(capacity, 25);
+    assert!(cut.contains(&edge));
+    assert_eq!(graph.minimum_st_cut(v[5], v[0]), (vec![edge], 2));
+  }
+
+  #[test]
+  fn min_cost_flow_prefers_cheap_paths() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+    let mut costs = HashMap::new();
+    let edges = [(0, 1, 2, 1), (0, 2, 1, 2), (1, 2, 1, 1), (1, 3, 1, 3), (2, 3, 2, 1)].iter()
+      .map(|&(from, to, capacity, cost)| {
+        let edge = graph.insert_edge(v[from], v[to], capacity).unwrap();
+        costs.insert(edge, cost);
+        edge
+      })
+      .collect::<Vec<_>>();
+    let cost_fn = |edge| costs[&edge];
+    let flows = |supply| graph.min_cost_flow(v[0], v[3], supply, cost_fn)
+      .map(|flow_map| edges.iter().map(|edge| flow_map[edge]).collect::<Vec<_>>());
+
+    assert_eq!(flows(0), Some(vec![0; 5]));
+    assert_eq!(flows(2), Some(vec![1, 1, 1, 0, 2]));
+    assert_eq!(flows(3), Some(vec![2, 1, 1, 1, 2]));
+    assert_eq!(flows(4), None);
+    assert_eq!(graph.min_cost_flow(v[3], v[0], 1, cost_fn), None);
+    assert_eq!(graph.min_cost_flow(v[1], v[1], 1, cost_fn), None);
+    assert_eq!(
+      graph.min_cost_flow(v[1], v[1], 0, cost_fn).map(|flow_map| flow_map.values().sum::<u32>()),
+      Some(0)
+    );
+  }
+
+  #[test]
+  fn min_cost_flow_uses_bi_edges_in_one_direction() {
+    let mut graph = FiniteGraph::<(), i32>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+    let bi_edge = graph.insert_bi_edge(a, b, 5).unwrap();
+    let edge = graph.insert_edge(c, a, 5).unwrap();
+
+    let flow_map = graph.min_cost_flow(b, c, 4, |_| 1);
+
+    assert_eq!(flow_map, None);
+
+    graph.insert_edge(a, c, 3).unwrap();
+
+    let flow_map = graph.min_cost_flow(b, c, 3, |_| 1).unwrap();
+
+    assert_eq!(flow_map[&bi_edge], 3);
+    assert_eq!(flow_map[&edge], 0);
+    assert_eq!(graph.min_cost_flow(b, a, 1, |_| -1), None);
+  }
+
+  /// Adds up the flows of the decomposition on every edge, assuming there are no parallel
+  /// edges.
+  fn recompose(graph: &FiniteGraph<(), u32>, decomposition: &[(Vec<Id>, u32)]) -> HashMap<Id, u32> {
+    let mut flow_map = HashMap::new();
+
+    for (vertices, value) in decomposition {
+      for pair in vertices.windows(2) {
+        let edge = graph.edges_map.iter()
+          .find(|&(&edge, &(_, from, to))| {
+            (from, to) == (pair[0], pair[1]) || graph.is_bi_edge(edge, from, to) && (to, from) == (pair[0], pair[1])
+          })
+          .map(|(&edge, _)| edge)
+          .unwrap();
+
+        *flow_map.entry(edge).or_insert(0) += value;
+      }
+    }
+
+    flow_map
+  }
+
+  #[test]
+  fn flow_decomposition_of_maximum_flow() {
+    let (graph, v) = flow_network();
+    let flow_map = graph.min_cost_flow(v[0], v[5], 23, |_| 1).unwrap();
+    let decomposition = graph.flow_decomposition(v[0], v[5], &flow_map);
+
+    assert_eq!(decomposition.iter().map(|(_, value)| value).sum::<u32>(), 23);
+    assert!(decomposition.iter().all(|(vertices, _)| vertices[0] == v[0] && vertices.last() == Some(&v[5])));
+
+    let positive_flow_map = flow_map.into_iter().filter(|&(_, value)| value > 0).collect::<HashMap<_, _>>();
+
+    assert_eq!(recompose(&graph, &decomposition), positive_flow_map);
+  }
+
+  #[test]
+  fn flow_decomposition_finds_cycles() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..5).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+    let mut flow_map = HashMap::new();
+
+    for &(from, to, flow, bidirectional) in &[
+      (0, 1, 5, false), (2, 1, 5, true), (2, 3, 5, false), (3, 0, 2, false),
+      (3, 4, 3, false), (4, 4, 1, false), (0, 4, 0, false)
+    ] {
+      let edge = if bidirectional {
+        graph.insert_bi_edge(v[from], v[to], 0).unwrap()
+      } else {
+        graph.insert_edge(v[from], v[to], 0).unwrap()
+      };
+
+      flow_map.insert(edge, flow);
+    }
+
+    let decomposition = graph.flow_decomposition(v[0], v[4], &flow_map);
+
+    assert_eq!(decomposition, vec![
+      (vec![v[0], v[1], v[2], v[3], v[4]], 3),
+      (vec![v[0], v[1], v[2], v[3], v[0]], 2),
+      (vec![v[4], v[4]], 1)
+    ]);
+
+    flow_map.retain(|_, &mut value| value > 0);
+
+    assert_eq!(recompose(&graph, &decomposition), flow_map);
+    assert_eq!(graph.flow_decomposition(v[4], v[4], &HashMap::new()), vec![]);
+  }
+
+  #[test]
+  fn flow_decomposition_orients_bi_edges() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let [s, x, y, q, t] = [(); 5].map(|_| graph.insert_vertex(()));
+    let mut flow_map = HashMap::new();
+
+    // The flow on `y - x` runs from `y` to `x`, but `x - y` has the smaller id at `x`
+    for &(from, to, flow, bidirectional) in &[
+      (s, x, 1, false), (s, q, 1, false), (q, y, 1, false), (y, x, 1, true), (x, t, 2, false)
+    ] {
+      let edge = if bidirectional {
+        graph.insert_bi_edge(from, to, 0).unwrap()
+      } else {
+        graph.insert_edge(from, to, 0).unwrap()
+      };
+
+      flow_map.insert(edge, flow);
+    }
+
+    let decomposition = graph.flow_decomposition(s, t, &flow_map);
+
+    assert_eq!(decomposition, vec![(vec![s, x, t], 1), (vec![s, q, y, x, t], 1)]);
+    assert_eq!(recompose(&graph, &decomposition), flow_map);
+
+    // Without the orientation from flow conservation, the search backtracks from `y`
+    let orientation = graph.edges_map.iter()
+      .filter(|&(&edge, &(_, from, to))| !graph.is_bi_edge(edge, from, to))
+      .map(|(&edge, &(_, from, to))| (edge, (from, to)))
+      .collect::<HashMap<_, _>>();
+    let (vertices, _) = graph.trace_flow(x, Some(t), &flow_map, &orientation);
+
+    assert_eq!(vertices, vec![x, t]);
+  }
+}
@@ -0,0 +1,194 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::error::Error;
+use super::*;
+
+/// The optional header of a graph6 string.
+const GRAPH6_HEADER: &str = ">>graph6<<";
+
+/// The error returned by [`FiniteGraph::from_graph6`](struct.FiniteGraph.html#method.from_graph6).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Graph6Error {
+  /// The byte at the position, not counting the header, is not a printable character between
+  /// `?` and `~`.
+  InvalidCharacter(usize),
+  /// The number of bytes doesn't match the number of vertices. Contains the expected and the
+  /// actual number of bytes, not counting the header.
+  InvalidLength(usize, usize)
+}
+
+impl fmt::Display for Graph6Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Graph6Error::InvalidCharacter(position) => write!(f, "invalid graph6 character at position {}", position),
+      Graph6Error::InvalidLength(expected, actual) => {
+        write!(f, "expected {} graph6 characters, found {}", expected, actual)
+      }
+    }
+  }
+}
+
+impl Error for Graph6Error {}
+
+/// Appends `bits` to `result` in chunks of six bits, padding the last chunk with zeros.
+fn push_graph6_bits(result: &mut String, bits: impl Iterator<Item = bool>) {
+  let mut chunk = 0;
+  let mut len = 0;
+
+  for bit in bits {
+    chunk = chunk << 1 | bit as u8;
+    len += 1;
+
+    if len == 6 {
+      result.push((chunk + 63) as char);
+      chunk = 0;
+      len = 0;
+    }
+  }
+
+  if len > 0 {
+    result.push(((chunk << (6 - len)) + 63) as char);
+  }
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Encodes the graph in the [graph6 format](https://users.cecs.anu.edu.au/~bdm/data/formats.txt)
+  /// used by tools like `nauty`, without header. Vertices are numbered in ascending order of
+  /// their ids. The format only describes simple undirected graphs, so edge directions and
+  /// values, parallel edges and self-loops are dropped.
+  pub fn to_graph6(&self) -> String {
+    let order = self.all_vertices_ordered().map(|(vertex, _)| vertex).collect::<Vec<_>>();
+    let index_map = order.iter()
+      .enumerate()
+      .map(|(index, &vertex)| (vertex, index))
+      .collect::<HashMap<_, _>>();
+    let edges = self.edges_map.values()
+      .map(|&(_, from, to)| (index_map[&from], index_map[&to]))
+      .map(|(i, j)| (i.min(j), i.max(j)))
+      .collect::<HashSet<_>>();
+    let n = order.len();
+    let mut result = String::new();
+
+    match n {
+      0..=62 => result.push((n as u8 + 63) as char),
+      63..=258_047 => {
+        result.push('~');
+        push_graph6_bits(&mut result, (0..18).rev().map(|i| n >> i & 1 == 1));
+      },
+      _ => {
+        result.push_str("~~");
+        push_graph6_bits(&mut result, (0..36).rev().map(|i| (n as u64) >> i & 1 == 1));
+      }
+    }
+
+    push_graph6_bits(&mut result, (1..n).flat_map(|j| (0..j).map(move |i| (i, j))).map(|edge| edges.contains(&edge)));
+
+    result
+  }
+}
+
+impl FiniteGraph<usize, ()> {
+  /// Decodes a graph in the [graph6 format](https://users.cecs.anu.edu.au/~bdm/data/formats.txt)
+  /// with an optional `>>graph6<<` header and surrounding whitespace. The vertices hold their
+  /// number and are inserted in ascending order, while each edge becomes a bidirectional edge.
+  pub fn from_graph6(s: &str) -> Result<FiniteGraph<usize, ()>, Graph6Error> {
+    let s = s.trim();
+    let bytes = s.strip_prefix(GRAPH6_HEADER).unwrap_or(s).as_bytes();
+
+    if let Some(position) = bytes.iter().position(|&byte| !(63..=126).contains(&byte)) {
+      return Err(Graph6Error::InvalidCharacter(position));
+    }
+
+    let chunks = bytes.iter().map(|&byte| (byte - 63) as u64).collect::<Vec<_>>();
+    let (n, offset) = match chunks[..] {
+      [] => return Err(Graph6Error::InvalidLength(1, 0)),
+      [63, 63, ..] if chunks.len() >= 8 => (chunks[2..8].iter().fold(0, |n, &chunk| n << 6 | chunk), 8),
+      [63, 63, ..] => return Err(Graph6Error::InvalidLength(8, chunks.len())),
+      [63, ..] if chunks.len() >= 4 => (chunks[1..4].iter().fold(0, |n, &chunk| n << 6 | chunk), 4),
+      [63, ..] => return Err(Graph6Error::InvalidLength(4, chunks.len())),
+      [n, ..] => (n, 1)
+    };
+    // A malformed header can claim up to 2^36 vertices, so the number of adjacency bits does
+    // not necessarily fit into 64 bits
+    let bits = n as u128 * n.saturating_sub(1) as u128 / 2;
+    let expected = usize::try_from(offset as u128 + bits.div_ceil(6)).unwrap_or(usize::MAX);
+
+    if chunks.len() != expected {
+      return Err(Graph6Error::InvalidLength(expected, chunks.len()));
+    }
+
+    let n = n as usize;
+
+    let mut graph = FiniteGraph::new();
+    let vertices = (0..n).map(|i| graph.insert_vertex(i)).collect::<Vec<_>>();
+    let edges = (1..n).flat_map(|j| (0..j).map(move |i| (i, j)));
+
+    for (k, (i, j)) in edges.enumerate() {
+      if chunks[offset + k / 6] >> (5 - k % 6) & 1 == 1 {
+        graph.insert_bi_edge(vertices[i], vertices[j], ()).unwrap();
+      }
+    }
+
+    Ok(graph)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use graph::Graph;
+
+  #[test]
+  fn graph6_round_trips_known_graphs() {
+    // The Petersen graph as listed in the House of Graphs
+    let petersen = FiniteGraph::from_graph6(">>graph6<<IheA@GUAo\n").unwrap();
+
+    assert_eq!(petersen.len(), (10, 15));
+    assert!(petersen.vertices_map.keys().all(|vertex| petersen.neighbors(vertex).len() == 3));
+    assert_eq!(petersen.to_graph6(), "IheA@GUAo");
+
+    // The complete graph K4 with a self-loop, a parallel edge and a reversed edge
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..4 {
+      for j in 0..i {
+        graph.insert_edge(v[i], v[j], 1).unwrap();
+      }
+    }
+
+    graph.insert_edge(v[0], v[1], 2).unwrap();
+    graph.insert_edge(v[2], v[2], 3).unwrap();
+
+    assert_eq!(graph.to_graph6(), "C~");
+
+    graph.remove_vertex(v[0]);
+
+    assert_eq!(graph.to_graph6(), "Bw");
+    assert_eq!(FiniteGraph::<(), ()>::new().to_graph6(), "?");
+
+    let mut large = FiniteGraph::<(), ()>::new();
+    let v = (0..63).map(|_| large.insert_vertex(())).collect::<Vec<_>>();
+
+    large.insert_edge(v[62], v[0], ()).unwrap();
+
+    let encoded = large.to_graph6();
+    let decoded = FiniteGraph::from_graph6(&encoded).unwrap();
+
+    assert!(encoded.starts_with("~??~"));
+    let vertices = decoded.all_vertices_ordered().map(|(vertex, _)| vertex).collect::<Vec<_>>();
+
+    assert_eq!(decoded.len(), (63, 1));
+    assert_eq!(decoded.neighbors(&vertices[0]), vec![vertices[62]]);
+    assert_eq!(decoded.to_graph6(), encoded);
+  }
+
+  #[test]
+  fn from_graph6_rejects_invalid_strings() {
+    assert_eq!(FiniteGraph::from_graph6("").err(), Some(Graph6Error::InvalidLength(1, 0)));
+    assert_eq!(FiniteGraph::from_graph6("C~?").err(), Some(Graph6Error::InvalidLength(2, 3)));
+    assert_eq!(FiniteGraph::from_graph6("Ih A@GUAo").err(), Some(Graph6Error::InvalidCharacter(2)));
+    assert_eq!(FiniteGraph::from_graph6("~?").err(), Some(Graph6Error::InvalidLength(4, 2)));
+    assert_eq!(FiniteGraph::from_graph6("~~~~~~~~").err(), Some(Graph6Error::InvalidLength(usize::MAX, 8)));
+  }
+}
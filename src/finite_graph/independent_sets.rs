@@ -0,0 +1,184 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use super::*;
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns `true` if no two of the given vertices are connected by an edge in any
+  /// direction, and none of them has a self-loop.
+  pub fn is_independent_set(&self, vertices: &[Id]) -> bool {
+    let set = vertices.iter().copied().collect::<HashSet<_>>();
+
+    set.iter().all(|&vertex| {
+      self.neighbors_map.get(&vertex).into_iter().flatten()
+      .all(|(neighbor, _)| !set.contains(neighbor))
+    })
+  }
+
+  /// Returns a maximal independent set, i.e. a set of vertices without edges between them that
+  /// can't be extended by another vertex, ignoring edge directions. The result is sorted.
+  ///
+  /// The set is built greedily by repeatedly picking a vertex of minimum degree among the
+  /// remaining vertices, and removing it together with its neighbors. Vertices with self-loops
+  /// are never picked. The result is not necessarily a maximum independent set.
+  pub fn maximal_independent_set(&self) -> Vec<Id> {
+    let mut degree_map = self.vertices_map.keys()
+      .map(|&vertex| (vertex, self.adjacent_set(vertex).len()))
+      .collect::<HashMap<_, _>>();
+    let mut buckets = degree_map.iter()
+      .map(|(&vertex, &degree)| (degree, vertex))
+      .collect::<BTreeSet<_>>();
+    let mut result = vec![];
+
+    let remove = |vertex: Id, degree_map: &mut HashMap<Id, usize>, buckets: &mut BTreeSet<(usize, Id)>| {
+      if let Some(degree) = degree_map.remove(&vertex) {
+        buckets.remove(&(degree, vertex));
+
+        for neighbor in self.adjacent_set(vertex) {
+          if let Some(degree) = degree_map.get_mut(&neighbor) {
+            buckets.remove(&(*degree, neighbor));
+            *degree -= 1;
+            buckets.insert((*degree, neighbor));
+          }
+        }
+      }
+    };
+
+    while let Some(&(_, vertex)) = buckets.iter().next() {
+      remove(vertex, &mut degree_map, &mut buckets);
+
+      if self.parallel_edge_count(vertex, vertex) > 0 {
+        continue;
+      }
+
+      for neighbor in self.adjacent_set(vertex) {
+        remove(neighbor, &mut degree_map, &mut buckets);
+      }
+
+      result.push(vertex);
+    }
+
+    result.sort();
+    result
+  }
+
+  /// Returns an independent set with maximum weight sum, where the weight of a vertex is its
+  /// value converted into `f64` and edge directions are ignored. The result is sorted. Vertices
+  /// with self-loops are never picked and vertices with non-positive weight are never needed.
+  ///
+  /// Only works on forests: returns `None` unless the graph without edge directions and
+  /// parallel edges is a forest, e.g. if each vertex has at most one incoming edge. Finding a
+  /// maximum weight independent set is NP-hard in general, even for directed acyclic graphs,
+  /// whereas on forests it takes `O(V + E)` time with dynamic programming.
+  pub fn maximum_weight_independent_set_forest(&self) -> Option<Vec<Id>> where V: Clone + Into<f64> {
+    let mut roots = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    roots.sort();
+
+    let mut parent_map = HashMap::<Id, Option<Id>>::new();
+    let mut order = vec![];
+
+    for root in roots {
+      if parent_map.contains_key(&root) {
+        continue;
+      }
+
+      parent_map.insert(root, None);
+      let mut stack = vec![root];
+
+      while let Some(vertex) = stack.pop() {
+        order.push(vertex);
+
+        for neighbor in self.adjacent_set(vertex) {
+          if parent_map.get(&vertex) == Some(&Some(neighbor)) {
+            continue;
+          } else if parent_map.contains_key(&neighbor) {
+            return None;
+          }
+
+          parent_map.insert(neighbor, Some(vertex));
+          stack.push(neighbor);
+        }
+      }
+    }
+
+    // The best weight sums of each subtree with and without its root
+    let mut with_map = HashMap::<Id, f64>::new();
+    let mut without_map = HashMap::<Id, f64>::new();
+
+    for &vertex in order.iter().rev() {
+      let weight: f64 = self.vertices_map[&vertex].clone().into();
+      let with = if self.parallel_edge_count(vertex, vertex) > 0 { f64::NEG_INFINITY } else { weight };
+
+      *with_map.entry(vertex).or_insert(0.0) += with;
+      without_map.entry(vertex).or_insert(0.0);
+
+      if let Some(parent) = parent_map[&vertex] {
+        let best = with_map[&vertex].max(without_map[&vertex]);
+
+        *without_map.entry(parent).or_insert(0.0) += best;
+        *with_map.entry(parent).or_insert(0.0) += without_map[&vertex];
+      }
+    }
+
+    let mut picked = HashSet::new();
+
+    for &vertex in &order {
+      let parent_picked = parent_map[&vertex].is_some_and(|parent| picked.contains(&parent));
+
+      if !parent_picked && with_map[&vertex] > without_map[&vertex] {
+        picked.insert(vertex);
+      }
+    }
+
+    let mut result = picked.into_iter().collect::<Vec<_>>();
+    result.sort();
+
+    Some(result)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn maximal_independent_set_of_star_and_path() {
+    let mut graph = FiniteGraph::<f64, ()>::new();
+    let v = (0..7).map(|_| graph.insert_vertex(1.0)).collect::<Vec<_>>();
+
+    // A star with center 0, and a path 4 - 5 - 6 with a self-loop on 6
+    for i in 1..4 {
+      graph.insert_edge(v[0], v[i], ()).unwrap();
+    }
+
+    graph.insert_bi_edge(v[4], v[5], ()).unwrap();
+    graph.insert_edge(v[6], v[5], ()).unwrap();
+    graph.insert_edge(v[6], v[6], ()).unwrap();
+
+    let set = graph.maximal_independent_set();
+
+    assert_eq!(set, vec![v[1], v[2], v[3], v[4]]);
+    assert!(graph.is_independent_set(&set));
+    assert!(!graph.is_independent_set(&[v[0], v[1]]));
+    assert!(!graph.is_independent_set(&[v[6]]));
+    assert!(graph.is_independent_set(&[]));
+  }
+
+  #[test]
+  fn maximum_weight_independent_set_of_tree() {
+    let mut graph = FiniteGraph::<f64, ()>::new();
+    let weights = [10.0, 3.0, 3.0, 1.0, 5.0, 2.0];
+    let v = weights.iter().map(|&weight| graph.insert_vertex(weight)).collect::<Vec<_>>();
+
+    for &(from, to) in &[(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)] {
+      graph.insert_edge(v[from], v[to], ()).unwrap();
+    }
+
+    let set = graph.maximum_weight_independent_set_forest().unwrap();
+
+    assert_eq!(set, vec![v[0], v[3], v[4], v[5]]);
+    assert!(graph.is_independent_set(&set));
+
+    graph.insert_edge(v[3], v[4], ()).unwrap();
+
+    assert_eq!(graph.maximum_weight_independent_set_forest(), None);
+  }
+}
@@ -0,0 +1,434 @@
+use std::collections::{HashMap, HashSet};
+use super::*;
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Orders the vertices such that each vertex is connected with as many preceding vertices
+  /// as possible, starting with a vertex of maximum degree, which helps pruning the search early.
+  fn matching_order(&self) -> Vec<Id> {
+    let mut remaining = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    let mut order = Vec::with_capacity(remaining.len());
+    let mut connections = HashMap::<Id, usize>::new();
+
+    remaining.sort();
+
+    while !remaining.is_empty() {
+      let index = (0..remaining.len())
+        .max_by_key(|&i| {
+          let vertex = remaining[i];
+          let degree = self.out_degree(vertex) + self.in_degree(vertex);
+
+          (connections.get(&vertex).copied().unwrap_or(0), degree, std::cmp::Reverse(i))
+        })
+        .unwrap();
+      let vertex = remaining.remove(index);
+
+      for (neighbor, _) in self.undirected_neighbors(vertex) {
+        *connections.entry(neighbor).or_insert(0) += 1;
+      }
+
+      order.push(vertex);
+    }
+
+    order
+  }
+
+  /// Returns `true` if `pattern` is isomorphic to a subgraph of the graph, see
+  /// [`find_subgraph_mapping`](#method.find_subgraph_mapping).
+  pub fn contains_subgraph<W, F>(&self, pattern: &FiniteGraph<W, F>) -> bool {
+    self.find_subgraph_mapping(pattern).is_some()
+  }
+
+  /// Searches an injective mapping from the vertices of `pattern` to the vertices of the graph,
+  /// such that for every edge of `pattern` there is a corresponding edge between the mapped
+  /// vertices with the same direction. Parallel edges of `pattern` need as many parallel edges
+  /// in the graph. Vertex and edge data is ignored, and the graph may contain additional edges
+  /// between mapped vertices. Returns `None` if there is no such mapping.
+  ///
+  /// This uses a backtracking search, which takes exponential time in the worst case, so it is
+  /// only recommended for patterns with at most 8 vertices.
+  pub fn find_subgraph_mapping<W, F>(&self, pattern: &FiniteGraph<W, F>) -> Option<HashMap<Id, Id>> {
+    let order = pattern.matching_order();
+    let mut mapping = HashMap::new();
+    let mut used = HashSet::new();
+
+    if order.len() <= self.vertices_map.len()
+      && self.extend_subgraph_mapping(pattern, &order, &mut mapping, &mut used)
+    {
+      Some(mapping)
+    } else {
+      None
+    }
+  }
+
+  /// Returns the number of distinct vertices `vertex` has edges to and from.
+  fn distinct_degrees(&self, vertex: Id) -> (usize, usize) {
+    let count = |map: &HashMap<Id, Vec<(Id, Id)>>| {
+      map.get(&vertex).map_or(0, |neighbors| {
+        neighbors.iter().map(|&(neighbor, _)| neighbor).collect::<HashSet<_>>().len()
+      })
+    };
+
+    (count(&self.neighbors_map), count(&self.reverse_neighbors_map))
+  }
+
+  /// Lazily yields all injective mappings from the vertices of `pattern` to the vertices of
+  /// the graph, such that `vertex_match` accepts the values of every pattern vertex and its
+  /// image, and for every edge of `pattern` there is an edge between the mapped vertices with
+  /// the same direction which `edge_match` accepts. Unlike
+  /// [`find_subgraph_mapping`](#method.find_subgraph_mapping), parallel edges of `pattern` may
+  /// be matched by the same edge of the graph, i.e. they only need to be present at least once.
+  ///
+  /// Mappings are yielded in lexicographic order of the images, where pattern vertices are
+  /// ordered to connect to as many preceding vertices as possible. Symmetric patterns yield
+  /// one mapping per automorphism, e.g. a directed triangle is found three times. The
+  /// backtracking search only extends partial mappings along edges and prunes candidates with
+  /// too few distinct neighbors, which is suitable for patterns with at most 8 vertices.
+  pub fn find_subgraph_isomorphisms<'a, VP, EP>(
+    &'a self,
+    pattern: &'a FiniteGraph<VP, EP>,
+    vertex_match: impl Fn(&V, &VP) -> bool + 'a,
+    edge_match: impl Fn(&E, &EP) -> bool + 'a
+  ) -> impl Iterator<Item = HashMap<Id, Id>> + 'a {
+    SubgraphIsomorphisms {
+      graph: self,
+      pattern,
+      vertex_match,
+      edge_match,
+      order: pattern.matching_order(),
+      degree_map: self.vertices_map.keys().map(|&vertex| (vertex, self.distinct_degrees(vertex))).collect(),
+      started: false,
+      stack: vec![],
+      images: vec![],
+      used: HashSet::new()
+    }
+  }
+
+  fn extend_subgraph_mapping<W, F>(
+    &self,
+    pattern: &FiniteGraph<W, F>,
+    order: &[Id],
+    mapping: &mut HashMap<Id, Id>,
+    used: &mut HashSet<Id>
+  ) -> bool {
+    let vertex = match order.get(mapping.len()) {
+      Some(&vertex) => vertex,
+      None => return true
+    };
+
+    // Candidates have to be adjacent to the image of an already mapped neighbor
+    let mapped_neighbor = pattern.undirected_neighbors(vertex)
+      .find_map(|(neighbor, _)| mapping.get(&neighbor).copied());
+
+    let mut candidates = match mapped_neighbor {
+      Some(image) => self.undirected_neighbors(image).map(|(neighbor, _)| neighbor).collect::<Vec<_>>(),
+      None => self.vertices_map.keys().copied().collect()
+    };
+
+    candidates.sort();
+    candidates.dedup();
+
+    for candidate in candidates {
+      if used.contains(&candidate)
+        || self.out_degree(candidate) < pattern.out_degree(vertex)
+        || self.in_degree(candidate) < pattern.in_degree(vertex)
+        || self.parallel_edge_count(candidate, candidate) < pattern.parallel_edge_count(vertex, vertex)
+      {
+        continue;
+      }
+
+      let compatible = mapping.iter().all(|(&other, &image)| {
+        self.parallel_edge_count(candidate, image) >= pattern.parallel_edge_count(vertex, other)
+        && self.parallel_edge_count(image, candidate) >= pattern.parallel_edge_count(other, vertex)
+      });
+
+      if !compatible {
+        continue;
+      }
+
+      mapping.insert(vertex, candidate);
+      used.insert(candidate);
+
+      if self.extend_subgraph_mapping(pattern, order, mapping, used) {
+        return true;
+      }
+
+      mapping.remove(&vertex);
+      used.remove(&candidate);
+    }
+
+    false
+  }
+}
+
+/// The lazy backtracking search of
+/// [`FiniteGraph::find_subgraph_isomorphisms`](struct.FiniteGraph.html#method.find_subgraph_isomorphisms).
+struct SubgraphIsomorphisms<'a, V, E, VP, EP, FV, FE> {
+  graph: &'a FiniteGraph<V, E>,
+  pattern: &'a FiniteGraph<VP, EP>,
+  vertex_match: FV,
+  edge_match: FE,
+  order: Vec<Id>,
+  degree_map: HashMap<Id, (usize, usize)>,
+  started: bool,
+  // The remaining candidates for each pattern vertex in `order` up to the current one, in
+  // reverse order, and the images of the pattern vertices before the current one
+  stack: Vec<Vec<Id>>,
+  images: Vec<Id>,
+  used: HashSet<Id>
+}
+
+impl<'a, V, E, VP, EP, FV, FE> SubgraphIsomorphisms<'a, V, E, VP, EP, FV, FE>
+where
+  FV: Fn(&V, &VP) -> bool,
+  FE: Fn(&E, &EP) -> bool
+{
+  fn candidates(&self) -> Vec<Id> {
+    let vertex = self.order[self.images.len()];
+    let mapped_neighbor = self.pattern.undirected_neighbors(vertex)
+      .find_map(|(neighbor, _)| {
+        self.order[..self.images.len()].iter()
+        .position(|&other| other == neighbor)
+        .map(|index| self.images[index])
+      });
+
+    let mut candidates = match mapped_neighbor {
+      Some(image) => self.graph.undirected_neighbors(image).map(|(neighbor, _)| neighbor).collect::<Vec<_>>(),
+      None => self.graph.vertices_map.keys().copied().collect()
+    };
+
+    candidates.sort_by(|a, b| b.cmp(a));
+    candidates.dedup();
+    candidates
+  }
+
+  /// Returns `true` if there is an edge from `vertex` to `other` in the graph matching `edge`.
+  fn has_matching_edge(&self, vertex: Id, other: Id, edge: Id) -> bool {
+    let data = &self.pattern.edges_map[&edge].0;
+
+    self.graph.neighbors_map.get(&vertex).into_iter().flatten()
+    .any(|&(neighbor, host_edge)| {
+      neighbor == other && (self.edge_match)(&self.graph.edges_map[&host_edge].0, data)
+    })
+  }
+
+  fn is_feasible(&self, candidate: Id) -> bool {
+    let vertex = self.order[self.images.len()];
+    let (out_degree, in_degree) = self.pattern.distinct_degrees(vertex);
+    let (host_out_degree, host_in_degree) = self.degree_map[&candidate];
+
+    if self.used.contains(&candidate)
+      || host_out_degree < out_degree
+      || host_in_degree < in_degree
+      || !(self.vertex_match)(&self.graph.vertices_map[&candidate], &self.pattern.vertices_map[&vertex])
+    {
+      return false;
+    }
+
+    let image = |other: Id| {
+      if other == vertex {
+        Some(candidate)
+      } else {
+        self.order[..self.images.len()].iter()
+        .position(|&mapped| mapped == other)
+        .map(|index| self.images[index])
+      }
+    };
+
+    let outgoing = self.pattern.neighbors_map.get(&vertex).into_iter().flatten()
+      .filter_map(|&(neighbor, edge)| image(neighbor).map(|other| (candidate, other, edge)));
+    let incoming = self.pattern.reverse_neighbors_map.get(&vertex).into_iter().flatten()
+      .filter(|&&(neighbor, _)| neighbor != vertex)
+      .filter_map(|&(neighbor, edge)| image(neighbor).map(|other| (other, candidate, edge)));
+
+    outgoing.chain(incoming).all(|(from, to, edge)| self.has_matching_edge(from, to, edge))
+  }
+}
+
+impl<'a, V, E, VP, EP, FV, FE> Iterator for SubgraphIsomorphisms<'a, V, E, VP, EP, FV, FE>
+where
+  FV: Fn(&V, &VP) -> bool,
+  FE: Fn(&E, &EP) -> bool
+{
+  type Item = HashMap<Id, Id>;
+
+  fn next(&mut self) -> Option<HashMap<Id, Id>> {
+    if !self.started {
+      self.started = true;
+
+      if self.order.is_empty() {
+        return Some(HashMap::new());
+      } else if self.order.len() > self.graph.vertices_map.len() {
+        return None;
+      }
+
+      self.stack.push(self.candidates());
+    }
+
+    loop {
+      match self.stack.last_mut()?.pop() {
+        Some(candidate) => {
+          if !self.is_feasible(candidate) {
+            continue;
+          }
+
+          self.images.push(candidate);
+          self.used.insert(candidate);
+
+          if self.images.len() < self.order.len() {
+            self.stack.push(self.candidates());
+            continue;
+          }
+
+          let mapping = self.order.iter().copied().zip(self.images.iter().copied()).collect();
+
+          self.images.pop();
+          self.used.remove(&candidate);
+
+          return Some(mapping);
+        },
+        None => {
+          self.stack.pop();
+
+          if let Some(image) = self.images.pop() {
+            self.used.remove(&image);
+          }
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn cycle(size: usize, bidirectional: bool) -> FiniteGraph<(), ()> {
+    let mut graph = FiniteGraph::new();
+    let vertices = (0..size).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..size {
+      let (from, to) = (vertices[i], vertices[(i + 1) % size]);
+
+      if bidirectional {
+        graph.insert_bi_edge(from, to, ()).unwrap();
+      } else {
+        graph.insert_edge(from, to, ()).unwrap();
+      }
+    }
+
+    graph
+  }
+
+  #[test]
+  fn find_subgraph_mapping_respects_edges() {
+    let mut host = FiniteGraph::<&str, u32>::new();
+    let v = (0..5).map(|_| host.insert_vertex("")).collect::<Vec<_>>();
+
+    for &(from, to) in &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (0, 3)] {
+      host.insert_edge(v[from], v[to], 1).unwrap();
+    }
+
+    let triangle = cycle(3, false);
+    let mapping = host.find_subgraph_mapping(&triangle).unwrap();
+
+    assert_eq!(mapping.len(), 3);
+
+    for (from, to, _) in &triangle {
+      assert_eq!(host.parallel_edge_count(mapping[&from], mapping[&to]), 1);
+    }
+
+    assert!(!host.contains_subgraph(&cycle(3, true)));
+    assert!(host.contains_subgraph(&cycle(4, false)));
+    assert!(!host.contains_subgraph(&cycle(5, false)));
+    assert!(host.contains_subgraph(&FiniteGraph::<(), ()>::new()));
+  }
+
+  #[test]
+  fn find_subgraph_mapping_of_disconnected_pattern() {
+    let host = cycle(6, true);
+
+    // Two parallel bidirectional edges don't fit into the host
+    assert!(!host.contains_subgraph(&cycle(2, true)));
+
+    let mut pattern = FiniteGraph::<(), ()>::new();
+    let v = (0..4).map(|_| pattern.insert_vertex(())).collect::<Vec<_>>();
+
+    pattern.insert_bi_edge(v[0], v[1], ()).unwrap();
+    pattern.insert_edge(v[2], v[3], ()).unwrap();
+
+    assert!(host.contains_subgraph(&pattern));
+
+    pattern.insert_vertex(());
+    pattern.insert_vertex(());
+    pattern.insert_vertex(());
+
+    assert!(!host.contains_subgraph(&pattern));
+  }
+
+  #[test]
+  fn find_subgraph_isomorphisms_counts_triangles() {
+    let mut host = FiniteGraph::<usize, ()>::new();
+    let v = (0..12).map(|i| host.insert_vertex(i)).collect::<Vec<_>>();
+
+    for i in 0..12 {
+      for j in i + 1..12 {
+        if (i * 7 + j * 5) % 4 == 0 || j == i + 1 {
+          host.insert_bi_edge(v[i], v[j], ()).unwrap();
+        }
+      }
+    }
+
+    let adjacent = |i: usize, j: usize| host.parallel_edge_count(v[i], v[j]) > 0;
+    let triangle_count = (0..12)
+      .flat_map(|i| (i + 1..12).flat_map(move |j| (j + 1..12).map(move |k| (i, j, k))))
+      .filter(|&(i, j, k)| adjacent(i, j) && adjacent(j, k) && adjacent(i, k))
+      .count();
+
+    assert!(triangle_count > 0);
+
+    // Every undirected triangle has six automorphisms
+    let count = host.find_subgraph_isomorphisms(&cycle(3, true), |_, _| true, |_, _| true).count();
+
+    assert_eq!(count, 6 * triangle_count);
+
+    // Only triangles whose vertices are all even
+    let count = host.find_subgraph_isomorphisms(&cycle(3, true), |&value, _| value % 2 == 0, |_, _| true).count();
+    let even_count = (0..12).step_by(2)
+      .flat_map(|i| (i + 2..12).step_by(2).flat_map(move |j| (j + 2..12).step_by(2).map(move |k| (i, j, k))))
+      .filter(|&(i, j, k)| adjacent(i, j) && adjacent(j, k) && adjacent(i, k))
+      .count();
+
+    assert_eq!(count, 6 * even_count);
+  }
+
+  #[test]
+  fn find_subgraph_isomorphisms_respects_directions_and_data() {
+    let mut host = FiniteGraph::<(), u32>::new();
+    let v = (0..4).map(|_| host.insert_vertex(())).collect::<Vec<_>>();
+
+    for &(from, to, weight) in &[(0, 1, 1), (1, 2, 1), (2, 0, 1), (1, 3, 2), (3, 2, 2)] {
+      host.insert_edge(v[from], v[to], weight).unwrap();
+    }
+
+    let mut pattern = cycle(3, false);
+
+    assert_eq!(host.find_subgraph_isomorphisms(&pattern, |_, _| true, |_, _| true).count(), 3);
+    assert_eq!(host.find_subgraph_isomorphisms(&pattern, |_, _| true, |&w, _| w == 2).count(), 0);
+
+    // A parallel pattern edge may be matched by the same host edge
+    let edge = pattern.all_edges_ordered().next().unwrap().0;
+    let (from, to) = {
+      let &(_, from, to) = &pattern.edges_map[&edge];
+      (from, to)
+    };
+
+    pattern.insert_edge(from, to, ()).unwrap();
+
+    let mappings = host.find_subgraph_isomorphisms(&pattern, |_, _| true, |_, _| true).collect::<Vec<_>>();
+
+    assert_eq!(mappings.len(), 3);
+    assert!(mappings.iter().all(|mapping| host.parallel_edge_count(mapping[&from], mapping[&to]) == 1));
+    assert_eq!(host.find_subgraph_isomorphisms(&cycle(3, true), |_, _| true, |_, _| true).count(), 0);
+    assert_eq!(host.find_subgraph_isomorphisms(&FiniteGraph::<(), ()>::new(), |_, _| true, |_, _| true).count(), 1);
+  }
+}
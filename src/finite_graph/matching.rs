@@ -0,0 +1,297 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use super::*;
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns the neighbors of every vertex in `left`, if all edges of the graph connect
+  /// a vertex of `left` with a vertex of `right`, ignoring edge directions.
+  fn bipartite_adjacency(&self, left: &[Id], right: &[Id]) -> Option<HashMap<Id, Vec<Id>>> {
+    let left_set = left.iter().copied().collect::<HashSet<_>>();
+    let right_set = right.iter().copied().collect::<HashSet<_>>();
+
+    if left_set.iter().chain(&right_set).any(|vertex| !self.vertices_map.contains_key(vertex))
+      || !left_set.is_disjoint(&right_set)
+    {
+      return None;
+    }
+
+    let mut adjacency = left.iter().map(|&vertex| (vertex, vec![])).collect::<HashMap<_, _>>();
+
+    for &(_, from, to) in self.edges_map.values() {
+      let (vertex, other) = if left_set.contains(&from) && right_set.contains(&to) {
+        (from, to)
+      } else if left_set.contains(&to) && right_set.contains(&from) {
+        (to, from)
+      } else {
+        return None;
+      };
+
+      adjacency.get_mut(&vertex).unwrap().push(other);
+    }
+
+    for neighbors in adjacency.values_mut() {
+      neighbors.sort();
+      neighbors.dedup();
+    }
+
+    Some(adjacency)
+  }
+
+  /// Colors the component of `start` with two colors using a breadth-first search, such that
+  /// adjacent vertices get different colors, ignoring edge directions. `start` gets the color
+  /// `false`. Returns `false` if the component is not bipartite.
+  fn two_color_component(&self, start: Id, coloring: &mut HashMap<Id, bool>) -> bool {
+    let mut queue = VecDeque::new();
+
+    coloring.insert(start, false);
+    queue.push_back(start);
+
+    while let Some(vertex) = queue.pop_front() {
+      let color = coloring[&vertex];
+
+      for (neighbor, _) in self.undirected_neighbors(vertex) {
+        match coloring.get(&neighbor) {
+          Some(&neighbor_color) if neighbor_color == color => return false,
+          Some(_) => {},
+          None => {
+            coloring.insert(neighbor, !color);
+            queue.push_back(neighbor);
+          }
+        }
+      }
+    }
+
+    true
+  }
+
+  /// Splits a two-coloring into the sorted vertices of both colors.
+  fn partition_by_color(coloring: HashMap<Id, bool>) -> (Vec<Id>, Vec<Id>) {
+    let (mut first, mut second) = (vec![], vec![]);
+
+    for (vertex, color) in coloring {
+      if color { second.push(vertex) } else { first.push(vertex) }
+    }
+
+    first.sort();
+    second.sort();
+    (first, second)
+  }
+
+  /// Splits the vertices of the graph into two sets, such that every edge connects a vertex of
+  /// the first set with a vertex of the second set, ignoring edge directions. Returns `None` if
+  /// there is no such partition, e.g. if the graph has an odd cycle or a self-loop.
+  ///
+  /// The vertex with the smallest id of each connected component is put into the first set.
+  /// Both sets are sorted and can be passed to the bipartite matching methods.
+  pub fn bipartite_partition(&self) -> Option<(Vec<Id>, Vec<Id>)> {
+    let mut vertices = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    let mut coloring = HashMap::new();
+
+    vertices.sort();
+
+    for vertex in vertices {
+      if !coloring.contains_key(&vertex) && !self.two_color_component(vertex, &mut coloring) {
+        return None;
+      }
+    }
+
+    Some(FiniteGraph::<V, E>::partition_by_color(coloring))
+  }
+
+  /// Like [`bipartite_partition`](#method.bipartite_partition), but only splits the connected
+  /// component of `start`, which is put into the first set. Returns `None` if the component is
+  /// not bipartite or `start` doesn't exist.
+  pub fn bipartite_partition_from(&self, start: Id) -> Option<(Vec<Id>, Vec<Id>)> {
+    let mut coloring = HashMap::new();
+
+    if !self.contains_vertex(start) || !self.two_color_component(start, &mut coloring) {
+      return None;
+    }
+
+    Some(FiniteGraph::<V, E>::partition_by_color(coloring))
+  }
+
+  /// Splits the vertices of all connected components into two sets. This is an alias of
+  /// [`bipartite_partition`](#method.bipartite_partition), which checks every component.
+  pub fn bipartite_partition_all(&self) -> Option<(Vec<Id>, Vec<Id>)> {
+    self.bipartite_partition()
+  }
+
+  /// Returns the partner in `right` of every matched vertex in `left` of a maximum matching.
+  fn bipartite_matching_map(left: &[Id], adjacency: &HashMap<Id, Vec<Id>>) -> HashMap<Id, Id> {
+    fn augment(
+      vertex: Id,
+      adjacency: &HashMap<Id, Vec<Id>>,
+      visited: &mut HashSet<Id>,
+      partner_map: &mut HashMap<Id, Id>
+    ) -> bool {
+      for &other in &adjacency[&vertex] {
+        if !visited.insert(other) {
+          continue;
+        }
+
+        let augmented = match partner_map.get(&other) {
+          None => true,
+          Some(&partner) => augment(partner, adjacency, visited, partner_map)
+        };
+
+        if augmented {
+          partner_map.insert(other, vertex);
+          return true;
+        }
+      }
+
+      false
+    }
+
+    let mut partner_map = HashMap::new();
+
+    for &vertex in left {
+      augment(vertex, adjacency, &mut HashSet::new(), &mut partner_map);
+    }
+
+    partner_map.into_iter().map(|(other, vertex)| (vertex, other)).collect()
+  }
+
+  /// Finds a maximum matching of a bipartite graph with the partition `left` and `right`,
+  /// ignoring edge directions, and returns it as pairs of matched vertices from `left`
+  /// and `right`, sorted by the left vertex.
+  ///
+  /// Returns `None` if the graph is not bipartite on the given partition, i.e. if the
+  /// partition overlaps, references unknown vertices, or if there is an edge that doesn't
+  /// connect a vertex of `left` with a vertex of `right`.
+  pub fn maximum_bipartite_matching(&self, left: &[Id], right: &[Id]) -> Option<Vec<(Id, Id)>> {
+    let adjacency = self.bipartite_adjacency(left, right)?;
+    let mut matching = FiniteGraph::<V, E>::bipartite_matching_map(left, &adjacency)
+      .into_iter()
+      .collect::<Vec<_>>();
+
+    matching.sort();
+    Some(matching)
+  }
+
+  /// Finds a minimum vertex cover of a bipartite graph with the partition `left` and `right`,
+  /// i.e. a smallest set of vertices such that every edge has at least one endpoint in it.
+  /// The result is sorted.
+  ///
+  /// By Kőnig's theorem, the size of the cover equals the size of a
+  /// [maximum matching](#method.maximum_bipartite_matching). The cover consists of all vertices
+  /// of `left` that can't be reached by an alternating path from an unmatched vertex of `left`,
+  /// and all vertices of `right` that can.
+  ///
+  /// Returns `None` if the graph is not bipartite on the given partition.
+  pub fn minimum_vertex_cover_bipartite(&self, left: &[Id], right: &[Id]) -> Option<Vec<Id>> {
+    let adjacency = self.bipartite_adjacency(left, right)?;
+    let matching_map = FiniteGraph::<V, E>::bipartite_matching_map(left, &adjacency);
+    let reverse_matching_map = matching_map.iter()
+      .map(|(&vertex, &other)| (other, vertex))
+      .collect::<HashMap<_, _>>();
+
+    let mut stack = adjacency.keys()
+      .copied()
+      .filter(|vertex| !matching_map.contains_key(vertex))
+      .collect::<Vec<_>>();
+    let mut reached = stack.iter().copied().collect::<HashSet<_>>();
+
+    while let Some(vertex) = stack.pop() {
+      for &other in &adjacency[&vertex] {
+        if matching_map.get(&vertex) == Some(&other) || !reached.insert(other) {
+          continue;
+        }
+
+        if let Some(&partner) = reverse_matching_map.get(&other) {
+          if reached.insert(partner) {
+            stack.push(partner);
+          }
+        }
+      }
+    }
+
+    let mut cover = adjacency.keys()
+      .copied()
+      .filter(|vertex| !reached.contains(vertex))
+      .chain(right.iter().copied().filter(|vertex| reached.contains(vertex)))
+      .collect::<Vec<_>>();
+
+    cover.sort();
+    cover.dedup();
+    Some(cover)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn minimum_vertex_cover_matches_maximum_matching() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let left = (0..3).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+    let right = (0..3).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_edge(left[0], right[0], ()).unwrap();
+    graph.insert_edge(left[0], right[1], ()).unwrap();
+    graph.insert_edge(left[0], right[2], ()).unwrap();
+    graph.insert_edge(right[1], left[1], ()).unwrap();
+    graph.insert_bi_edge(left[2], right[1], ()).unwrap();
+
+    let matching = graph.maximum_bipartite_matching(&left, &right).unwrap();
+    let cover = graph.minimum_vertex_cover_bipartite(&left, &right).unwrap();
+
+    assert_eq!(matching.len(), 2);
+    assert_eq!(cover, vec![left[0], right[1]]);
+
+    for (from, to, _) in &graph {
+      assert!(cover.contains(&from) || cover.contains(&to));
+    }
+  }
+
+  #[test]
+  fn minimum_vertex_cover_rejects_invalid_partition() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+
+    graph.insert_edge(a, b, ()).unwrap();
+    graph.insert_edge(b, c, ()).unwrap();
+
+    assert_eq!(graph.minimum_vertex_cover_bipartite(&[a, c], &[b]), Some(vec![b]));
+    assert_eq!(graph.minimum_vertex_cover_bipartite(&[a], &[b, c]), None);
+    assert_eq!(graph.minimum_vertex_cover_bipartite(&[a, b], &[b, c]), None);
+  }
+
+  #[test]
+  fn bipartite_partitions_of_components() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..7).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    assert_eq!(FiniteGraph::<(), ()>::new().bipartite_partition(), Some((vec![], vec![])));
+
+    graph.insert_edge(v[0], v[1], ()).unwrap();
+    graph.insert_bi_edge(v[2], v[1], ()).unwrap();
+    graph.insert_edge(v[3], v[2], ()).unwrap();
+    graph.insert_edge(v[0], v[3], ()).unwrap();
+    graph.insert_edge(v[5], v[4], ()).unwrap();
+
+    assert_eq!(
+      graph.bipartite_partition(),
+      Some((vec![v[0], v[2], v[4], v[6]], vec![v[1], v[3], v[5]]))
+    );
+    assert_eq!(graph.bipartite_partition_all(), graph.bipartite_partition());
+    assert_eq!(graph.bipartite_partition_from(v[5]), Some((vec![v[5]], vec![v[4]])));
+
+    let (left, right) = graph.bipartite_partition().unwrap();
+
+    assert_eq!(graph.maximum_bipartite_matching(&left, &right).map(|matching| matching.len()), Some(3));
+
+    graph.insert_edge(v[6], v[6], ()).unwrap();
+
+    assert_eq!(graph.bipartite_partition(), None);
+    assert_eq!(graph.bipartite_partition_from(v[6]), None);
+    assert!(graph.bipartite_partition_from(v[0]).is_some());
+
+    graph.insert_edge(v[0], v[2], ()).unwrap();
+
+    assert_eq!(graph.bipartite_partition_from(v[3]), None);
+  }
+}
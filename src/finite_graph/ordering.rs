@@ -0,0 +1,140 @@
+use std::collections::{HashMap, HashSet};
+use super::*;
+
+/// Returns the breadth-first levels of the component of `start`.
+fn bfs_levels(adjacency_map: &HashMap<Id, Vec<Id>>, start: Id) -> Vec<Vec<Id>> {
+  let mut visited = HashSet::new();
+  let mut levels = vec![vec![start]];
+
+  visited.insert(start);
+
+  loop {
+    let next = levels.last().unwrap().iter()
+      .flat_map(|vertex| &adjacency_map[vertex])
+      .copied()
+      .filter(|&neighbor| visited.insert(neighbor))
+      .collect::<Vec<_>>();
+
+    if next.is_empty() {
+      return levels;
+    }
+
+    levels.push(next);
+  }
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns all vertices in the reverse Cuthill-McKee order, which tends to move the non-zero
+  /// entries of the adjacency matrix close to its diagonal, i.e. it reduces the
+  /// [`bandwidth`](#method.bandwidth). Edge directions and self-loops are ignored.
+  ///
+  /// Each connected component is traversed breadth-first from a pseudo-peripheral vertex,
+  /// which is found by repeatedly starting over from a vertex of minimum degree in the last
+  /// level until the number of levels stops growing. Neighbors are visited in ascending order
+  /// of their degrees. The reversed orderings of the components are concatenated in ascending
+  /// order of the smallest id in each component.
+  pub fn reverse_cuthill_mckee(&self) -> Vec<Id> {
+    let mut adjacency_map = self.vertices_map.keys()
+      .map(|&vertex| (vertex, self.adjacent_set(vertex).into_iter().collect::<Vec<_>>()))
+      .collect::<HashMap<_, _>>();
+    let degree_map = adjacency_map.iter()
+      .map(|(&vertex, neighbors)| (vertex, neighbors.len()))
+      .collect::<HashMap<_, _>>();
+    let degree = |vertex: Id| degree_map[&vertex];
+
+    for neighbors in adjacency_map.values_mut() {
+      neighbors.sort_by_key(|&neighbor| (degree(neighbor), neighbor));
+    }
+
+    let mut visited = HashSet::new();
+    let mut result = Vec::with_capacity(self.vertices_map.len());
+
+    for (vertex, _) in self.all_vertices_ordered() {
+      if visited.contains(&vertex) {
+        continue;
+      }
+
+      let component = bfs_levels(&adjacency_map, vertex).concat();
+      let start = component.iter().copied().min_by_key(|&vertex| (degree(vertex), vertex)).unwrap();
+      let mut levels = bfs_levels(&adjacency_map, start);
+
+      loop {
+        let candidate = levels.last().unwrap().iter()
+          .copied()
+          .min_by_key(|&vertex| (degree(vertex), vertex))
+          .unwrap();
+        let candidate_levels = bfs_levels(&adjacency_map, candidate);
+
+        if candidate_levels.len() <= levels.len() {
+          break;
+        }
+
+        levels = candidate_levels;
+      }
+
+      // The levels already list the vertices in Cuthill-McKee order, since neighbors are added
+      // in the order of their parents and of the sorted adjacency lists
+      let mut order = levels.concat();
+
+      order.reverse();
+      visited.extend(order.iter().copied());
+      result.extend(order);
+    }
+
+    result
+  }
+
+  /// Returns the bandwidth of the adjacency matrix whose rows and columns are arranged in the
+  /// given order, i.e. the largest distance in `ordering` between two adjacent vertices. Edge
+  /// directions and self-loops are ignored, and so are vertices that are not part of `ordering`.
+  pub fn bandwidth(&self, ordering: &[Id]) -> usize {
+    let position_map = ordering.iter()
+      .enumerate()
+      .map(|(position, &vertex)| (vertex, position))
+      .collect::<HashMap<_, _>>();
+
+    self.edges_map.values()
+    .filter_map(|&(_, from, to)| Some((*position_map.get(&from)?, *position_map.get(&to)?)))
+    .map(|(i, j)| i.abs_diff(j))
+    .max()
+    .unwrap_or(0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reverse_cuthill_mckee_reduces_grid_bandwidth() {
+    let (width, height) = (10, 3);
+    let mut graph = FiniteGraph::<(usize, usize), ()>::new();
+    let ids = (0..height)
+      .flat_map(|y| (0..width).map(move |x| (x, y)))
+      .map(|position| (position, graph.insert_vertex(position)))
+      .collect::<HashMap<_, _>>();
+
+    for (&(x, y), &id) in &ids {
+      if x + 1 < width {
+        graph.insert_bi_edge(id, ids[&(x + 1, y)], ()).unwrap();
+      }
+
+      if y + 1 < height {
+        graph.insert_bi_edge(id, ids[&(x, y + 1)], ()).unwrap();
+      }
+    }
+
+    // An isolated vertex forms a component of its own
+    let isolated = graph.insert_vertex((0, height));
+    let insertion_order = graph.all_vertices_ordered().map(|(id, _)| id).collect::<Vec<_>>();
+    let order = graph.reverse_cuthill_mckee();
+    let mut sorted = order.clone();
+    sorted.sort();
+
+    assert_eq!(sorted, insertion_order);
+    assert_eq!(order.last(), Some(&isolated));
+    assert_eq!(graph.bandwidth(&insertion_order), width);
+    assert!(graph.bandwidth(&order) <= height + 1);
+    assert_eq!(FiniteGraph::<(), ()>::new().reverse_cuthill_mckee(), vec![]);
+  }
+}
@@ -0,0 +1,273 @@
+use std::collections::{HashMap, HashSet};
+use super::*;
+
+/// A point in the edit history of an [`OverlayGraph`](struct.OverlayGraph.html), created by
+/// [`snapshot`](struct.OverlayGraph.html#method.snapshot).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct OverlaySnapshot(usize);
+
+#[derive(Clone, Debug)]
+enum OverlayChange {
+  HideVertex(Id),
+  HideEdge(Id),
+  AddEdge
+}
+
+/// A view of a [`FiniteGraph`](struct.FiniteGraph.html) with vertices and edges hidden or added
+/// on top of it, without modifying or cloning the underlying graph.
+///
+/// All edits are recorded, so they can be undone cheaply by returning to a
+/// [`snapshot`](#method.snapshot), which makes the overlay suitable for backtracking searches.
+/// Added edges get ids that are not used by the underlying graph at the time the overlay is
+/// created.
+///
+/// # Example
+///
+/// ```
+/// use graph_iter::{FiniteGraph, Graph, OverlayGraph};
+///
+/// let mut graph = FiniteGraph::<(), ()>::new();
+/// let a = graph.insert_vertex(());
+/// let b = graph.insert_vertex(());
+/// let ab = graph.insert_edge(a, b, ()).unwrap();
+///
+/// let mut overlay = OverlayGraph::new(&graph);
+/// let snapshot = overlay.snapshot();
+///
+/// overlay.hide_edge(ab);
+/// overlay.add_edge(b, a, ());
+/// assert_eq!(overlay.neighbors(&a), vec![]);
+/// assert_eq!(overlay.neighbors(&b), vec![a]);
+///
+/// overlay.restore(snapshot);
+/// assert_eq!(overlay.neighbors(&a), vec![b]);
+/// assert_eq!(overlay.neighbors(&b), vec![]);
+/// ```
+#[derive(Clone)]
+pub struct OverlayGraph<'a, V, E> {
+  graph: &'a FiniteGraph<V, E>,
+  hidden_vertices: HashSet<Id>,
+  hidden_edges: HashSet<Id>,
+  added_edges: Vec<(E, Id, Id)>,
+  added_neighbors_map: HashMap<Id, Vec<(Id, Id)>>,
+  changes: Vec<OverlayChange>
+}
+
+impl<'a, V, E> OverlayGraph<'a, V, E> {
+  /// Creates an overlay without any edits on top of `graph`.
+  pub fn new(graph: &'a FiniteGraph<V, E>) -> OverlayGraph<'a, V, E> {
+    OverlayGraph {
+      graph,
+      hidden_vertices: HashSet::new(),
+      hidden_edges: HashSet::new(),
+      added_edges: vec![],
+      added_neighbors_map: HashMap::new(),
+      changes: vec![]
+    }
+  }
+
+  /// Returns the underlying graph.
+  pub fn base(&self) -> &'a FiniteGraph<V, E> {
+    self.graph
+  }
+
+  /// Returns the index of `edge` in `added_edges` if it is an added edge.
+  fn added_index(&self, edge: Id) -> Option<usize> {
+    edge.0.checked_sub(self.graph.id.0 + 1).filter(|&index| index < self.added_edges.len())
+  }
+
+  /// Returns `true` if the overlay contains the specified vertex, i.e. it exists in the
+  /// underlying graph and hasn't been hidden.
+  pub fn contains_vertex(&self, vertex: Id) -> bool {
+    self.graph.contains_vertex(vertex) && !self.hidden_vertices.contains(&vertex)
+  }
+
+  /// Returns `true` if the overlay contains the specified edge, i.e. it exists in the underlying
+  /// graph or has been added, it hasn't been hidden, and neither of its endpoints is hidden.
+  pub fn contains_edge(&self, edge: Id) -> bool {
+    self.endpoints(edge).is_some()
+  }
+
+  /// Returns a reference to the value of the specified edge, or `None` if the overlay doesn't
+  /// contain it.
+  pub fn get_edge(&self, edge: Id) -> Option<&E> {
+    self.endpoints(edge)?;
+
+    match self.added_index(edge) {
+      Some(index) => Some(&self.added_edges[index].0),
+      None => self.graph.get_edge(edge)
+    }
+  }
+
+  /// Returns the endpoints of the specified edge if the overlay contains it.
+  fn endpoints(&self, edge: Id) -> Option<(Id, Id)> {
+    if self.hidden_edges.contains(&edge) {
+      return None;
+    }
+
+    let (from, to) = match self.added_index(edge) {
+      Some(index) => (self.added_edges[index].1, self.added_edges[index].2),
+      None => self.graph.edges_map.get(&edge).map(|&(_, from, to)| (from, to))?
+    };
+
+    Some((from, to)).filter(|&(from, to)| self.contains_vertex(from) && self.contains_vertex(to))
+  }
+
+  /// Hides the specified vertex together with all of its edges. Returns `false` if the overlay
+  /// doesn't contain the vertex.
+  pub fn hide_vertex(&mut self, vertex: Id) -> bool {
+    if !self.contains_vertex(vertex) {
+      return false;
+    }
+
+    self.hidden_vertices.insert(vertex);
+    self.changes.push(OverlayChange::HideVertex(vertex));
+    true
+  }
+
+  /// Hides the specified edge. Bidirectional edges are hidden in both directions. Returns
+  /// `false` if the overlay doesn't contain the edge.
+  pub fn hide_edge(&mut self, edge: Id) -> bool {
+    if !self.contains_edge(edge) {
+      return false;
+    }
+
+    self.hidden_edges.insert(edge);
+    self.changes.push(OverlayChange::HideEdge(edge));
+    true
+  }
+
+  /// Adds a new edge from `from` to `to` and returns its id, or `None` if the overlay doesn't
+  /// contain both vertices.
+  pub fn add_edge(&mut self, from: Id, to: Id, value: E) -> Option<Id> {
+    if !self.contains_vertex(from) || !self.contains_vertex(to) {
+      return None;
+    }
+
+    let edge = Id(self.graph.id.0 + 1 + self.added_edges.len());
+
+    self.added_edges.push((value, from, to));
+    self.added_neighbors_map.entry(from).or_default().push((to, edge));
+    self.changes.push(OverlayChange::AddEdge);
+
+    Some(edge)
+  }
+
+  /// Returns a snapshot of the current edits, which can be returned to with
+  /// [`restore`](#method.restore).
+  pub fn snapshot(&self) -> OverlaySnapshot {
+    OverlaySnapshot(self.changes.len())
+  }
+
+  /// Undoes all edits made after `snapshot` was taken, in `O(k)` time for `k` edits. Snapshots
+  /// taken after `snapshot` become invalid and restoring them has no effect.
+  pub fn restore(&mut self, snapshot: OverlaySnapshot) {
+    while self.changes.len() > snapshot.0 {
+      match self.changes.pop().unwrap() {
+        OverlayChange::HideVertex(vertex) => {
+          self.hidden_vertices.remove(&vertex);
+        },
+        OverlayChange::HideEdge(edge) => {
+          self.hidden_edges.remove(&edge);
+        },
+        OverlayChange::AddEdge => {
+          let (_, from, _) = self.added_edges.pop().unwrap();
+          self.added_neighbors_map.get_mut(&from).unwrap().pop();
+        }
+      }
+    }
+  }
+
+  /// An iterator visiting all visible outgoing neighbors of `vertex` together with the edge
+  /// leading to them.
+  fn visible_neighbors(&self, vertex: Id) -> impl Iterator<Item = (Id, Id)> + '_ {
+    Some(vertex).filter(|&vertex| self.contains_vertex(vertex)).into_iter()
+    .flat_map(move |vertex| {
+      self.graph.neighbors_map.get(&vertex).into_iter().flatten()
+      .chain(self.added_neighbors_map.get(&vertex).into_iter().flatten())
+    })
+    .copied()
+    .filter(move |(neighbor, edge)| !self.hidden_edges.contains(edge) && !self.hidden_vertices.contains(neighbor))
+  }
+}
+
+impl<'a, V, E> Graph<Id> for OverlayGraph<'a, V, E> {
+  type NeighborsIterator = Vec<Id>;
+
+  fn neighbors(&self, vertex: &Id) -> Vec<Id> {
+    self.visible_neighbors(*vertex).map(|(neighbor, _)| neighbor).collect()
+  }
+}
+
+impl<'a, V, E: Edge> EdgedGraph<Id, E> for OverlayGraph<'a, V, E> {
+  type EdgesIterator = Vec<E>;
+
+  fn edges(&self, vertex: &Id, other: &Id) -> Vec<E> {
+    self.visible_neighbors(*vertex)
+    .filter(|(neighbor, _)| neighbor == other)
+    .filter_map(|(_, edge)| self.get_edge(edge).cloned())
+    .collect()
+  }
+
+  fn neighbors_with_edges<'s>(&'s self, vertex: &Id) -> impl Iterator<Item = (Id, E)> + use<'s, 'a, V, E> {
+    self.visible_neighbors(*vertex)
+    .filter_map(move |(neighbor, edge)| self.get_edge(edge).map(|value| (neighbor, value.clone())))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use vertex_traverser::VertexTraverser;
+
+  #[test]
+  fn overlay_edits_and_restores() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+    let e01 = graph.insert_edge(v[0], v[1], 1).unwrap();
+    let e12 = graph.insert_bi_edge(v[1], v[2], 1).unwrap();
+    graph.insert_edge(v[0], v[3], 5).unwrap();
+    graph.insert_edge(v[3], v[2], 5).unwrap();
+
+    let mut overlay = OverlayGraph::new(&graph);
+    let root = overlay.snapshot();
+
+    assert_eq!(overlay.dijkstra(&v[0]).construct_path(&v[2]), Some(vec![v[0], v[1], v[2]]));
+    assert!(overlay.hide_edge(e12));
+    assert!(!overlay.hide_edge(e12));
+    assert_eq!(overlay.neighbors(&v[2]), vec![]);
+    assert_eq!(overlay.dijkstra(&v[0]).construct_path(&v[2]), Some(vec![v[0], v[3], v[2]]));
+
+    let hidden_edge = overlay.snapshot();
+    let shortcut = overlay.add_edge(v[0], v[2], 3).unwrap();
+
+    assert!(!graph.contains_edge(shortcut));
+    assert_eq!(overlay.get_edge(shortcut), Some(&3));
+    assert_eq!(overlay.edges(&v[0], &v[2]), vec![3]);
+    assert_eq!(overlay.dijkstra(&v[0]).construct_path(&v[2]), Some(vec![v[0], v[2]]));
+
+    assert!(overlay.hide_vertex(v[2]));
+    assert!(!overlay.contains_edge(shortcut));
+    assert_eq!(overlay.neighbors(&v[0]), vec![v[1], v[3]]);
+    assert_eq!(overlay.add_edge(v[1], v[2], 1), None);
+    assert_eq!(overlay.bfs(&v[0]).iter().count(), 3);
+
+    overlay.restore(hidden_edge);
+
+    assert!(overlay.contains_vertex(v[2]));
+    assert!(!overlay.contains_edge(shortcut));
+    assert_eq!(overlay.dijkstra(&v[0]).construct_path(&v[2]), Some(vec![v[0], v[3], v[2]]));
+
+    let shortcut_again = overlay.add_edge(v[0], v[2], 1).unwrap();
+
+    assert_eq!(shortcut_again, shortcut);
+    assert!(overlay.hide_edge(e01));
+    assert_eq!(overlay.neighbors_with_edges(&v[0]).collect::<Vec<_>>(), vec![(v[3], 5), (v[2], 1)]);
+
+    overlay.restore(root);
+
+    assert_eq!(overlay.neighbors(&v[0]), graph.neighbors(&v[0]));
+    assert_eq!(overlay.neighbors(&v[2]), vec![v[1]]);
+    assert_eq!(overlay.dijkstra(&v[0]).construct_path(&v[2]), Some(vec![v[0], v[1], v[2]]));
+  }
+}
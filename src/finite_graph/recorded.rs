@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use super::*;
+
+type Adjacency = Vec<(Id, Id)>;
+type EdgeEntry<E> = (E, Id, Id);
+
+/// The previous state of all entries of a [`FiniteGraph`](struct.FiniteGraph.html) that are
+/// touched by a mutation. Applying a patch restores these entries and returns a patch with the
+/// state they had before, which is how both undo and redo work.
+#[derive(Clone)]
+struct Patch<V, E> {
+  id: Id,
+  vertices: Vec<(Id, Option<V>)>,
+  edges: Vec<(Id, Option<EdgeEntry<E>>)>,
+  neighbors: Vec<(Id, Option<Adjacency>)>,
+  reverse_neighbors: Vec<(Id, Option<Adjacency>)>
+}
+
+/// Replaces the values of the given keys, where `None` stands for a missing key, and returns
+/// the replaced values. Keys may repeat, so the entries are replaced in reverse order, which
+/// makes swapping the result back in restore the map.
+fn swap_entries<T>(map: &mut HashMap<Id, T>, entries: Vec<(Id, Option<T>)>) -> Vec<(Id, Option<T>)> {
+  entries.into_iter()
+  .rev()
+  .map(|(key, value)| {
+    let previous = match value {
+      Some(value) => map.insert(key, value),
+      None => map.remove(&key)
+    };
+
+    (key, previous)
+  })
+  .collect()
+}
+
+fn clone_entries<K: Copy + Eq + Hash, T: Clone>(map: &HashMap<K, T>, keys: &[K]) -> Vec<(K, Option<T>)> {
+  keys.iter().map(|key| (*key, map.get(key).cloned())).collect()
+}
+
+impl<V: Clone, E: Clone> Patch<V, E> {
+  /// Captures the current state of the given vertices, edges, and adjacency lists of
+  /// `adjacent` vertices.
+  fn capture(graph: &FiniteGraph<V, E>, vertices: &[Id], edges: &[Id], adjacent: &[Id]) -> Patch<V, E> {
+    Patch {
+      id: graph.id,
+      vertices: clone_entries(&graph.vertices_map, vertices),
+      edges: clone_entries(&graph.edges_map, edges),
+      neighbors: clone_entries(&graph.neighbors_map, adjacent),
+      reverse_neighbors: clone_entries(&graph.reverse_neighbors_map, adjacent)
+    }
+  }
+}
+
+impl<V, E> Patch<V, E> {
+  fn apply(self, graph: &mut FiniteGraph<V, E>) -> Patch<V, E> {
+    Patch {
+      id: std::mem::replace(&mut graph.id, self.id),
+      vertices: swap_entries(&mut graph.vertices_map, self.vertices),
+      edges: swap_entries(&mut graph.edges_map, self.edges),
+      neighbors: swap_entries(&mut graph.neighbors_map, self.neighbors),
+      reverse_neighbors: swap_entries(&mut graph.reverse_neighbors_map, self.reverse_neighbors)
+    }
+  }
+}
+
+/// A [`FiniteGraph`](struct.FiniteGraph.html) that records all of its mutations, so they can be
+/// undone and redone.
+///
+/// The graph can only be mutated through the methods of this wrapper, which take the place of
+/// `get_vertex_mut` and `get_edge_mut` with [`set_vertex`](#method.set_vertex) and
+/// [`set_edge`](#method.set_edge). Each mutation stores the previous state of the vertices,
+/// edges, and adjacency lists it touches, so removing a vertex takes memory proportional to its
+/// degree. Undoing restores the graph exactly, including the order of neighbors and the ids that
+/// will be assigned next.
+///
+/// # Example
+///
+/// ```
+/// use graph_iter::{FiniteGraph, RecordedGraph};
+///
+/// let mut graph = RecordedGraph::new(FiniteGraph::<&str, ()>::new());
+/// let a = graph.insert_vertex("a");
+///
+/// graph.set_vertex(a, "b");
+/// assert_eq!(graph.graph().get_vertex(a), Some(&"b"));
+///
+/// assert!(graph.undo());
+/// assert_eq!(graph.graph().get_vertex(a), Some(&"a"));
+///
+/// assert!(graph.redo());
+/// assert_eq!(graph.graph().get_vertex(a), Some(&"b"));
+/// ```
+#[derive(Clone)]
+pub struct RecordedGraph<V, E> {
+  graph: FiniteGraph<V, E>,
+  undo_stack: Vec<Patch<V, E>>,
+  redo_stack: Vec<Patch<V, E>>
+}
+
+impl<V: Clone, E: Clone> RecordedGraph<V, E> {
+  /// Starts recording the mutations of `graph` with an empty history.
+  pub fn new(graph: FiniteGraph<V, E>) -> RecordedGraph<V, E> {
+    RecordedGraph {
+      graph,
+      undo_stack: vec![],
+      redo_stack: vec![]
+    }
+  }
+
+  /// Returns the recorded graph.
+  pub fn graph(&self) -> &FiniteGraph<V, E> {
+    &self.graph
+  }
+
+  /// Stops recording and returns the graph.
+  pub fn into_inner(self) -> FiniteGraph<V, E> {
+    self.graph
+  }
+
+  /// Returns the number of mutations that can be undone.
+  pub fn history_len(&self) -> usize {
+    self.undo_stack.len()
+  }
+
+  /// Returns the number of undone mutations that can be redone.
+  pub fn redo_len(&self) -> usize {
+    self.redo_stack.len()
+  }
+
+  /// Keeps `patch` in the history if the mutation succeeded, which discards all undone
+  /// mutations.
+  fn record<T>(&mut self, patch: Patch<V, E>, result: Option<T>) -> Option<T> {
+    if result.is_some() {
+      self.undo_stack.push(patch);
+      self.redo_stack.clear();
+    }
+
+    result
+  }
+
+  /// Returns the vertices whose adjacency lists change when `edge` is inserted or removed.
+  fn endpoints(&self, edge: Id) -> Vec<Id> {
+    self.graph.edges_map.get(&edge)
+    .map(|&(_, from, to)| vec![from, to])
+    .unwrap_or_default()
+  }
+
+  /// See [`FiniteGraph::insert_vertex`](struct.FiniteGraph.html#method.insert_vertex).
+  pub fn insert_vertex(&mut self, value: V) -> Id {
+    let patch = Patch::capture(&self.graph, &[Id(self.graph.id.0 + 1)], &[], &[]);
+    let result = self.graph.insert_vertex(value);
+
+    self.record(patch, Some(result)).unwrap()
+  }
+
+  /// See [`FiniteGraph::remove_vertex`](struct.FiniteGraph.html#method.remove_vertex).
+  pub fn remove_vertex(&mut self, vertex: Id) -> Option<V> {
+    let (mut adjacent, mut edges): (Vec<_>, Vec<_>) = self.graph.undirected_neighbors(vertex).unzip();
+
+    adjacent.push(vertex);
+
+    for collection in &mut [&mut adjacent, &mut edges] {
+      collection.sort();
+      collection.dedup();
+    }
+
+    let patch = Patch::capture(&self.graph, &[vertex], &edges, &adjacent);
+    let result = self.graph.remove_vertex(vertex);
+
+    self.record(patch, result)
+  }
+
+  /// See [`FiniteGraph::insert_edge`](struct.FiniteGraph.html#method.insert_edge).
+  pub fn insert_edge(&mut self, from: Id, to: Id, value: E) -> Option<Id> {
+    let patch = Patch::capture(&self.graph, &[], &[Id(self.graph.id.0 + 1)], &[from, to]);
+    let result = self.graph.insert_edge(from, to, value);
+
+    self.record(patch, result)
+  }
+
+  /// See [`FiniteGraph::insert_bi_edge`](struct.FiniteGraph.html#method.insert_bi_edge).
+  pub fn insert_bi_edge(&mut self, from: Id, to: Id, value: E) -> Option<Id> {
+    let patch = Patch::capture(&self.graph, &[], &[Id(self.graph.id.0 + 1)], &[from, to]);
+    let result = self.graph.insert_bi_edge(from, to, value);
+
+    self.record(patch, result)
+  }
+
+  /// See [`FiniteGraph::remove_edge`](struct.FiniteGraph.html#method.remove_edge).
+  pub fn remove_edge(&mut self, edge: Id) -> Option<E> {
+    let patch = Patch::capture(&self.graph, &[], &[edge], &self.endpoints(edge));
+    let result = self.graph.remove_edge(edge);
+
+    self.record(patch, result)
+  }
+
+  /// Replaces the value of `vertex` and returns the previous value, or `None` if the vertex
+  /// doesn't exist.
+  pub fn set_vertex(&mut self, vertex: Id, value: V) -> Option<V> {
+    let patch = Patch::capture(&self.graph, &[vertex], &[], &[]);
+    let result = self.graph.get_vertex_mut(vertex).map(|current| std::mem::replace(current, value));
+
+    self.record(patch, result)
+  }
+
+  /// Replaces the value of `edge` and returns the previous value, or `None` if the edge
+  /// doesn't exist.
+  pub fn set_edge(&mut self, edge: Id, value: E) -> Option<E> {
+    let patch = Patch::capture(&self.graph, &[], &[edge], &[]);
+    let result = self.graph.get_edge_mut(edge).map(|current| std::mem::replace(current, value));
+
+    self.record(patch, result)
+  }
+
+  /// Undoes the last mutation that hasn't been undone yet. Returns `false` if there is none.
+  pub fn undo(&mut self) -> bool {
+    match self.undo_stack.pop() {
+      Some(patch) => {
+        self.redo_stack.push(patch.apply(&mut self.graph));
+        true
+      },
+      None => false
+    }
+  }
+
+  /// Redoes the last undone mutation. Returns `false` if there is none, which is also the case
+  /// after any mutation.
+  pub fn redo(&mut self) -> bool {
+    match self.redo_stack.pop() {
+      Some(patch) => {
+        self.undo_stack.push(patch.apply(&mut self.graph));
+        true
+      },
+      None => false
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_structurally_equal(graph: &FiniteGraph<u32, u32>, other: &FiniteGraph<u32, u32>) {
+    assert_eq!(graph.id, other.id);
+    assert_eq!(graph.vertices_map, other.vertices_map);
+    assert_eq!(graph.edges_map, other.edges_map);
+    assert_eq!(graph.neighbors_map, other.neighbors_map);
+    assert_eq!(graph.reverse_neighbors_map, other.reverse_neighbors_map);
+  }
+
+  #[test]
+  fn undo_all_random_mutations_restores_graph() {
+    let mut original = FiniteGraph::<u32, u32>::new();
+    let v = (0..5).map(|i| original.insert_vertex(i)).collect::<Vec<_>>();
+
+    original.insert_edge(v[0], v[1], 1).unwrap();
+    original.insert_bi_edge(v[1], v[2], 2).unwrap();
+    original.insert_edge(v[2], v[2], 3).unwrap();
+    original.insert_edge(v[3], v[0], 4).unwrap();
+
+    // A linear congruential generator keeps the operation sequence reproducible
+    let mut state = 12345u64;
+    let mut random = |bound: usize| {
+      state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+      (state >> 33) as usize % bound
+    };
+
+    for round in 0..20 {
+      let mut graph = RecordedGraph::new(original.clone());
+      let mut states = vec![graph.graph().clone()];
+
+      for step in 0..30 {
+        let vertices = graph.graph().all_vertices_ordered().map(|(id, _)| id).collect::<Vec<_>>();
+        let edges = graph.graph().all_edges_ordered().map(|(id, _)| id).collect::<Vec<_>>();
+        let value = (round * 100 + step) as u32;
+        let len = graph.history_len();
+
+        match random(7) {
+          0 => { graph.insert_vertex(value); },
+          1 if !vertices.is_empty() => { graph.remove_vertex(vertices[random(vertices.len())]); },
+          2 | 3 if !vertices.is_empty() => {
+            let (from, to) = (vertices[random(vertices.len())], vertices[random(vertices.len())]);
+
+            if random(2) == 0 {
+              graph.insert_edge(from, to, value);
+            } else {
+              graph.insert_bi_edge(from, to, value);
+            }
+          },
+          4 if !edges.is_empty() => { graph.remove_edge(edges[random(edges.len())]); },
+          5 if !vertices.is_empty() => { graph.set_vertex(vertices[random(vertices.len())], value); },
+          6 if !edges.is_empty() => { graph.set_edge(edges[random(edges.len())], value); },
+          _ => {}
+        }
+
+        if graph.history_len() > len {
+          states.push(graph.graph().clone());
+        }
+      }
+
+      assert_eq!(graph.history_len(), states.len() - 1);
+
+      while graph.undo() {
+        assert_structurally_equal(graph.graph(), &states[graph.history_len()]);
+      }
+
+      assert_structurally_equal(graph.graph(), &original);
+
+      while graph.redo() {
+        assert_structurally_equal(graph.graph(), &states[graph.history_len()]);
+      }
+
+      assert_eq!(graph.history_len(), states.len() - 1);
+    }
+  }
+
+  #[test]
+  fn failed_mutations_are_not_recorded() {
+    let mut graph = RecordedGraph::new(FiniteGraph::<u32, u32>::new());
+    let a = graph.insert_vertex(0);
+    let e = graph.insert_edge(a, a, 0).unwrap();
+
+    assert_eq!(graph.remove_vertex(e), None);
+    assert_eq!(graph.remove_edge(a), None);
+    assert_eq!(graph.set_vertex(e, 1), None);
+    assert_eq!(graph.insert_edge(a, e, 1), None);
+    assert_eq!(graph.history_len(), 2);
+
+    assert!(graph.undo());
+    assert_eq!(graph.redo_len(), 1);
+
+    graph.set_vertex(a, 1);
+
+    assert!(!graph.redo());
+    assert!(graph.undo());
+    assert!(graph.undo());
+    assert!(!graph.undo());
+    assert_eq!(graph.into_inner().len(), (0, 0));
+  }
+}
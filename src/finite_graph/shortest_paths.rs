@@ -0,0 +1,263 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::ops::Neg;
+use edge::WeightedEdge;
+use super::*;
+
+impl<V, E: WeightedEdge> FiniteGraph<V, E> {
+  /// Returns `true` if the graph contains a cycle whose edge weights sum up to a negative value.
+  ///
+  /// This uses the [Bellman-Ford algorithm](https://en.wikipedia.org/wiki/Bellman%E2%80%93Ford_algorithm)
+  /// and is useful to validate a graph with arbitrary weights before calling
+  /// [`dijkstra`](./trait.EdgedGraph.html#method.dijkstra) on it.
+  pub fn has_negative_cycle(&self) -> bool where E: Neg<Output = E> {
+    let mut distance_map = self.vertices_map.keys()
+      .map(|&vertex| (vertex, E::default()))
+      .collect::<HashMap<_, _>>();
+
+    let relax = |distance_map: &mut HashMap<Id, E>| {
+      let mut relaxed = false;
+
+      for (vertex, neighbors) in &self.neighbors_map {
+        for (neighbor, edge) in neighbors {
+          let new_distance = distance_map[vertex].clone() + self.edges_map[edge].0.clone();
+
+          if new_distance < distance_map[neighbor] {
+            distance_map.insert(*neighbor, new_distance);
+            relaxed = true;
+          }
+        }
+      }
+
+      relaxed
+    };
+
+    for _ in 1..self.vertices_map.len() {
+      if !relax(&mut distance_map) {
+        return false;
+      }
+    }
+
+    relax(&mut distance_map)
+  }
+
+  /// See [`EdgedGraph::shortest_cycle_through`](trait.EdgedGraph.html#method.shortest_cycle_through).
+  /// Since edges are known by their ids here, the way back may not use the first edge again,
+  /// so a bidirectional edge alone is not a cycle, like in [`girth`](#method.girth).
+  pub(crate) fn shortest_cycle_through_vertex(&self, vertex: Id) -> Option<(Vec<Id>, E)> {
+    let mut best = None::<(Vec<Id>, E)>;
+
+    for &(neighbor, first_edge) in self.neighbors_map.get(&vertex).into_iter().flatten() {
+      let first_cost = self.edges_map[&first_edge].0.clone();
+
+      if best.as_ref().is_some_and(|(_, cost)| &first_cost >= cost) {
+        continue;
+      } else if neighbor == vertex {
+        best = Some((vec![vertex, vertex], first_cost));
+        continue;
+      }
+
+      let mut cost_map = HashMap::new();
+      let mut parent_map = HashMap::new();
+      let mut heap = BinaryHeap::new();
+
+      cost_map.insert(neighbor, first_cost.clone());
+      heap.push((Reverse(first_cost), neighbor));
+
+      while let Some((Reverse(cost), current)) = heap.pop() {
+        if best.as_ref().is_some_and(|(_, best_cost)| &cost >= best_cost) {
+          break;
+        } else if cost_map[&current] < cost {
+          continue;
+        } else if current == vertex {
+          let mut path = vec![vertex];
+
+          while path.last() != Some(&neighbor) {
+            path.push(parent_map[path.last().unwrap()]);
+          }
+
+          path.push(vertex);
+          path.reverse();
+          best = Some((path, cost));
+          break;
+        }
+
+        for &(next, edge) in self.neighbors_map.get(&current).into_iter().flatten() {
+          if edge == first_edge {
+            continue;
+          }
+
+          let new_cost = cost.clone() + self.edges_map[&edge].0.clone();
+
+          if cost_map.get(&next).is_none_or(|old_cost| &new_cost < old_cost) {
+            cost_map.insert(next, new_cost.clone());
+            parent_map.insert(next, current);
+            heap.push((Reverse(new_cost), next));
+          }
+        }
+      }
+    }
+
+    best
+  }
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns the length of a shortest cycle of the graph ignoring edge directions, or `None`
+  /// if the graph has no cycles. A cycle may not use the same edge twice, so bidirectional edges
+  /// alone don't form cycles, whereas self-loops are cycles of length one and parallel edges,
+  /// including edges going in opposite directions, are cycles of length two.
+  ///
+  /// This runs a breadth-first search from every vertex, each of which stops as soon as it
+  /// can't find a shorter cycle anymore, so it takes `O(V * (V + E))` time in the worst case.
+  pub fn girth(&self) -> Option<usize> {
+    let mut girth = None::<usize>;
+
+    for &source in self.vertices_map.keys() {
+      let mut distance_map = HashMap::new();
+      let mut parent_edge_map = HashMap::<Id, Id>::new();
+      let mut queue = VecDeque::new();
+
+      distance_map.insert(source, 0);
+      queue.push_back(source);
+
+      while let Some(vertex) = queue.pop_front() {
+        let distance = distance_map[&vertex];
+
+        // Cycles found from here on have a length of at least `2 * distance + 1`
+        if girth.is_some_and(|girth| 2 * distance + 1 >= girth) {
+          break;
+        }
+
+        for (neighbor, edge) in self.undirected_neighbors(vertex) {
+          if parent_edge_map.get(&vertex) == Some(&edge) || parent_edge_map.get(&neighbor) == Some(&edge) {
+            continue;
+          }
+
+          if let Some(&neighbor_distance) = distance_map.get(&neighbor) {
+            // Self-loops appear twice, but both occurrences yield the same length
+            let length = distance + neighbor_distance + 1;
+
+            if girth.is_none_or(|girth| length < girth) {
+              girth = Some(length);
+            }
+          } else {
+            distance_map.insert(neighbor, distance + 1);
+            parent_edge_map.insert(neighbor, edge);
+            queue.push_back(neighbor);
+          }
+        }
+      }
+    }
+
+    girth
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn detect_negative_cycles() {
+    let mut graph = FiniteGraph::<(), i32>::new();
+
+    assert!(!graph.has_negative_cycle());
+
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+    let d = graph.insert_vertex(());
+
+    graph.insert_edge(a, b, 4).unwrap();
+    graph.insert_edge(b, c, -2).unwrap();
+    graph.insert_edge(c, d, 3).unwrap();
+    let e = graph.insert_edge(d, b, -1).unwrap();
+
+    assert!(!graph.has_negative_cycle());
+
+    graph.remove_edge(e);
+    graph.insert_edge(d, b, -2).unwrap();
+
+    assert!(graph.has_negative_cycle());
+  }
+
+  #[test]
+  fn negative_bi_edges_are_negative_cycles() {
+    let mut graph = FiniteGraph::<(), i32>::new();
+
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+
+    graph.insert_bi_edge(a, b, -1).unwrap();
+
+    assert!(graph.has_negative_cycle());
+  }
+
+  #[test]
+  fn girth_conventions() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..5).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    assert_eq!(graph.girth(), None);
+
+    for pair in v.windows(2) {
+      graph.insert_bi_edge(pair[0], pair[1], ()).unwrap();
+    }
+
+    assert_eq!(graph.girth(), None);
+
+    graph.insert_edge(v[4], v[0], ()).unwrap();
+
+    assert_eq!(graph.girth(), Some(5));
+
+    graph.insert_edge(v[3], v[1], ()).unwrap();
+
+    assert_eq!(graph.girth(), Some(3));
+
+    let e = graph.insert_edge(v[2], v[1], ()).unwrap();
+
+    assert_eq!(graph.girth(), Some(2));
+
+    graph.remove_edge(e);
+    graph.insert_edge(v[4], v[4], ()).unwrap();
+
+    assert_eq!(graph.girth(), Some(1));
+  }
+
+  #[test]
+  fn shortest_cycle_through_vertex() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], 1).unwrap();
+    graph.insert_edge(v[1], v[2], 1).unwrap();
+    graph.insert_edge(v[2], v[0], 5).unwrap();
+    graph.insert_edge(v[1], v[3], 1).unwrap();
+    graph.insert_edge(v[3], v[0], 1).unwrap();
+
+    assert_eq!(graph.shortest_cycle_through(&v[0]), Some((vec![v[0], v[1], v[3], v[0]], 3)));
+    assert_eq!(graph.shortest_cycle_through(&v[2]), Some((vec![v[2], v[0], v[1], v[2]], 7)));
+
+    graph.insert_edge(v[0], v[0], 2).unwrap();
+
+    assert_eq!(graph.shortest_cycle_through(&v[0]), Some((vec![v[0], v[0]], 2)));
+
+    // Going out and back along the same bidirectional edge is not a cycle, but opposite edges
+    // are, like in `girth`
+    let e = graph.insert_bi_edge(v[2], v[3], 1).unwrap();
+
+    assert_eq!(graph.shortest_cycle_through(&v[2]), Some((vec![v[2], v[3], v[0], v[1], v[2]], 4)));
+
+    let opposite = graph.insert_edge(v[3], v[2], 1).unwrap();
+
+    assert_eq!(graph.shortest_cycle_through(&v[2]), Some((vec![v[2], v[3], v[2]], 2)));
+
+    graph.remove_edge(e);
+    graph.remove_edge(opposite);
+    let isolated = graph.insert_vertex(());
+    graph.insert_edge(v[0], isolated, 1).unwrap();
+
+    assert_eq!(graph.shortest_cycle_through(&isolated), None);
+  }
+}
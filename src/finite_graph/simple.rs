@@ -0,0 +1,291 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::error::Error;
+use graph::ReversibleGraph;
+use super::*;
+
+/// The error returned by [`SimpleUngraph`](struct.SimpleUngraph.html) if an edge would violate
+/// its invariants.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SimpleGraphError {
+  /// The vertex doesn't exist.
+  MissingVertex(Id),
+  /// The edge would start and end at the vertex.
+  SelfLoop(Id),
+  /// The vertices are already connected by an edge.
+  ParallelEdge(Id, Id),
+  /// The edge of the converted graph is not bidirectional.
+  DirectedEdge(Id)
+}
+
+impl fmt::Display for SimpleGraphError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SimpleGraphError::MissingVertex(vertex) => write!(f, "vertex {:?} doesn't exist", vertex),
+      SimpleGraphError::SelfLoop(vertex) => write!(f, "self-loop at vertex {:?}", vertex),
+      SimpleGraphError::ParallelEdge(vertex, other) => {
+        write!(f, "vertices {:?} and {:?} are already connected", vertex, other)
+      },
+      SimpleGraphError::DirectedEdge(edge) => write!(f, "edge {:?} is not bidirectional", edge)
+    }
+  }
+}
+
+impl Error for SimpleGraphError {}
+
+/// A wrapper around a [`FiniteGraph`](struct.FiniteGraph.html) that is a simple undirected
+/// graph, i.e. all edges are bidirectional, there are no self-loops, and every pair of
+/// vertices is connected by at most one edge.
+///
+/// It implements [`Graph`](trait.Graph.html), [`ReversibleGraph`](trait.ReversibleGraph.html),
+/// and [`EdgedGraph`](trait.EdgedGraph.html), so all traversers work on it, and
+/// [`graph`](#method.graph) gives access to the algorithms of `FiniteGraph`.
+///
+/// # Example
+///
+/// ```
+/// # use graph_iter::{Graph, SimpleGraphError, SimpleUngraph};
+/// let mut graph = SimpleUngraph::new();
+/// let a = graph.insert_vertex('a');
+/// let b = graph.insert_vertex('b');
+///
+/// graph.insert_edge(a, b, 1).unwrap();
+///
+/// assert_eq!(graph.insert_edge(b, a, 2), Err(SimpleGraphError::ParallelEdge(b, a)));
+/// assert_eq!(graph.edges_between(b, a), Some(&1));
+/// assert_eq!(graph.neighbors(&b), vec![a]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimpleUngraph<V, E> {
+  graph: FiniteGraph<V, E>
+}
+
+impl<V, E> Default for SimpleUngraph<V, E> {
+  fn default() -> SimpleUngraph<V, E> {
+    SimpleUngraph::new()
+  }
+}
+
+impl<V, E> SimpleUngraph<V, E> {
+  /// Creates an empty graph.
+  pub fn new() -> SimpleUngraph<V, E> {
+    SimpleUngraph {
+      graph: FiniteGraph::new()
+    }
+  }
+
+  /// Returns the graph, where every edge is a bidirectional edge.
+  pub fn graph(&self) -> &FiniteGraph<V, E> {
+    &self.graph
+  }
+
+  /// Stops enforcing the invariants and returns the graph.
+  pub fn into_inner(self) -> FiniteGraph<V, E> {
+    self.graph
+  }
+
+  /// See [`FiniteGraph::len`](struct.FiniteGraph.html#method.len).
+  pub fn len(&self) -> (usize, usize) {
+    self.graph.len()
+  }
+
+  /// Returns `true` if the graph has no vertices.
+  pub fn is_empty(&self) -> bool {
+    self.graph.vertices_map.is_empty()
+  }
+
+  /// See [`FiniteGraph::get_vertex`](struct.FiniteGraph.html#method.get_vertex).
+  pub fn get_vertex(&self, vertex: Id) -> Option<&V> {
+    self.graph.get_vertex(vertex)
+  }
+
+  /// See [`FiniteGraph::get_vertex_mut`](struct.FiniteGraph.html#method.get_vertex_mut).
+  pub fn get_vertex_mut(&mut self, vertex: Id) -> Option<&mut V> {
+    self.graph.get_vertex_mut(vertex)
+  }
+
+  /// Returns the number of neighbors of `vertex`.
+  pub fn degree(&self, vertex: Id) -> usize {
+    self.graph.out_degree(vertex)
+  }
+
+  /// Returns the edge between `vertex` and `other` if there is one.
+  pub fn edge_between(&self, vertex: Id, other: Id) -> Option<Id> {
+    self.graph.neighbors_map.get(&vertex)?.iter()
+    .find(|&&(neighbor, _)| neighbor == other)
+    .map(|&(_, edge)| edge)
+  }
+
+  /// Returns the value of the edge between `vertex` and `other` if there is one.
+  pub fn edges_between(&self, vertex: Id, other: Id) -> Option<&E> {
+    self.edge_between(vertex, other).and_then(|edge| self.graph.get_edge(edge))
+  }
+
+  /// Returns a mutable reference to the value of the edge between `vertex` and `other` if
+  /// there is one.
+  pub fn edges_between_mut(&mut self, vertex: Id, other: Id) -> Option<&mut E> {
+    let edge = self.edge_between(vertex, other)?;
+
+    self.graph.get_edge_mut(edge)
+  }
+
+  /// See [`FiniteGraph::insert_vertex`](struct.FiniteGraph.html#method.insert_vertex).
+  pub fn insert_vertex(&mut self, value: V) -> Id {
+    self.graph.insert_vertex(value)
+  }
+
+  /// See [`FiniteGraph::remove_vertex`](struct.FiniteGraph.html#method.remove_vertex).
+  pub fn remove_vertex(&mut self, vertex: Id) -> Option<V> {
+    self.graph.remove_vertex(vertex)
+  }
+
+  /// Connects `vertex` and `other` by an edge and returns it, unless one of the vertices
+  /// doesn't exist, they are the same vertex, or they are already connected.
+  pub fn insert_edge(&mut self, vertex: Id, other: Id, value: E) -> Result<Id, SimpleGraphError> {
+    for &vertex in &[vertex, other] {
+      if !self.graph.contains_vertex(vertex) {
+        return Err(SimpleGraphError::MissingVertex(vertex));
+      }
+    }
+
+    if vertex == other {
+      return Err(SimpleGraphError::SelfLoop(vertex));
+    }
+
+    if self.edge_between(vertex, other).is_some() {
+      return Err(SimpleGraphError::ParallelEdge(vertex, other));
+    }
+
+    Ok(self.graph.insert_bi_edge(vertex, other, value).unwrap())
+  }
+
+  /// Removes the edge between `vertex` and `other` and returns its value if there is one.
+  pub fn remove_edge(&mut self, vertex: Id, other: Id) -> Option<E> {
+    let edge = self.edge_between(vertex, other)?;
+
+    self.graph.remove_edge(edge)
+  }
+}
+
+/// Checks that all edges are bidirectional, there are no self-loops and no parallel edges.
+/// Edges are checked in ascending order of their ids, and the first violation is returned.
+impl<V, E> TryFrom<FiniteGraph<V, E>> for SimpleUngraph<V, E> {
+  type Error = SimpleGraphError;
+
+  fn try_from(graph: FiniteGraph<V, E>) -> Result<SimpleUngraph<V, E>, SimpleGraphError> {
+    let mut edges = graph.edges_map.iter()
+      .map(|(&edge, &(_, from, to))| (edge, from, to))
+      .collect::<Vec<_>>();
+    let mut pairs = HashSet::new();
+
+    edges.sort_unstable();
+
+    for (edge, from, to) in edges {
+      if from == to {
+        return Err(SimpleGraphError::SelfLoop(from));
+      } else if !graph.is_bi_edge(edge, from, to) {
+        return Err(SimpleGraphError::DirectedEdge(edge));
+      } else if !pairs.insert((from.min(to), from.max(to))) {
+        return Err(SimpleGraphError::ParallelEdge(from, to));
+      }
+    }
+
+    Ok(SimpleUngraph { graph })
+  }
+}
+
+impl<V, E> From<SimpleUngraph<V, E>> for FiniteGraph<V, E> {
+  fn from(graph: SimpleUngraph<V, E>) -> FiniteGraph<V, E> {
+    graph.into_inner()
+  }
+}
+
+impl<V, E> Graph<Id> for SimpleUngraph<V, E> {
+  type NeighborsIterator = Vec<Id>;
+
+  fn neighbors(&self, vertex: &Id) -> Vec<Id> {
+    self.graph.neighbors(vertex)
+  }
+}
+
+/// Every edge goes in both directions, so the reverse neighbors are the neighbors.
+impl<V, E> ReversibleGraph<Id> for SimpleUngraph<V, E> {
+  type ReverseNeighborsIterator = Vec<Id>;
+
+  fn reverse_neighbors(&self, vertex: &Id) -> Vec<Id> {
+    self.graph.neighbors(vertex)
+  }
+}
+
+impl<V, E: Edge> EdgedGraph<Id, E> for SimpleUngraph<V, E> {
+  type EdgesIterator = Option<E>;
+
+  fn edges(&self, vertex: &Id, other: &Id) -> Option<E> {
+    self.edges_between(*vertex, *other).cloned()
+  }
+
+  fn neighbors_with_edges<'a>(&'a self, vertex: &Id) -> impl Iterator<Item = (Id, E)> + use<'a, V, E> {
+    self.graph.neighbors_with_edges(vertex)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use vertex_traverser::VertexTraverser;
+
+  #[test]
+  fn simple_ungraph_rejects_invalid_edges() {
+    let mut graph = SimpleUngraph::new();
+    let v = (0..4).map(|i| graph.insert_vertex(i)).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], 5).unwrap();
+    graph.insert_edge(v[1], v[2], 1).unwrap();
+    graph.insert_edge(v[2], v[0], 1).unwrap();
+
+    assert_eq!(graph.insert_edge(v[1], v[0], 1), Err(SimpleGraphError::ParallelEdge(v[1], v[0])));
+    assert_eq!(graph.insert_edge(v[3], v[3], 1), Err(SimpleGraphError::SelfLoop(v[3])));
+    assert_eq!(graph.insert_edge(v[3], Id(100), 1), Err(SimpleGraphError::MissingVertex(Id(100))));
+    assert_eq!(graph.len(), (4, 3));
+    assert_eq!(graph.neighbors(&v[0]), vec![v[1], v[2]]);
+    assert_eq!(graph.reverse_neighbors(&v[0]), vec![v[1], v[2]]);
+    assert_eq!(graph.edges(&v[1], &v[0]), Some(5));
+    assert_eq!(graph.dijkstra(&v[0]).construct_path(&v[1]), Some(vec![v[0], v[2], v[1]]));
+    assert_eq!(graph.weighted_path_between(&v[1], &v[0]), Some(vec![v[1], v[2], v[0]]));
+
+    *graph.edges_between_mut(v[2], v[1]).unwrap() = 10;
+
+    assert_eq!(graph.remove_edge(v[0], v[1]), Some(5));
+    assert_eq!(graph.remove_edge(v[0], v[1]), None);
+    assert_eq!(graph.edges_between(v[1], v[2]), Some(&10));
+    assert_eq!(graph.degree(v[2]), 2);
+    assert_eq!(graph.bfs(&v[3]).iter().count(), 1);
+  }
+
+  #[test]
+  fn simple_ungraph_validates_conversions() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..3).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_bi_edge(v[0], v[1], ()).unwrap();
+    graph.insert_bi_edge(v[2], v[1], ()).unwrap();
+
+    let simple = SimpleUngraph::try_from(graph.clone()).unwrap();
+
+    assert_eq!(FiniteGraph::from(simple), graph);
+
+    let mut directed = graph.clone();
+    let edge = directed.insert_edge(v[0], v[2], ()).unwrap();
+
+    assert_eq!(SimpleUngraph::try_from(directed).err(), Some(SimpleGraphError::DirectedEdge(edge)));
+
+    let mut parallel = graph.clone();
+    parallel.insert_bi_edge(v[1], v[2], ()).unwrap();
+
+    assert_eq!(SimpleUngraph::try_from(parallel).err(), Some(SimpleGraphError::ParallelEdge(v[1], v[2])));
+
+    graph.insert_bi_edge(v[2], v[2], ()).unwrap();
+
+    assert_eq!(SimpleUngraph::try_from(graph).err(), Some(SimpleGraphError::SelfLoop(v[2])));
+  }
+}
@@ -0,0 +1,568 @@
+use std::collections::{BTreeMap, HashMap};
+use super::*;
+use super::components::connected_components;
+
+/// A sparse matrix in compressed sparse row format, as used by most sparse linear algebra
+/// libraries. The entries of row `i` are stored at the positions
+/// `row_offsets[i]..row_offsets[i + 1]` of `column_indices` and `values` in ascending order of
+/// their columns. Entries that are not stored are zero.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Csr<T> {
+  pub num_rows: usize,
+  pub num_cols: usize,
+  /// The start of every row in `column_indices` and `values`, followed by the number of
+  /// stored entries, so it has `num_rows + 1` elements.
+  pub row_offsets: Vec<usize>,
+  pub column_indices: Vec<usize>,
+  pub values: Vec<T>
+}
+
+impl<T: Clone + Default> Csr<T> {
+  /// Builds a square matrix from rows of `(column, value)` entries in ascending column order.
+  fn from_rows(rows: Vec<Vec<(usize, T)>>) -> Csr<T> {
+    let mut matrix = Csr {
+      num_rows: rows.len(),
+      num_cols: rows.len(),
+      row_offsets: vec![0],
+      column_indices: vec![],
+      values: vec![]
+    };
+
+    for row in rows {
+      for (column, value) in row {
+        matrix.column_indices.push(column);
+        matrix.values.push(value);
+      }
+
+      matrix.row_offsets.push(matrix.values.len());
+    }
+
+    matrix
+  }
+
+  /// Returns the matrix as a vector of rows, where entries that are not stored are
+  /// `T::default()`.
+  pub fn to_dense(&self) -> Vec<Vec<T>> {
+    let mut result = vec![vec![T::default(); self.num_cols]; self.num_rows];
+
+    for (i, row) in result.iter_mut().enumerate() {
+      for index in self.row_offsets[i]..self.row_offsets[i + 1] {
+        row[self.column_indices[index]] = self.values[index].clone();
+      }
+    }
+
+    result
+  }
+}
+
+/// Turns the adjacency matrix `A` into the Laplacian matrix `D - A`.
+fn laplacian_of(mut matrix: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+  for (i, row) in matrix.iter_mut().enumerate() {
+    let degree = row.iter().sum::<f64>();
+
+    for entry in row.iter_mut() {
+      *entry = -*entry;
+    }
+
+    row[i] += degree;
+  }
+
+  matrix
+}
+
+fn dot(vector: &[f64], other: &[f64]) -> f64 {
+  vector.iter().zip(other).map(|(value, other)| value * other).sum()
+}
+
+/// Removes the component along the constant vector and scales the vector to unit length.
+fn deflate_and_normalize(vector: &mut [f64]) {
+  let mean = vector.iter().sum::<f64>() / vector.len() as f64;
+
+  vector.iter_mut().for_each(|value| *value -= mean);
+
+  let norm = dot(vector, vector).sqrt();
+
+  if norm > 0.0 {
+    vector.iter_mut().for_each(|value| *value /= norm);
+  }
+}
+
+impl Csr<f64> {
+  /// Returns the stored diagonal entries, where missing ones are zero.
+  fn diagonal(&self) -> Vec<f64> {
+    (0..self.num_rows)
+    .map(|i| {
+      (self.row_offsets[i]..self.row_offsets[i + 1])
+      .find(|&index| self.column_indices[index] == i)
+      .map_or(0.0, |index| self.values[index])
+    })
+    .collect()
+  }
+
+  /// Returns the matrix-vector product in `O(num_rows + num_entries)` time.
+  fn multiply(&self, vector: &[f64]) -> Vec<f64> {
+    (0..self.num_rows)
+    .map(|i| {
+      (self.row_offsets[i]..self.row_offsets[i + 1])
+      .map(|index| self.values[index] * vector[self.column_indices[index]])
+      .sum()
+    })
+    .collect()
+  }
+
+  /// Solves `M y = x` for a symmetric matrix `M` that is positive definite on the vectors
+  /// orthogonal to the constant vector, like the Laplacian of a connected graph, with the
+  /// conjugate gradient method. The right-hand side should be orthogonal to the constant vector,
+  /// and so is the solution, since the residuals are kept in that subspace.
+  fn solve_deflated(&self, vector: &[f64]) -> Vec<f64> {
+    let n = vector.len();
+    let mut solution = vec![0.0; n];
+    let mut residual = vector.to_vec();
+    let mut direction = residual.clone();
+    let mut residual_norm = dot(&residual, &residual);
+    let tolerance = 1e-24 * residual_norm;
+
+    for _ in 0..10 * n {
+      if residual_norm <= tolerance {
+        break;
+      }
+
+      let product = self.multiply(&direction);
+      let curvature = dot(&direction, &product);
+
+      if curvature <= 0.0 {
+        break;
+      }
+
+      let step = residual_norm / curvature;
+
+      for i in 0..n {
+        solution[i] += step * direction[i];
+        residual[i] -= step * product[i];
+      }
+
+      let mean = residual.iter().sum::<f64>() / n as f64;
+
+      residual.iter_mut().for_each(|value| *value -= mean);
+
+      let next_norm = dot(&residual, &residual);
+
+      for i in 0..n {
+        direction[i] = residual[i] + next_norm / residual_norm * direction[i];
+      }
+
+      residual_norm = next_norm;
+    }
+
+    solution
+  }
+}
+
+/// The result of [`FiniteGraph::fiedler_vector`](struct.FiniteGraph.html#method.fiedler_vector).
+#[derive(Clone, Debug, PartialEq)]
+pub struct FiedlerVector {
+  /// The vertices in ascending order of their ids.
+  pub order: Vec<Id>,
+  /// The second smallest eigenvalue of the Laplacian matrix.
+  pub algebraic_connectivity: f64,
+  /// A unit eigenvector of the algebraic connectivity with entries in the order of `order`.
+  pub vector: Vec<f64>,
+  /// Whether the iteration met its tolerance before hitting the iteration cap, so `false`
+  /// means the values are only approximate.
+  pub converged: bool
+}
+
+impl<V, E: Clone + Into<f64>> FiniteGraph<V, E> {
+  /// Returns the vertices in ascending order of their ids and the weighted adjacency matrix,
+  /// where parallel edges add up.
+  fn adjacency_matrix(&self) -> (Vec<Id>, Vec<Vec<f64>>) {
+    let order = self.all_vertices_ordered().map(|(vertex, _)| vertex).collect::<Vec<_>>();
+    let index_map = order.iter()
+      .enumerate()
+      .map(|(index, &vertex)| (vertex, index))
+      .collect::<HashMap<_, _>>();
+    let mut matrix = vec![vec![0.0; order.len()]; order.len()];
+
+    for (i, vertex) in order.iter().enumerate() {
+      for &(neighbor, edge) in self.neighbors_map.get(vertex).into_iter().flatten() {
+        matrix[i][index_map[&neighbor]] += self.edges_map[&edge].0.clone().into();
+      }
+    }
+
+    (order, matrix)
+  }
+
+  /// Returns the vertices in ascending order of their ids and the Laplacian matrix
+  /// `L = D - A` with rows and columns in that order, where the degree matrix `D` holds the
+  /// out-degrees on its diagonal and `A` is the adjacency matrix. Edge weights are converted
+  /// into `f64`, so `L[i][i]` is the weight sum of the outgoing edges of vertex `i` and
+  /// `L[i][j]` is the negated weight sum of the edges from `i` to `j`. Bidirectional edges
+  /// count in both directions, so the matrix is symmetric if all edges are bidirectional.
+  /// Self-loops cancel out on the diagonal.
+  pub fn laplacian_matrix(&self) -> (Vec<Id>, Vec<Vec<f64>>) {
+    let (order, adjacency) = self.adjacency_matrix();
+
+    (order, laplacian_of(adjacency))
+  }
+
+  /// Returns the vertices like [`laplacian_matrix`](#method.laplacian_matrix) and the
+  /// normalized Laplacian matrix `D^(-1/2) L D^(-1/2)`, i.e. `L[i][j]` is divided by the square
+  /// root of the product of the degrees of `i` and `j`. Rows and columns of vertices without
+  /// outgoing edges are zero.
+  pub fn normalized_laplacian_matrix(&self) -> (Vec<Id>, Vec<Vec<f64>>) {
+    let (order, adjacency) = self.adjacency_matrix();
+    let scales = adjacency.iter()
+      .map(|row| row.iter().sum::<f64>())
+      .map(|degree| if degree > 0.0 { 1.0 / degree.sqrt() } else { 0.0 })
+      .collect::<Vec<_>>();
+    let mut matrix = laplacian_of(adjacency);
+
+    for (i, row) in matrix.iter_mut().enumerate() {
+      for (j, entry) in row.iter_mut().enumerate() {
+        *entry *= scales[i] * scales[j];
+      }
+    }
+
+    (order, matrix)
+  }
+
+  /// Returns the vertices like [`laplacian_matrix`](#method.laplacian_matrix) and the
+  /// signless Laplacian matrix `D + A`.
+  pub fn signless_laplacian_matrix(&self) -> (Vec<Id>, Vec<Vec<f64>>) {
+    let (order, mut matrix) = self.adjacency_matrix();
+
+    for (i, row) in matrix.iter_mut().enumerate() {
+      row[i] += row.iter().sum::<f64>();
+    }
+
+    (order, matrix)
+  }
+
+  /// Returns the vertices in ascending order of their ids and the rows of the weighted
+  /// adjacency matrix of the undirected view, i.e. every edge connects its endpoints in both
+  /// directions once, even if it is bidirectional. Parallel edges add up and self-loops are
+  /// ignored.
+  fn undirected_adjacency_rows(&self) -> (Vec<Id>, Vec<BTreeMap<usize, f64>>) {
+    let order = self.all_vertices_ordered().map(|(vertex, _)| vertex).collect::<Vec<_>>();
+    let index_map = order.iter()
+      .enumerate()
+      .map(|(index, &vertex)| (vertex, index))
+      .collect::<HashMap<_, _>>();
+    let mut rows = vec![BTreeMap::new(); order.len()];
+
+    for (data, from, to) in self.edges_map.values() {
+      if from != to {
+        let (i, j) = (index_map[from], index_map[to]);
+        let weight: f64 = data.clone().into();
+
+        *rows[i].entry(j).or_insert(0.0) += weight;
+        *rows[j].entry(i).or_insert(0.0) += weight;
+      }
+    }
+
+    (order, rows)
+  }
+
+  /// Returns the weighted degrees of the vertices, ignoring edge directions, in ascending order
+  /// of their ids like [`laplacian`](#method.laplacian). Every edge counts once for each of its
+  /// endpoints, even if it is bidirectional, and self-loops are ignored.
+  pub fn degree_vector(&self) -> Vec<f64> {
+    self.undirected_adjacency_rows().1.iter()
+    .map(|row| row.values().sum())
+    .collect()
+  }
+
+  /// Returns the Laplacian matrix `D - A` of the undirected view of the graph as a sparse
+  /// matrix with rows and columns in ascending order of the vertex ids, which is symmetric.
+  /// Every edge connects its endpoints in both directions once, even if it is bidirectional,
+  /// so `A[i][j]` is the weight sum of all edges between `i` and `j`, and `D` holds the
+  /// [degrees](#method.degree_vector) on its diagonal. Self-loops are ignored, since they
+  /// would cancel out on the diagonal anyway, and rows of isolated vertices are empty.
+  pub fn laplacian(&self) -> Csr<f64> {
+    let rows = self.undirected_adjacency_rows().1.into_iter()
+      .enumerate()
+      .map(|(i, row)| {
+        let degree = row.values().sum::<f64>();
+        let mut entries = row.into_iter().map(|(j, weight)| (j, -weight)).collect::<BTreeMap<_, _>>();
+
+        if degree != 0.0 {
+          entries.insert(i, degree);
+        }
+
+        entries.into_iter().collect()
+      })
+      .collect();
+
+    Csr::from_rows(rows)
+  }
+
+  /// Returns the normalized symmetric Laplacian matrix `D^(-1/2) L D^(-1/2)` of the undirected
+  /// view of the graph, where `L` is the [Laplacian](#method.laplacian), so the diagonal
+  /// entries are `1` and `L[i][j]` is divided by the square root of the product of the degrees
+  /// of `i` and `j`. Rows of isolated vertices are empty.
+  pub fn normalized_laplacian(&self) -> Csr<f64> {
+    let degrees = self.degree_vector();
+    let mut matrix = self.laplacian();
+
+    for i in 0..matrix.num_rows {
+      for index in matrix.row_offsets[i]..matrix.row_offsets[i + 1] {
+        matrix.values[index] /= (degrees[i] * degrees[matrix.column_indices[index]]).sqrt();
+      }
+    }
+
+    matrix
+  }
+
+  /// Returns the algebraic connectivity, i.e. the second smallest eigenvalue of the
+  /// [Laplacian](#method.laplacian), and a corresponding unit eigenvector, the Fiedler vector.
+  /// Edge directions are ignored and edge weights are converted into `f64`, which should be
+  /// non-negative.
+  ///
+  /// For disconnected graphs, the eigenvalue is zero and the vector separates the component of
+  /// the smallest vertex from the others. Otherwise, it is computed by inverse iteration in the
+  /// subspace orthogonal to the constant vector, where every step solves `L y = x` with the
+  /// conjugate gradient method in `O(k (V + E))` time for `k` gradient steps. The iteration
+  /// stops once the eigenvalue residual is small relative to the largest degree, or after a
+  /// fixed number of steps, in which case
+  /// [`converged`](struct.FiedlerVector.html#structfield.converged) is `false`. Vertices with
+  /// positive and negative entries in the Fiedler vector form a good bipartition of the graph.
+  pub fn fiedler_vector(&self) -> FiedlerVector {
+    let order = self.all_vertices_ordered().map(|(vertex, _)| vertex).collect::<Vec<_>>();
+    let laplacian = self.laplacian();
+    let n = order.len();
+
+    if n < 2 {
+      return FiedlerVector {order, algebraic_connectivity: 0.0, vector: vec![1.0; n], converged: true};
+    }
+
+    let index_map = order.iter()
+      .enumerate()
+      .map(|(index, &vertex)| (vertex, index))
+      .collect::<HashMap<_, _>>();
+    let components = connected_components(&order, |vertex| {
+      let i = index_map[&vertex];
+
+      (laplacian.row_offsets[i]..laplacian.row_offsets[i + 1])
+      .filter(|&index| laplacian.values[index] < 0.0)
+      .map(|index| order[laplacian.column_indices[index]])
+      .collect::<Vec<_>>()
+    });
+
+    if components.len() > 1 {
+      let mut vector = vec![0.0; n];
+
+      for vertex in &components[0] {
+        vector[index_map[vertex]] = 1.0;
+      }
+
+      deflate_and_normalize(&mut vector);
+
+      return FiedlerVector {order, algebraic_connectivity: 0.0, vector, converged: true};
+    }
+
+    // By the Gershgorin circle theorem, no eigenvalue exceeds twice the largest degree
+    let shift = 2.0 * laplacian.diagonal().into_iter().fold(0.0, f64::max);
+    let mut vector = (0..n).map(|i| (i as f64 + 1.0).sqrt()).collect::<Vec<_>>();
+    let mut eigenvalue = 0.0;
+    let mut converged = false;
+
+    deflate_and_normalize(&mut vector);
+
+    for _ in 0..100 {
+      let product = laplacian.multiply(&vector);
+
+      eigenvalue = dot(&vector, &product);
+
+      let residual = product.iter()
+        .zip(&vector)
+        .map(|(product, value)| (product - eigenvalue * value).powi(2))
+        .sum::<f64>()
+        .sqrt();
+
+      if residual <= 1e-10 * shift {
+        converged = true;
+        break;
+      }
+
+      vector = laplacian.solve_deflated(&vector);
+      deflate_and_normalize(&mut vector);
+    }
+
+    FiedlerVector {order, algebraic_connectivity: eigenvalue.max(0.0), vector, converged}
+  }
+
+  /// Returns the algebraic connectivity as computed by
+  /// [`fiedler_vector`](#method.fiedler_vector), which is positive if and only if the graph
+  /// is connected, ignoring edge directions. It is zero for graphs with less than two vertices.
+  pub fn algebraic_connectivity(&self) -> f64 {
+    self.fiedler_vector().algebraic_connectivity
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn laplacian_matrices_of_weighted_graph() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_bi_edge(v[0], v[1], 2).unwrap();
+    graph.insert_edge(v[1], v[2], 1).unwrap();
+    graph.insert_edge(v[1], v[2], 3).unwrap();
+    graph.insert_edge(v[2], v[2], 5).unwrap();
+
+    let (order, laplacian) = graph.laplacian_matrix();
+
+    assert_eq!(order, v);
+    assert_eq!(laplacian, vec![
+      vec![2.0, -2.0, 0.0, 0.0],
+      vec![-2.0, 6.0, -4.0, 0.0],
+      vec![0.0, 0.0, 0.0, 0.0],
+      vec![0.0, 0.0, 0.0, 0.0]
+    ]);
+
+    let (_, signless) = graph.signless_laplacian_matrix();
+
+    assert_eq!(signless, vec![
+      vec![2.0, 2.0, 0.0, 0.0],
+      vec![2.0, 6.0, 4.0, 0.0],
+      vec![0.0, 0.0, 10.0, 0.0],
+      vec![0.0, 0.0, 0.0, 0.0]
+    ]);
+
+    let (_, normalized) = graph.normalized_laplacian_matrix();
+    let expected = [
+      [1.0, -2.0 / 12f64.sqrt(), 0.0, 0.0],
+      [-2.0 / 12f64.sqrt(), 1.0, -4.0 / 30f64.sqrt(), 0.0],
+      [0.0, 0.0, 0.0, 0.0],
+      [0.0, 0.0, 0.0, 0.0]
+    ];
+
+    for (row, expected_row) in normalized.iter().zip(&expected) {
+      for (entry, expected_entry) in row.iter().zip(expected_row) {
+        assert!((entry - expected_entry).abs() < 1e-9, "{:?}", normalized);
+      }
+    }
+  }
+
+  #[test]
+  fn algebraic_connectivity_of_paths_cycles_and_complete_graphs() {
+    use std::f64::consts::PI;
+
+    let n = 10;
+    let mut path = FiniteGraph::<(), f64>::new();
+    let v = (0..n).map(|_| path.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..n - 1 {
+      path.insert_edge(v[i], v[i + 1], 1.0).unwrap();
+    }
+
+    let FiedlerVector {order, algebraic_connectivity, vector: fiedler, converged} = path.fiedler_vector();
+
+    assert_eq!(order, v);
+    assert!(converged);
+    assert!((algebraic_connectivity - 2.0 * (1.0 - (PI / n as f64).cos())).abs() < 1e-6);
+
+    // The Fiedler vector of a path is monotonic, so it splits the path in the middle
+    assert!(fiedler.windows(2).all(|pair| pair[0] < pair[1]) || fiedler.windows(2).all(|pair| pair[0] > pair[1]));
+
+    let mut cycle = path.clone();
+
+    cycle.insert_bi_edge(v[n - 1], v[0], 1.0).unwrap();
+
+    assert!((cycle.algebraic_connectivity() - 2.0 * (1.0 - (2.0 * PI / n as f64).cos())).abs() < 1e-6);
+
+    let mut complete = FiniteGraph::<(), f64>::new();
+    let v = (0..5).map(|_| complete.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..5 {
+      for j in i + 1..5 {
+        complete.insert_edge(v[j], v[i], 1.0).unwrap();
+      }
+    }
+
+    assert!((complete.algebraic_connectivity() - 5.0).abs() < 1e-6);
+
+    complete.insert_vertex(());
+
+    assert!(complete.algebraic_connectivity() < 1e-6);
+    assert_eq!(FiniteGraph::<(), f64>::new().algebraic_connectivity(), 0.0);
+  }
+
+  #[test]
+  fn sparse_laplacians_of_undirected_view() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    // a <-> b with weight 2, b -> c twice with weights 1 and 3, c <- a, a self-loop at c,
+    // and d is isolated
+    graph.insert_bi_edge(v[0], v[1], 2).unwrap();
+    graph.insert_edge(v[1], v[2], 1).unwrap();
+    graph.insert_edge(v[1], v[2], 3).unwrap();
+    graph.insert_edge(v[2], v[0], 4).unwrap();
+    graph.insert_edge(v[2], v[2], 5).unwrap();
+
+    let laplacian = graph.laplacian();
+
+    assert_eq!(graph.degree_vector(), vec![6.0, 6.0, 8.0, 0.0]);
+    assert_eq!(laplacian.row_offsets, vec![0, 3, 6, 9, 9]);
+    assert_eq!(laplacian.column_indices, vec![0, 1, 2, 0, 1, 2, 0, 1, 2]);
+    assert_eq!(laplacian.to_dense(), vec![
+      vec![6.0, -2.0, -4.0, 0.0],
+      vec![-2.0, 6.0, -4.0, 0.0],
+      vec![-4.0, -4.0, 8.0, 0.0],
+      vec![0.0, 0.0, 0.0, 0.0]
+    ]);
+
+    let normalized = graph.normalized_laplacian();
+    let expected = [
+      [1.0, -2.0 / 6.0, -4.0 / 48f64.sqrt(), 0.0],
+      [-2.0 / 6.0, 1.0, -4.0 / 48f64.sqrt(), 0.0],
+      [-4.0 / 48f64.sqrt(), -4.0 / 48f64.sqrt(), 1.0, 0.0],
+      [0.0, 0.0, 0.0, 0.0]
+    ];
+
+    assert_eq!(normalized.row_offsets, laplacian.row_offsets);
+
+    for (row, expected_row) in normalized.to_dense().iter().zip(&expected) {
+      for (entry, expected_entry) in row.iter().zip(expected_row) {
+        assert!((entry - expected_entry).abs() < 1e-9, "{:?}", normalized);
+      }
+    }
+
+    assert_eq!(FiniteGraph::<(), u32>::new().laplacian().row_offsets, vec![0]);
+  }
+
+  #[test]
+  fn fiedler_vector_of_long_path_and_disconnected_graph() {
+    use std::f64::consts::PI;
+
+    let n = 800;
+    let mut path = FiniteGraph::<(), f64>::new();
+    let v = (0..n).map(|_| path.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..n - 1 {
+      path.insert_edge(v[i], v[i + 1], 1.0).unwrap();
+    }
+
+    let fiedler = path.fiedler_vector();
+    let expected = 2.0 * (1.0 - (PI / n as f64).cos());
+
+    assert!(fiedler.converged);
+    assert!((fiedler.algebraic_connectivity - expected).abs() < 1e-6 * expected);
+
+    let mut graph = FiniteGraph::<(), f64>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], 1.0).unwrap();
+    graph.insert_edge(v[2], v[3], 1.0).unwrap();
+
+    let fiedler = graph.fiedler_vector();
+
+    assert_eq!(fiedler.algebraic_connectivity, 0.0);
+    assert!(fiedler.converged);
+    assert!(fiedler.vector.iter().zip([1.0, 1.0, -1.0, -1.0]).all(|(value, sign)| (value - sign / 2.0).abs() < 1e-12));
+  }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use super::*;
+
+/// Structural invariants of a graph as computed by
+/// [`FiniteGraph::summary`](struct.FiniteGraph.html#method.summary).
+///
+/// The `Display` implementation prints a compact one-line report, while `Debug` shows all fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphSummary {
+  /// The number of vertices.
+  pub vertex_count: usize,
+  /// The number of edges, where bidirectional edges are counted once.
+  pub edge_count: usize,
+  /// The number of bidirectional edges.
+  pub bi_edge_count: usize,
+  /// The number of edges that start and end at the same vertex.
+  pub self_loop_count: usize,
+  /// The number of pairs of distinct edges with the same endpoints and the same direction.
+  /// Bidirectional edges only pair up with other bidirectional edges.
+  pub parallel_edge_pair_count: usize,
+  /// The smallest in-degree plus out-degree of all vertices, or `0` if there are no vertices.
+  pub min_degree: usize,
+  /// The largest in-degree plus out-degree of all vertices, or `0` if there are no vertices.
+  pub max_degree: usize,
+  /// The average in-degree plus out-degree of all vertices, or `0` if there are no vertices.
+  pub mean_degree: f64,
+  /// The number of connected components when ignoring edge directions.
+  pub weak_component_count: usize,
+  /// Whether the graph has no directed cycles. Bidirectional edges and self-loops are cycles.
+  pub is_dag: bool
+}
+
+impl fmt::Display for GraphSummary {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} vertices, {} edges ({} bi, {} loops, {} parallel pairs), degree {}..{} (mean {:.2}), \
+      {} weak components, {}",
+      self.vertex_count,
+      self.edge_count,
+      self.bi_edge_count,
+      self.self_loop_count,
+      self.parallel_edge_pair_count,
+      self.min_degree,
+      self.max_degree,
+      self.mean_degree,
+      self.weak_component_count,
+      if self.is_dag { "acyclic" } else { "cyclic" }
+    )
+  }
+}
+
+/// Returns the representative of the set containing `vertex` and compresses the path to it.
+fn find_root(parents: &mut HashMap<Id, Id>, vertex: Id) -> Id {
+  let mut root = vertex;
+
+  while parents[&root] != root {
+    root = parents[&root];
+  }
+
+  let mut current = vertex;
+
+  while current != root {
+    current = parents.insert(current, root).unwrap();
+  }
+
+  root
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Computes a [`GraphSummary`](struct.GraphSummary.html) of the graph for quick inspection.
+  ///
+  /// All edge based counts and the weakly connected components are gathered in a single pass
+  /// over the edges. Whether the graph is a DAG is determined by
+  /// [`topological_generations`](#method.topological_generations).
+  pub fn summary(&self) -> GraphSummary {
+    let mut parents = self.vertices_map.keys()
+      .map(|&vertex| (vertex, vertex))
+      .collect::<HashMap<_, _>>();
+    let mut weak_component_count = self.vertices_map.len();
+    let mut endpoint_counts = HashMap::new();
+    let mut bi_edge_count = 0;
+    let mut self_loop_count = 0;
+
+    for (&edge, &(_, from, to)) in &self.edges_map {
+      let bidirectional = self.is_bi_edge(edge, from, to);
+      let key = if bidirectional { (from.min(to), from.max(to), true) } else { (from, to, false) };
+
+      *endpoint_counts.entry(key).or_insert(0) += 1;
+
+      if bidirectional {
+        bi_edge_count += 1;
+      }
+
+      if from == to {
+        self_loop_count += 1;
+      }
+
+      let (from_root, to_root) = (find_root(&mut parents, from), find_root(&mut parents, to));
+
+      if from_root != to_root {
+        parents.insert(from_root, to_root);
+        weak_component_count -= 1;
+      }
+    }
+
+    let degrees = self.vertices_map.keys()
+      .map(|&vertex| self.out_degree(vertex) + self.in_degree(vertex))
+      .collect::<Vec<_>>();
+
+    GraphSummary {
+      vertex_count: self.vertices_map.len(),
+      edge_count: self.edges_map.len(),
+      bi_edge_count,
+      self_loop_count,
+      parallel_edge_pair_count: endpoint_counts.values().map(|&count| count * (count - 1) / 2).sum(),
+      min_degree: degrees.iter().copied().min().unwrap_or(0),
+      max_degree: degrees.iter().copied().max().unwrap_or(0),
+      mean_degree: if degrees.is_empty() {
+        0.0
+      } else {
+        degrees.iter().sum::<usize>() as f64 / degrees.len() as f64
+      },
+      weak_component_count,
+      is_dag: self.topological_generations().is_some()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn summary_of_empty_graph() {
+    let graph = FiniteGraph::<(), ()>::new();
+    let summary = graph.summary();
+
+    assert_eq!(summary.vertex_count, 0);
+    assert_eq!(summary.weak_component_count, 0);
+    assert_eq!(summary.mean_degree, 0.0);
+    assert!(summary.is_dag);
+  }
+
+  #[test]
+  fn summary_counts_structural_features() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+    let d = graph.insert_vertex(());
+    graph.insert_vertex(());
+
+    graph.insert_edge(a, b, 1).unwrap();
+    graph.insert_edge(a, b, 2).unwrap();
+    graph.insert_edge(a, b, 3).unwrap();
+    graph.insert_edge(b, a, 4).unwrap();
+    graph.insert_bi_edge(b, c, 5).unwrap();
+    graph.insert_bi_edge(c, b, 6).unwrap();
+    graph.insert_edge(d, d, 7).unwrap();
+
+    let summary = graph.summary();
+
+    assert_eq!(summary, GraphSummary {
+      vertex_count: 5,
+      edge_count: 7,
+      bi_edge_count: 2,
+      self_loop_count: 1,
+      parallel_edge_pair_count: 4,
+      min_degree: 0,
+      max_degree: 8,
+      mean_degree: 3.6,
+      weak_component_count: 3,
+      is_dag: false
+    });
+    assert_eq!(
+      summary.to_string(),
+      "5 vertices, 7 edges (2 bi, 1 loops, 4 parallel pairs), degree 0..8 (mean 3.60), \
+      3 weak components, cyclic"
+    );
+
+    let mut dag = FiniteGraph::<(), ()>::new();
+    let a = dag.insert_vertex(());
+    let b = dag.insert_vertex(());
+
+    dag.insert_edge(a, b, ()).unwrap();
+
+    assert!(dag.summary().is_dag);
+  }
+}
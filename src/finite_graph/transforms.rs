@@ -0,0 +1,496 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Mul;
+use edge::WeightedEdge;
+use vertex_traverser::VertexTraverser;
+use super::*;
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns a graph with clones of all vertices under their current ids, but without any
+  /// edges. Edges inserted into the new graph get ids that are not used by this graph.
+  pub(crate) fn clone_vertices<F>(&self) -> FiniteGraph<V, F> where V: Clone {
+    FiniteGraph {
+      id: self.id,
+      vertices_map: self.vertices_map.clone(),
+      edges_map: HashMap::new(),
+      neighbors_map: HashMap::new(),
+      reverse_neighbors_map: HashMap::new()
+    }
+  }
+
+  /// Returns the `k`-th power of the graph, which has the same vertices with the same ids and
+  /// an edge from `u` to `v` whenever `v` can be reached from `u` using at most `k` edges,
+  /// `u != v`.
+  ///
+  /// If both `u` and `v` can reach each other within `k` edges, they are connected by a single
+  /// bidirectional edge, otherwise by a directed edge. The power contains no self-loops or
+  /// parallel edges, so the first power is the simple graph underlying this graph.
+  pub fn graph_power(&self, k: usize) -> FiniteGraph<V, ()> where V: Clone {
+    let mut result = self.clone_vertices();
+
+    if k == 0 {
+      return result;
+    }
+
+    let reachable = self.vertices_map.keys()
+      .map(|&vertex| {
+        let within = self.bfs(&vertex).iter_with_depth()
+          .take_while(|&(_, depth)| depth <= k)
+          .map(|(other, _)| other)
+          .filter(|&other| other != vertex)
+          .collect::<HashSet<_>>();
+
+        (vertex, within)
+      })
+      .collect::<HashMap<_, _>>();
+
+    let mut vertices = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    vertices.sort();
+
+    for &vertex in &vertices {
+      let mut targets = reachable[&vertex].iter().copied().collect::<Vec<_>>();
+      targets.sort();
+
+      for other in targets {
+        if !reachable[&other].contains(&vertex) {
+          result.insert_edge(vertex, other, ());
+        } else if vertex < other {
+          result.insert_bi_edge(vertex, other, ());
+        }
+      }
+    }
+
+    result
+  }
+
+  /// Returns the square of the graph, i.e. its [second power](#method.graph_power).
+  pub fn graph_square(&self) -> FiniteGraph<V, ()> where V: Clone {
+    self.graph_power(2)
+  }
+
+  /// Returns the vertices that can be reached from `vertex` using at least one edge, excluding
+  /// `vertex` itself.
+  fn reachable_set(&self, vertex: Id) -> HashSet<Id> {
+    self.bfs(&vertex).iter().skip(1).collect()
+  }
+
+  /// Returns the transitive closure of the graph, which has the same vertices with the same ids
+  /// and a directed edge from `u` to `v` whenever `v` can be reached from `u`, `u != v`. The
+  /// closure contains no self-loops, bidirectional edges or parallel edges, so it has at most
+  /// `V * (V - 1)` edges.
+  ///
+  /// This runs a breadth-first search from every vertex, which takes `O(V * (V + E))` time.
+  pub fn transitive_closure(&self) -> FiniteGraph<V, ()> where V: Clone {
+    let mut result = self.clone_vertices();
+    let mut vertices = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    vertices.sort();
+
+    for &vertex in &vertices {
+      let mut reachable = self.reachable_set(vertex).into_iter().collect::<Vec<_>>();
+      reachable.sort();
+
+      for other in reachable {
+        result.insert_edge(vertex, other, ());
+      }
+    }
+
+    result
+  }
+
+  /// Returns `true` if the graph is transitively closed, i.e. if the graph has an edge from `u`
+  /// to `v` whenever `v` can be reached from `u`, `u != v`. Parallel edges, self-loops and
+  /// bidirectional edges are allowed.
+  pub fn is_transitively_closed(&self) -> bool {
+    self.vertices_map.keys().all(|&vertex| {
+      let neighbors = self.neighbors(&vertex).into_iter().collect::<HashSet<_>>();
+
+      self.reachable_set(vertex).iter().all(|other| neighbors.contains(other))
+    })
+  }
+
+  /// Connects the vertices of `left` whose edges share a vertex outside of `left`, where each
+  /// shared vertex contributes the product of the incidence weights of both vertices to it.
+  fn projection<W>(&self, left: &[Id], incidence: impl Fn(&[&E]) -> W) -> FiniteGraph<V, W>
+  where V: Clone, W: WeightedEdge + Mul<Output = W> {
+    let left_set = left.iter().copied()
+      .filter(|vertex| self.contains_vertex(*vertex))
+      .collect::<HashSet<_>>();
+    let mut result = FiniteGraph {
+      id: self.id,
+      vertices_map: left_set.iter().map(|&vertex| (vertex, self.vertices_map[&vertex].clone())).collect(),
+      edges_map: HashMap::new(),
+      neighbors_map: HashMap::new(),
+      reverse_neighbors_map: HashMap::new()
+    };
+    let mut pair_weights = HashMap::<(Id, Id), W>::new();
+
+    for &right in self.vertices_map.keys().filter(|vertex| !left_set.contains(vertex)) {
+      let mut edge_ids = self.undirected_neighbors(right)
+        .filter(|(neighbor, _)| left_set.contains(neighbor))
+        .collect::<Vec<_>>();
+
+      // Bidirectional edges appear twice
+      edge_ids.sort();
+      edge_ids.dedup();
+
+      let mut incidences = Vec::<(Id, W)>::new();
+
+      for group in edge_ids.chunk_by(|(vertex, _), (other, _)| vertex == other) {
+        let edges = group.iter().map(|(_, edge)| &self.edges_map[edge].0).collect::<Vec<_>>();
+        incidences.push((group[0].0, incidence(&edges)));
+      }
+
+      for (i, (vertex, weight)) in incidences.iter().enumerate() {
+        for (other, other_weight) in &incidences[i + 1..] {
+          let sum = pair_weights.entry((*vertex, *other)).or_default();
+          *sum = sum.clone() + weight.clone() * other_weight.clone();
+        }
+      }
+    }
+
+    let mut pairs = pair_weights.into_iter().collect::<Vec<_>>();
+    pairs.sort_by_key(|&(pair, _)| pair);
+
+    for ((vertex, other), weight) in pairs {
+      result.insert_bi_edge(vertex, other, weight);
+    }
+
+    result
+  }
+
+  /// Returns the projection of a bipartite graph onto the vertices of `left`, which keep their
+  /// ids. Two vertices of `left` are connected by a bidirectional edge if they have common
+  /// neighbors outside of `left`, and its weight is the number of such common neighbors.
+  /// Edge directions, parallel edges and edges between vertices of `left` are ignored. The
+  /// projection onto the other side is obtained by passing the remaining vertices.
+  ///
+  /// This takes `O(sum of d(r)^2)` time, where `d(r)` is the number of neighbors of a vertex
+  /// `r` outside of `left` in `left`.
+  pub fn bipartite_projection(&self, left: &[Id]) -> FiniteGraph<V, usize> where V: Clone {
+    self.projection(left, |_| 1)
+  }
+
+  /// Like [`bipartite_projection`](#method.bipartite_projection), but each common neighbor `r`
+  /// of `u` and `v` contributes `w(u, r) * w(v, r)` to the weight of their edge instead of one,
+  /// where `w(u, r)` is the weight sum of all edges between `u` and `r`.
+  pub fn bipartite_projection_weighted(&self, left: &[Id]) -> FiniteGraph<V, E>
+  where V: Clone, E: WeightedEdge + Mul<Output = E> {
+    self.projection(left, |edges| {
+      edges.iter().fold(E::default(), |sum, &edge| sum + edge.clone())
+    })
+  }
+
+  /// Replaces every vertex value with the result of `f` applied to it and merges vertices whose
+  /// new values are equal. Returns the number of merges performed, i.e. the number of removed
+  /// vertices.
+  ///
+  /// Each group of colliding vertices is merged into the vertex with the smallest id like in
+  /// [`contract_vertices`](#method.contract_vertices), so edges between colliding vertices are
+  /// removed and all other edges keep their ids, including parallel edges created by merging.
+  /// The value of the remaining vertex is obtained by folding the new values of the group in
+  /// ascending id order with `merge`.
+  pub fn relabel(&mut self, mut f: impl FnMut(&V) -> V, mut merge: impl FnMut(V, V) -> V) -> usize
+  where V: Hash + Eq {
+    let mut ids = self.vertices_map.keys().copied().collect::<Vec<_>>();
+    ids.sort();
+
+    let new_values = ids.iter()
+      .map(|id| f(&self.vertices_map[id]))
+      .collect::<Vec<_>>();
+    // Maps each position to the position of the first vertex with the same new value
+    let group_heads = {
+      let mut first_positions = HashMap::new();
+
+      new_values.iter().enumerate()
+      .map(|(position, value)| *first_positions.entry(value).or_insert(position))
+      .collect::<Vec<_>>()
+    };
+
+    let mut merged_values = new_values.into_iter().map(Some).collect::<Vec<_>>();
+    let mut merge_count = 0;
+
+    for position in 0..ids.len() {
+      let head = group_heads[position];
+
+      if head == position {
+        continue;
+      }
+
+      let value = merged_values[position].take().unwrap();
+      let head_value = merged_values[head].take().unwrap();
+
+      merged_values[head] = Some(merge(head_value, value));
+      self.contract_vertices(ids[head], ids[position]);
+      merge_count += 1;
+    }
+
+    for (id, value) in ids.into_iter().zip(merged_values) {
+      if let Some(value) = value {
+        self.vertices_map.insert(id, value);
+      }
+    }
+
+    merge_count
+  }
+
+  /// Inserts a reverse edge with a clone of the weight for every directed edge from `u` to `v`
+  /// if there is no edge from `v` to `u`, and returns the number of inserted edges. See
+  /// [`symmetrize_with`](#method.symmetrize_with).
+  pub fn symmetrize(&mut self) -> usize where E: Clone {
+    self.symmetrize_with(E::clone)
+  }
+
+  /// Inserts a reverse edge with the weight given by `reverse_weight_fn` for every directed edge
+  /// from `u` to `v` if there is no edge from `v` to `u`, and returns the number of inserted
+  /// edges.
+  ///
+  /// Bidirectional edges and self-loops are already symmetric. Whether a reverse edge is missing
+  /// is decided before any edges are inserted, so parallel edges from `u` to `v` each get a
+  /// reverse edge. Reverse edges are inserted as directed edges in ascending order of the ids
+  /// of the original edges.
+  pub fn symmetrize_with<F: Fn(&E) -> E>(&mut self, reverse_weight_fn: F) -> usize {
+    let missing = self.all_edges_ordered()
+      .map(|(edge, _)| (edge, self.edges_map[&edge].1, self.edges_map[&edge].2))
+      .filter(|&(edge, from, to)| from != to && !self.is_bi_edge(edge, from, to))
+      .filter(|&(_, from, to)| self.neighbors_map.get(&to).into_iter().flatten().all(|&(neighbor, _)| neighbor != from))
+      .collect::<Vec<_>>();
+
+    for &(edge, from, to) in &missing {
+      let weight = reverse_weight_fn(&self.edges_map[&edge].0);
+      self.insert_edge(to, from, weight);
+    }
+
+    missing.len()
+  }
+
+  /// Returns `true` if `vertex` has an edge to itself.
+  pub fn has_self_loop(&self, vertex: Id) -> bool {
+    self.neighbors_map.get(&vertex).into_iter().flatten().any(|&(neighbor, _)| neighbor == vertex)
+  }
+
+  /// Inserts a self-loop at `vertex` and returns its id, or `None` if the vertex doesn't exist
+  /// or already has a self-loop.
+  pub fn add_self_loop(&mut self, vertex: Id, weight: E) -> Option<Id> {
+    if self.has_self_loop(vertex) {
+      return None;
+    }
+
+    self.insert_edge(vertex, vertex, weight)
+  }
+
+  /// Inserts a self-loop with a clone of `weight` at every vertex that doesn't have one yet, in
+  /// ascending order of the vertex ids, and returns the number of inserted self-loops.
+  pub fn add_self_loops_all(&mut self, weight: E) -> usize where E: Clone {
+    let vertices = self.all_vertices_ordered()
+      .map(|(vertex, _)| vertex)
+      .filter(|&vertex| !self.has_self_loop(vertex))
+      .collect::<Vec<_>>();
+
+    for &vertex in &vertices {
+      self.insert_edge(vertex, vertex, weight.clone());
+    }
+
+    vertices.len()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn edge_set<V>(graph: &FiniteGraph<V, ()>) -> Vec<(Id, Id)> {
+    let mut edges = graph.into_iter().map(|(from, to, _)| (from, to)).collect::<Vec<_>>();
+    edges.sort();
+    edges
+  }
+
+  #[test]
+  fn graph_power_of_a_path() {
+    let mut graph = FiniteGraph::<usize, u32>::new();
+    let path = (0..5).map(|i| graph.insert_vertex(i)).collect::<Vec<_>>();
+
+    for pair in path.windows(2) {
+      graph.insert_bi_edge(pair[0], pair[1], 7).unwrap();
+    }
+
+    graph.insert_edge(path[0], path[1], 3).unwrap();
+    graph.insert_edge(path[2], path[2], 3).unwrap();
+
+    assert_eq!(graph.graph_power(0).len(), (5, 0));
+
+    let first = graph.graph_power(1);
+
+    assert_eq!(first.len(), (5, 4));
+    assert_eq!(first.get_vertex(path[3]), Some(&3));
+
+    let square = graph.graph_square();
+
+    assert_eq!(square.len(), (5, 7));
+    assert!(square.neighbors(&path[0]).contains(&path[2]));
+    assert!(square.neighbors(&path[2]).contains(&path[0]));
+    assert!(!square.neighbors(&path[0]).contains(&path[3]));
+    assert_eq!(graph.graph_power(4).len(), (5, 10));
+  }
+
+  #[test]
+  fn transitive_closure_of_path_and_cycle() {
+    let mut graph = FiniteGraph::<usize, u32>::new();
+    let v = (0..4).map(|i| graph.insert_vertex(i)).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], 1).unwrap();
+    graph.insert_edge(v[1], v[2], 1).unwrap();
+    graph.insert_edge(v[1], v[2], 2).unwrap();
+
+    assert!(!graph.is_transitively_closed());
+
+    let closure = graph.transitive_closure();
+
+    assert_eq!(edge_set(&closure), vec![(v[0], v[1]), (v[0], v[2]), (v[1], v[2])]);
+    assert!(closure.is_transitively_closed());
+    assert_eq!(closure.get_vertex(v[3]), Some(&3));
+
+    graph.insert_bi_edge(v[2], v[3], 3).unwrap();
+    graph.insert_edge(v[3], v[0], 4).unwrap();
+
+    let closure = graph.transitive_closure();
+
+    assert_eq!(closure.len(), (4, 12));
+    assert!(closure.is_transitively_closed());
+    assert!(FiniteGraph::<(), ()>::new().is_transitively_closed());
+  }
+
+  #[test]
+  fn bipartite_projections_count_common_neighbors() {
+    let mut graph = FiniteGraph::<&str, u32>::new();
+    let users = ["alice", "bob", "carol"].iter()
+      .map(|&user| graph.insert_vertex(user))
+      .collect::<Vec<_>>();
+    let items = ["book", "lamp", "mug"].iter()
+      .map(|&item| graph.insert_vertex(item))
+      .collect::<Vec<_>>();
+
+    for &(user, item, count) in &[(0, 0, 1), (0, 1, 2), (1, 0, 3), (1, 1, 1), (2, 2, 1)] {
+      graph.insert_edge(users[user], items[item], count).unwrap();
+    }
+
+    graph.insert_bi_edge(users[0], items[1], 1).unwrap();
+    graph.insert_edge(users[0], users[1], 1).unwrap();
+
+    let projection = graph.bipartite_projection(&users);
+
+    assert_eq!(projection.len(), (3, 1));
+    assert_eq!(projection.get_vertex(users[2]), Some(&"carol"));
+    assert_eq!(projection.neighbors(&users[0]), vec![users[1]]);
+    assert_eq!(projection.edges(&users[1], &users[0]), vec![2]);
+
+    let projection = graph.bipartite_projection(&items);
+
+    assert_eq!(projection.len(), (3, 1));
+    assert_eq!(projection.edges(&items[0], &items[1]), vec![2]);
+
+    let projection = graph.bipartite_projection_weighted(&users);
+
+    assert_eq!(projection.edges(&users[0], &users[1]), vec![6]);
+  }
+
+  #[test]
+  fn relabel_merges_colliding_vertices() {
+    let mut graph = FiniteGraph::<String, u32>::new();
+    let a = graph.insert_vertex("a.com".to_string());
+    let b = graph.insert_vertex("B.com".to_string());
+    let c = graph.insert_vertex("A.com".to_string());
+    let d = graph.insert_vertex("b.COM".to_string());
+
+    graph.insert_edge(a, b, 1).unwrap();
+    let e2 = graph.insert_edge(c, d, 2).unwrap();
+    graph.insert_edge(a, c, 3).unwrap();
+    let e4 = graph.insert_bi_edge(d, c, 4).unwrap();
+
+    let merges = graph.relabel(|value| value.to_lowercase(), |value, other| format!("{}|{}", value, other));
+
+    assert_eq!(merges, 2);
+    assert_eq!(graph.len(), (2, 3));
+    assert_eq!(graph.get_vertex(a).map(String::as_str), Some("a.com|a.com"));
+    assert_eq!(graph.get_vertex(b).map(String::as_str), Some("b.com|b.com"));
+    assert!(!graph.contains_vertex(c) && !graph.contains_vertex(d));
+    assert_eq!(graph.neighbors(&a), vec![b, b, b]);
+    assert_eq!(graph.neighbors(&b), vec![a]);
+    assert!(graph.contains_edge(e2) && graph.contains_edge(e4));
+
+    assert_eq!(graph.relabel(|value| value.clone(), |value, _| value), 0);
+    assert_eq!(graph.len(), (2, 3));
+  }
+
+  #[test]
+  fn symmetrize_inserts_missing_reverse_edges() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..4).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], 1).unwrap();
+    graph.insert_edge(v[0], v[1], 2).unwrap();
+    graph.insert_edge(v[1], v[2], 3).unwrap();
+    graph.insert_edge(v[2], v[1], 4).unwrap();
+    graph.insert_bi_edge(v[2], v[3], 5).unwrap();
+    graph.insert_edge(v[3], v[3], 6).unwrap();
+    graph.insert_edge(v[3], v[0], 7).unwrap();
+
+    let mut sink = FiniteGraph::<(), u32>::new();
+    let (a, b) = (sink.insert_vertex(()), sink.insert_vertex(()));
+
+    sink.insert_edge(a, b, 1).unwrap();
+
+    assert_eq!(sink.symmetrize(), 1);
+    assert_eq!(sink.neighbors(&b), vec![a]);
+
+    let mut residual = graph.clone();
+
+    assert_eq!(graph.symmetrize(), 3);
+    assert_eq!(graph.edges(&v[1], &v[0]), vec![1, 2]);
+    assert_eq!(graph.edges(&v[0], &v[3]), vec![7]);
+    assert_eq!(graph.len(), (4, 10));
+    assert_eq!(graph.symmetrize(), 0);
+
+    assert_eq!(residual.symmetrize_with(|_| 0), 3);
+    assert_eq!(residual.edges(&v[1], &v[0]), vec![0, 0]);
+    assert_eq!(residual.edges(&v[2], &v[1]), vec![4]);
+  }
+
+  #[test]
+  fn add_self_loops_skips_existing_ones() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+    let v = (0..3).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_bi_edge(v[0], v[1], 1).unwrap();
+    graph.insert_bi_edge(v[2], v[2], 2).unwrap();
+
+    assert!(!graph.has_self_loop(v[0]));
+    assert!(graph.has_self_loop(v[2]));
+    assert_eq!(graph.add_self_loop(v[2], 3), None);
+
+    let e = graph.add_self_loop(v[1], 4).unwrap();
+
+    assert_eq!(graph.get_edge(e), Some(&4));
+    assert_eq!(graph.add_self_loops_all(5), 1);
+    assert_eq!(graph.edges(&v[0], &v[0]), vec![5]);
+    assert_eq!(graph.add_self_loops_all(5), 0);
+    assert_eq!(graph.len(), (3, 4));
+    assert_eq!(graph.add_self_loop(e, 6), None);
+  }
+
+  #[test]
+  fn graph_power_keeps_directions() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+
+    graph.insert_edge(a, b, ()).unwrap();
+    graph.insert_edge(b, c, ()).unwrap();
+    graph.insert_edge(c, b, ()).unwrap();
+
+    let square = graph.graph_square();
+
+    assert_eq!(edge_set(&square), vec![(a, b), (a, c), (b, c)]);
+    assert!(square.neighbors(&c).contains(&b));
+    assert!(!square.neighbors(&c).contains(&a));
+  }
+}
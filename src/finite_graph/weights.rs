@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+use edge::WeightedEdge;
+use super::*;
+
+/// Statistics of all edge weights as computed by
+/// [`FiniteGraph::edge_stats`](struct.FiniteGraph.html#method.edge_stats).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EdgeStats<E> {
+  pub min: E,
+  pub max: E,
+  /// The sum of all weights, where bidirectional edges are counted once.
+  pub sum: E,
+  /// The number of edges, where bidirectional edges are counted once.
+  pub count: usize
+}
+
+impl<V, E> FiniteGraph<V, E> {
+  /// Returns `true` if `edge` from `from` to `to` is a bidirectional edge.
+  pub(crate) fn is_bi_edge(&self, edge: Id, from: Id, to: Id) -> bool {
+    // A directed self-loop appears once in its own neighbors, a bidirectional one twice
+    let min_count = if from == to { 2 } else { 1 };
+
+    self.neighbors_map.get(&to).map_or(0, |neighbors| {
+      neighbors.iter().filter(|&&neighbor| neighbor == (from, edge)).count()
+    }) >= min_count
+  }
+
+  /// Returns the edge with the smallest weight, or `None` if the graph has no edges.
+  /// Ties are broken by the smallest id.
+  pub fn min_edge(&self) -> Option<(Id, &E)> where E: Ord {
+    self.edges_map.iter()
+    .map(|(&edge, (data, _, _))| (edge, data))
+    .min_by(|(e1, d1), (e2, d2)| d1.cmp(d2).then(e1.cmp(e2)))
+  }
+
+  /// Returns the edge with the largest weight, or `None` if the graph has no edges.
+  /// Ties are broken by the smallest id.
+  pub fn max_edge(&self) -> Option<(Id, &E)> where E: Ord {
+    self.edges_map.iter()
+    .map(|(&edge, (data, _, _))| (edge, data))
+    .max_by(|(e1, d1), (e2, d2)| d1.cmp(d2).then(e2.cmp(e1)))
+  }
+}
+
+impl<V, E: WeightedEdge> FiniteGraph<V, E> {
+  /// Returns the sum of all edge weights. Parallel edges are counted individually and
+  /// bidirectional edges are counted once.
+  pub fn total_edge_weight(&self) -> E {
+    self.edges_map.values()
+    .fold(E::default(), |sum, (data, _, _)| sum + data.clone())
+  }
+
+  /// Returns the minimum, maximum, and sum of all edge weights, or `None` if the graph has no
+  /// edges.
+  pub fn edge_stats(&self) -> Option<EdgeStats<E>> {
+    let mut weights = self.edges_map.values().map(|(data, _, _)| data);
+    let first = weights.next()?.clone();
+
+    Some(weights.fold(
+      EdgeStats { min: first.clone(), max: first.clone(), sum: first, count: 1 },
+      |stats, data| EdgeStats {
+        min: if data < &stats.min { data.clone() } else { stats.min },
+        max: if data > &stats.max { data.clone() } else { stats.max },
+        sum: stats.sum + data.clone(),
+        count: stats.count + 1
+      }
+    ))
+  }
+
+  /// Replaces every edge weight by the result of `f`, which gets the old weight and the
+  /// [`edge_stats`](#method.edge_stats) of all weights before any weight is replaced.
+  /// Bidirectional edges are rewritten once.
+  pub fn rescale_edges(&mut self, mut f: impl FnMut(&E, &EdgeStats<E>) -> E) {
+    let stats = match self.edge_stats() {
+      Some(stats) => stats,
+      None => return
+    };
+
+    for (data, _, _) in self.edges_map.values_mut() {
+      *data = f(data, &stats);
+    }
+  }
+
+  /// Returns the sum of the weights of all edges crossing the bipartition of vertices into
+  /// `left` and the remaining vertices.
+  ///
+  /// If `both_directions` is `false`, only edges going from `left` to the remaining vertices
+  /// are counted, otherwise edges going in the other direction are counted as well.
+  /// Bidirectional edges go in both directions, but are counted once. Parallel edges are
+  /// counted individually.
+  pub fn cut_weight(&self, left: &HashSet<Id>, both_directions: bool) -> E {
+    self.edges_map.iter()
+    .filter(|&(&edge, &(_, from, to))| match (left.contains(&from), left.contains(&to)) {
+      (true, false) => true,
+      (false, true) => both_directions || self.is_bi_edge(edge, from, to),
+      _ => false
+    })
+    .fold(E::default(), |sum, (_, (data, _, _))| sum + data.clone())
+  }
+}
+
+impl<V> FiniteGraph<V, f64> {
+  /// Maps all edge weights linearly onto the range from `lo` to `hi`, such that the smallest
+  /// weight becomes `lo` and the largest weight becomes `hi`. If all weights are equal, they
+  /// all become `lo`. `NaN` weights are ignored when determining the current range.
+  pub fn normalize_to_range(&mut self, lo: f64, hi: f64) {
+    let (min, max) = self.edges_map.values()
+      .map(|&(data, _, _)| data)
+      .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), data| (min.min(data), max.max(data)));
+    let scale = if max > min { (hi - lo) / (max - min) } else { 0.0 };
+
+    for (data, _, _) in self.edges_map.values_mut() {
+      *data = lo + (*data - min) * scale;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn weight_summaries_handle_parallel_and_bi_edges() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+
+    assert_eq!(graph.total_edge_weight(), 0);
+    assert_eq!(graph.min_edge(), None);
+    assert_eq!(graph.max_edge(), None);
+    assert_eq!(graph.cut_weight(&HashSet::new(), true), 0);
+
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+    let c = graph.insert_vertex(());
+
+    let e1 = graph.insert_edge(a, b, 1).unwrap();
+    graph.insert_edge(a, b, 2).unwrap();
+    graph.insert_edge(b, a, 4).unwrap();
+    graph.insert_bi_edge(b, c, 8).unwrap();
+    let e5 = graph.insert_edge(c, c, 16).unwrap();
+
+    assert_eq!(graph.total_edge_weight(), 31);
+    assert_eq!(graph.min_edge(), Some((e1, &1)));
+    assert_eq!(graph.max_edge(), Some((e5, &16)));
+
+    let left = vec![a].into_iter().collect::<HashSet<_>>();
+
+    assert_eq!(graph.cut_weight(&left, false), 3);
+    assert_eq!(graph.cut_weight(&left, true), 7);
+
+    let left = vec![c].into_iter().collect::<HashSet<_>>();
+
+    assert_eq!(graph.cut_weight(&left, false), 8);
+    assert_eq!(graph.cut_weight(&left, true), 8);
+  }
+
+  #[test]
+  fn rescale_edges_uses_stats_of_old_weights() {
+    let mut graph = FiniteGraph::<(), u32>::new();
+
+    graph.rescale_edges(|_, _| unreachable!());
+    assert_eq!(graph.edge_stats(), None);
+
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+
+    graph.insert_edge(a, b, 3).unwrap();
+    graph.insert_bi_edge(a, b, 5).unwrap();
+    graph.insert_edge(b, b, 12).unwrap();
+
+    assert_eq!(graph.edge_stats(), Some(EdgeStats { min: 3, max: 12, sum: 20, count: 3 }));
+
+    let mut calls = 0;
+
+    graph.rescale_edges(|&weight, stats| {
+      calls += 1;
+      weight * 100 / stats.sum + stats.max - stats.min
+    });
+
+    assert_eq!(calls, 3);
+    assert_eq!(graph.edge_stats(), Some(EdgeStats { min: 24, max: 69, sum: 24 + 34 + 69, count: 3 }));
+  }
+
+  #[test]
+  fn normalize_to_range() {
+    let mut graph = FiniteGraph::<(), f64>::new();
+    let a = graph.insert_vertex(());
+    let b = graph.insert_vertex(());
+
+    let e1 = graph.insert_edge(a, b, -2.0).unwrap();
+    let e2 = graph.insert_bi_edge(a, b, 0.0).unwrap();
+    let e3 = graph.insert_edge(b, a, 6.0).unwrap();
+
+    graph.normalize_to_range(0.0, 1.0);
+
+    assert_eq!(graph.get_edge(e1), Some(&0.0));
+    assert_eq!(graph.get_edge(e2), Some(&0.25));
+    assert_eq!(graph.get_edge(e3), Some(&1.0));
+
+    graph.remove_edge(e3);
+    graph.remove_edge(e1);
+    graph.normalize_to_range(2.0, 4.0);
+
+    assert_eq!(graph.get_edge(e2), Some(&2.0));
+  }
+}
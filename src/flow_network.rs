@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+
+/// An arc of a [`FlowNetwork`](struct.FlowNetwork.html). Arcs added with
+/// [`add_arc`](struct.FlowNetwork.html#method.add_arc) carry its label, while their reverse
+/// arcs in the residual network carry none.
+pub(crate) struct FlowArc<L> {
+  pub(crate) to: usize,
+  pub(crate) capacity: usize,
+  pub(crate) label: Option<L>,
+  reverse: usize
+}
+
+/// A flow network with integer capacities on nodes `0..n`, where each node stores its
+/// outgoing arcs together with the reverse arcs of its incoming arcs.
+pub(crate) struct FlowNetwork<L>(Vec<Vec<FlowArc<L>>>);
+
+impl<L> FlowNetwork<L> {
+  pub(crate) fn new(node_count: usize) -> FlowNetwork<L> {
+    FlowNetwork((0..node_count).map(|_| vec![]).collect())
+  }
+
+  pub(crate) fn add_arc(&mut self, from: usize, to: usize, capacity: usize, label: L) {
+    let (forward, reverse) = (self.0[from].len(), self.0[to].len() + if from == to { 1 } else { 0 });
+
+    self.0[from].push(FlowArc { to, capacity, label: Some(label), reverse });
+    self.0[to].push(FlowArc { to: from, capacity: 0, label: None, reverse: forward });
+  }
+
+  pub(crate) fn node_count(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns the arcs of `node`, including reverse arcs.
+  pub(crate) fn arcs(&self, node: usize) -> &[FlowArc<L>] {
+    &self.0[node]
+  }
+
+  /// Returns the flow along the `index`-th arc of `node`, which is only meaningful for arcs
+  /// that carry a label.
+  pub(crate) fn flow(&self, node: usize, index: usize) -> usize {
+    let arc = &self.0[node][index];
+    self.0[arc.to][arc.reverse].capacity
+  }
+
+  /// Finds a shortest augmenting path from `source` to `sink` and sends one unit of flow
+  /// along it. Returns `false` if there is no augmenting path.
+  pub(crate) fn augment(&mut self, source: usize, sink: usize) -> bool {
+    let mut predecessors = vec![None; self.0.len()];
+    let mut queue = VecDeque::new();
+
+    queue.push_back(source);
+
+    while let Some(node) = queue.pop_front() {
+      if node == sink {
+        break;
+      }
+
+      for (index, arc) in self.0[node].iter().enumerate() {
+        if arc.capacity > 0 && arc.to != source && predecessors[arc.to].is_none() {
+          predecessors[arc.to] = Some((node, index));
+          queue.push_back(arc.to);
+        }
+      }
+    }
+
+    if predecessors[sink].is_none() {
+      return false;
+    }
+
+    let mut node = sink;
+
+    while let Some((predecessor, index)) = predecessors[node] {
+      let reverse = self.0[predecessor][index].reverse;
+
+      self.0[predecessor][index].capacity -= 1;
+      self.0[node][reverse].capacity += 1;
+      node = predecessor;
+    }
+
+    true
+  }
+
+  /// Sends as many units of flow as possible, but at most `limit`, from `source` to `sink` and
+  /// returns the number of units sent.
+  pub(crate) fn max_flow(&mut self, source: usize, sink: usize, limit: usize) -> usize {
+    if source == sink {
+      return 0;
+    }
+
+    (0..limit).take_while(|_| self.augment(source, sink)).count()
+  }
+}
@@ -1,8 +1,20 @@
 use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::iter;
+use std::rc::Rc;
+use std::sync::Arc;
 use vertex::Vertex;
 use edge::{Edge, WeightedEdge};
-use graph_adapters::Reversed;
-use vertex_traverser::{DfsVertexTrav, BfsVertexTrav, AstarVertexTrav};
+use graph_adapters::{CostRings, Reversed, TurnCosts};
+use isochrone::{IsochroneBand, UnsortedThresholdsError};
+use finite_graph::{FiniteGraph, Id};
+use materialize::MaterializeLimit;
+use vertex_traverser::{VertexTraverser, DfsVertexTrav, BfsVertexTrav, AstarVertexTrav};
+#[cfg(feature = "rand")]
+use rand::RngCore;
+
+/// The traverser returned by [`EdgedGraph::dijkstra`](trait.EdgedGraph.html#method.dijkstra).
+type DijkstraVertexTrav<'a, G, V, E> = AstarVertexTrav<'a, G, V, E, fn(&V) -> E>;
 
 /// Represents a directed, potentially infinite, graph.
 ///
@@ -65,6 +77,11 @@ pub trait Graph<V: Vertex> where Self: Sized {
 
   /// Returns a [`VertexTraverser`](./vertex_traverser/trait.VertexTraverser.html) that iterates the
   /// graph vertices in a breadth-first manner.
+  ///
+  /// Paths constructed by this traverser have the smallest number of edges, regardless of any
+  /// edge weights. Use [`EdgedGraph::dijkstra`](./trait.EdgedGraph.html#method.dijkstra) or
+  /// [`EdgedGraph::uniform_cost`](./trait.EdgedGraph.html#method.uniform_cost) to find paths with
+  /// the smallest weight sum instead.
   fn bfs(&self, start: &V) -> BfsVertexTrav<'_, Self, V> {
     BfsVertexTrav::new(self, start.clone())
   }
@@ -75,6 +92,204 @@ pub trait Graph<V: Vertex> where Self: Sized {
     DfsVertexTrav::new(self, start.clone())
   }
 
+  /// Returns a path from `from` to `to` with the smallest number of edges, or `None` if `to`
+  /// can't be reached. This is a shorthand for constructing the path with a temporary
+  /// [`bfs`](#method.bfs) traverser, so on infinite graphs it doesn't return if `to` is
+  /// unreachable.
+  fn path_between(&self, from: &V, to: &V) -> Option<Vec<V>> {
+    self.bfs(from).construct_path(to)
+  }
+
+  /// Returns `true` if `path` is a path of the graph, i.e. if every vertex of `path` is a
+  /// neighbor of its preceding vertex. A path consisting of a single vertex is valid, whereas
+  /// an empty path is not.
+  fn is_valid_path(&self, path: &[V]) -> bool {
+    !path.is_empty() && path.windows(2).all(|pair| {
+      self.neighbors(&pair[0]).into_iter().any(|neighbor| neighbor == pair[1])
+    })
+  }
+
+  /// Colors the given vertices greedily in the given order, such that no two adjacent vertices
+  /// have the same color, by assigning each vertex the smallest color that is not used by
+  /// any of its already colored neighbors, regardless of edge directions. Colors are numbered
+  /// from zero and self-loops are ignored. Neighbors that are not part of `all_vertices`
+  /// are ignored as well.
+  ///
+  /// The number of used colors depends heavily on the order of `all_vertices`. Passing the
+  /// vertices in descending order of their degrees results in the Welsh-Powell heuristic.
+  fn greedy_color<I: IntoIterator<Item = V>>(&self, all_vertices: I) -> HashMap<V, usize> {
+    let vertices = all_vertices.into_iter().collect::<Vec<_>>();
+    let vertex_set = vertices.iter().cloned().collect::<HashSet<_>>();
+    let mut coloring = HashMap::<V, usize>::new();
+    // Colors of already colored vertices with edges pointing to uncolored vertices
+    let mut forbidden_map = HashMap::<V, HashSet<usize>>::new();
+
+    for vertex in vertices {
+      if coloring.contains_key(&vertex) {
+        continue;
+      }
+
+      let neighbors = self.neighbors(&vertex).into_iter()
+        .filter(|neighbor| neighbor != &vertex && vertex_set.contains(neighbor))
+        .collect::<Vec<_>>();
+      let mut forbidden = forbidden_map.remove(&vertex).unwrap_or_default();
+
+      forbidden.extend(neighbors.iter().filter_map(|neighbor| coloring.get(neighbor).copied()));
+
+      let color = (0..).find(|color| !forbidden.contains(color)).unwrap();
+
+      for neighbor in neighbors {
+        if !coloring.contains_key(&neighbor) {
+          forbidden_map.entry(neighbor).or_default().insert(color);
+        }
+      }
+
+      coloring.insert(vertex, color);
+    }
+
+    coloring
+  }
+
+  /// Returns the smallest number of colors needed to color the given vertices, such that no two
+  /// adjacent vertices have the same color, regardless of edge directions. Self-loops and
+  /// neighbors that are not part of `all_vertices` are ignored.
+  ///
+  /// This uses a backtracking search, which takes exponential time in the worst case, so it is
+  /// only suitable for small graphs.
+  fn chromatic_number_exact<I: IntoIterator<Item = V>>(&self, all_vertices: I) -> usize {
+    let mut vertices = all_vertices.into_iter().collect::<Vec<_>>();
+    let mut index_map = HashMap::new();
+
+    vertices.retain(|vertex| {
+      let new = !index_map.contains_key(vertex);
+
+      if new {
+        index_map.insert(vertex.clone(), index_map.len());
+      }
+
+      new
+    });
+
+    let mut adjacency = vec![HashSet::new(); vertices.len()];
+
+    for (i, vertex) in vertices.iter().enumerate() {
+      for neighbor in self.neighbors(vertex) {
+        if let Some(&j) = index_map.get(&neighbor) {
+          if i != j {
+            adjacency[i].insert(j);
+            adjacency[j].insert(i);
+          }
+        }
+      }
+    }
+
+    fn colorable(adjacency: &[HashSet<usize>], colors: &mut Vec<usize>, k: usize, used: usize) -> bool {
+      let i = colors.len();
+
+      if i == adjacency.len() {
+        return true;
+      }
+
+      // Trying only one unused color avoids exploring permutations of colors
+      for color in 0..k.min(used + 1) {
+        if adjacency[i].iter().any(|&j| j < i && colors[j] == color) {
+          continue;
+        }
+
+        colors.push(color);
+
+        if colorable(adjacency, colors, k, used.max(color + 1)) {
+          return true;
+        }
+
+        colors.pop();
+      }
+
+      false
+    }
+
+    (0..=vertices.len())
+    .find(|&k| colorable(&adjacency, &mut vec![], k, 0))
+    .unwrap()
+  }
+
+  /// Finds a `k`-regular spanning subgraph of the given vertices, i.e. a set of edges such that
+  /// every vertex of `all_vertices` has degree exactly `k`, ignoring edge directions. Returns
+  /// the selected edges as `(from, to)` pairs, where `from` comes first in `all_vertices`, or
+  /// `None` if there is no such subgraph. Self-loops, parallel edges and neighbors that are not
+  /// part of `all_vertices` are ignored.
+  ///
+  /// For `k = 1`, this is a perfect matching, which could also be found with a maximum matching
+  /// algorithm, whereas `k > 1` requires more complex algorithms like Tutte's reduction to a
+  /// matching problem. Instead, a maximum flow on the bipartite double cover, which has an
+  /// outgoing and an incoming copy of every vertex, rules out most graphs without a solution in
+  /// `O(k * n * m)` time, before the edges are selected by backtracking. The backtracking takes
+  /// exponential time in the worst case, so this is meant for small graphs.
+  fn find_regular_subgraph(&self, k: usize, all_vertices: Vec<V>) -> Option<Vec<(V, V)>> {
+    regular_subgraph::find_regular_subgraph(self, k, all_vertices)
+  }
+
+  /// Copies the region around `start` that is explored by a breadth-first search into a
+  /// [`FiniteGraph`](./struct.FiniteGraph.html), so algorithms that need a finite graph can run
+  /// on a window of an infinite or implicit graph. `limit` determines which vertices are copied.
+  /// All edges between copied vertices are copied as well, while edges to other vertices are
+  /// dropped, even if they are discovered.
+  ///
+  /// Returns the copied graph together with the ids of the copied vertices. Vertices are
+  /// inserted in breadth-first order.
+  fn materialize(&self, start: &V, limit: MaterializeLimit<'_, V>) -> (FiniteGraph<V, ()>, HashMap<V, Id>) {
+    materialize::materialize(start, limit, |vertex| {
+      self.neighbors(vertex).into_iter().map(|neighbor| (neighbor, ()))
+    })
+  }
+
+  /// Returns the vertices of `all_vertices` in an order produced by lexicographic breadth-first
+  /// search, which visits next the vertex whose visited neighbors were visited earliest, i.e. it
+  /// prefers the neighbors of the first visited vertex, then of the second, and so on. Ties are
+  /// broken by the order of `all_vertices`, so the search starts at its first vertex.
+  ///
+  /// Edge directions, self-loops and neighbors that are not part of `all_vertices` are ignored.
+  /// This uses partition refinement and takes `O(n²)` time. The reversed ordering is a perfect
+  /// elimination ordering if and only if the graph is chordal.
+  fn lexicographic_bfs(&self, all_vertices: Vec<V>) -> Vec<V> {
+    chordal::lexicographic_bfs(self, all_vertices)
+  }
+
+  /// Returns `true` if `ordering` is a perfect elimination ordering, i.e. if the neighbors of
+  /// every vertex that come after it in `ordering` are pairwise adjacent. Edge directions,
+  /// self-loops and neighbors that are not part of `ordering` are ignored. Returns `false` if
+  /// `ordering` contains a vertex more than once.
+  fn is_perfect_elimination_ordering(&self, ordering: &[V]) -> bool {
+    chordal::is_perfect_elimination_ordering(self, ordering)
+  }
+
+  /// Returns `true` if every cycle of length at least four among `all_vertices` has a chord,
+  /// i.e. an edge between two vertices of the cycle that aren't consecutive. Edge directions,
+  /// self-loops and neighbors that are not part of `all_vertices` are ignored.
+  ///
+  /// This checks whether the reversed [`lexicographic_bfs`](#method.lexicographic_bfs) ordering
+  /// is a [perfect elimination ordering](#method.is_perfect_elimination_ordering).
+  fn is_chordal(&self, all_vertices: Vec<V>) -> bool {
+    chordal::is_chordal(self, all_vertices)
+  }
+
+  /// Samples a spanning tree of `all_vertices` uniformly at random with Wilson's algorithm and
+  /// returns it as a map from each vertex to its parent, where `root` has no parent. Following
+  /// the parents from any vertex leads to `root` along edges of the graph.
+  ///
+  /// Starting from every vertex that is not part of the tree yet, a random walk is performed
+  /// until it hits the tree, and the walk with all of its loops erased is added to the tree.
+  /// Unlike a depth-first search with random neighbor order, this yields every spanning tree of
+  /// an undirected graph with the same probability, i.e. every spanning arborescence towards
+  /// `root` in the directed case. Parallel edges make their trees proportionally more likely.
+  ///
+  /// Neighbors that are not part of `all_vertices` are ignored, and vertices that can't reach
+  /// `root` are left out of the result.
+  #[cfg(feature = "rand")]
+  fn random_spanning_tree(&self, root: &V, all_vertices: Vec<V>, rng: impl RngCore) -> HashMap<V, Option<V>> {
+    spanning_tree::random_spanning_tree(self, root, all_vertices, rng)
+  }
+
   /// Returns a graph by reversing all edges.
   fn rev(&self) -> Reversed<'_, Self> where Self: ReversibleGraph<V> {
     Reversed::new(self)
@@ -87,6 +302,28 @@ pub trait ReversibleGraph<V: Vertex>: Graph<V> {
 
   /// Generates a list of adjacent vertices that can be reached from `vertex` by traveling along an edge backwards.
   fn reverse_neighbors(&self, vertex: &V) -> Self::ReverseNeighborsIterator;
+
+  /// Returns all vertices from which `vertex` can be reached, excluding `vertex` itself.
+  fn ancestors(&self, vertex: &V) -> HashSet<V> {
+    self.rev().bfs(vertex).iter().skip(1).collect()
+  }
+
+  /// Returns all vertices that can be reached from `vertex`, excluding `vertex` itself.
+  fn descendants(&self, vertex: &V) -> HashSet<V> {
+    self.bfs(vertex).iter().skip(1).collect()
+  }
+
+  /// Returns `true` if `vertex` can be reached from `candidate`, where `candidate` is
+  /// not equal to `vertex`.
+  fn is_ancestor(&self, candidate: &V, vertex: &V) -> bool {
+    self.bfs(candidate).iter().skip(1).any(|v| &v == vertex)
+  }
+
+  /// Returns `true` if `candidate` can be reached from `vertex`, where `candidate` is
+  /// not equal to `vertex`.
+  fn is_descendant(&self, candidate: &V, vertex: &V) -> bool {
+    self.is_ancestor(vertex, candidate)
+  }
 }
 
 /// Represents a directed, potentially infinite, multigraph, where edges contain certain data.
@@ -128,7 +365,7 @@ pub trait ReversibleGraph<V: Vertex>: Graph<V> {
 ///   type EdgesIterator = Vec<u32>;
 ///
 ///   fn edges(&self, &(x1, y1): &Position, &(x2, y2): &Position) -> Vec<u32> {
-///     vec![(x2 - x1).abs() as u32 + (y2 - y1).pow(2) as u32]
+///     vec![(x2 - x1).unsigned_abs() + (y2 - y1).pow(2) as u32]
 ///   }
 /// }
 ///
@@ -146,6 +383,39 @@ pub trait EdgedGraph<V: Vertex, E: Edge>: Graph<V> {
   /// Generates a list of edges that connect `vertex` with `other`.
   fn edges(&self, vertex: &V, other: &V) -> Self::EdgesIterator;
 
+  /// Generates the adjacent vertices of `vertex` lazily, each paired with an edge that connects
+  /// `vertex` with it. Neighbors connected by multiple edges appear once per edge. The iterator
+  /// may borrow the graph, but not `vertex`.
+  ///
+  /// The default implementation calls [`edges`](#tymethod.edges) for every distinct neighbor.
+  /// Override this method if your graph is able to enumerate outgoing edges more efficiently,
+  /// since weighted vertex traversers use it to expand vertices. Implementations can return
+  /// any iterator type.
+  fn neighbors_with_edges<'a>(&'a self, vertex: &V) -> impl Iterator<Item = (V, E)> + use<'a, Self, V, E> {
+    let vertex = vertex.clone();
+    let mut visited = HashSet::new();
+
+    self.neighbors(&vertex).into_iter()
+    .filter(move |neighbor| visited.insert(neighbor.clone()))
+    .flat_map(move |neighbor| {
+      self.edges(&vertex, &neighbor).into_iter()
+      .map(move |edge| (neighbor.clone(), edge))
+    })
+  }
+
+  /// Returns the weight sum of `path`, where each step uses the smallest edge between two
+  /// consecutive vertices. Returns `None` if two consecutive vertices aren't connected by an
+  /// edge or if `path` is empty. A path consisting of a single vertex costs `E::default()`.
+  fn path_cost(&self, path: &[V]) -> Option<E> where E: WeightedEdge {
+    if path.is_empty() {
+      return None;
+    }
+
+    path.windows(2).try_fold(E::default(), |cost, pair| {
+      self.edges(&pair[0], &pair[1]).into_iter().min().map(|edge| cost + edge)
+    })
+  }
+
   /// Returns a [`VertexTraverser`](./vertex_traverser/trait.VertexTraverser.html) that iterates the
   /// graph vertices in a smallest-weight-sum-first manner. This function requires your edge type `E`
   /// to implement the [`WeightedEdge`](./trait.WeightedEdge.html) trait, i.e. additionally
@@ -170,6 +440,206 @@ pub trait EdgedGraph<V: Vertex, E: Edge>: Graph<V> {
     AstarVertexTrav::new(self, start.clone())
   }
 
+  /// Returns a [`VertexTraverser`](./vertex_traverser/trait.VertexTraverser.html) that iterates the
+  /// graph vertices in a smallest-weight-sum-first manner. This is an alias of
+  /// [`dijkstra`](#method.dijkstra), also known as uniform cost search.
+  fn uniform_cost(&self, start: &V) -> AstarVertexTrav<'_, Self, V, E, fn(&V) -> E>
+  where E: WeightedEdge {
+    self.dijkstra(start)
+  }
+
+  /// Returns a path from `from` to `to` with the smallest weight sum, or `None` if `to` can't
+  /// be reached. This is a shorthand for constructing the path with a temporary
+  /// [`dijkstra`](#method.dijkstra) traverser, so on infinite graphs it doesn't return if `to`
+  /// is unreachable.
+  fn weighted_path_between(&self, from: &V, to: &V) -> Option<Vec<V>>
+  where E: WeightedEdge {
+    self.dijkstra(from).construct_path(to)
+  }
+
+  /// Answers many shortest path queries at once and returns a path with the smallest weight
+  /// sum together with its weight sum for each `(from, to)` pair in the same order, or `None`
+  /// if `to` can't be reached. Queries are grouped by `from`, so there is only one
+  /// [`dijkstra`](#method.dijkstra) traversal per distinct source, which stops as soon as all
+  /// targets of that source have been reached. On infinite graphs this doesn't return if a
+  /// target is unreachable. See [`par_batch_shortest_paths`](#method.par_batch_shortest_paths)
+  /// for a parallel version.
+  fn batch_shortest_paths(&self, queries: &[(V, V)]) -> Vec<Option<(Vec<V>, E)>>
+  where Self: Sized, E: WeightedEdge {
+    batch::batch_shortest_paths(self, queries)
+  }
+
+  /// Like [`batch_shortest_paths`](#method.batch_shortest_paths), but runs the traversals of
+  /// the distinct sources in parallel.
+  #[cfg(feature = "rayon")]
+  fn par_batch_shortest_paths(&self, queries: &[(V, V)]) -> Vec<Option<(Vec<V>, E)>>
+  where Self: Sized + Sync, V: Send + Sync, E: WeightedEdge + Send {
+    batch::par_batch_shortest_paths(self, queries)
+  }
+
+  /// Returns all vertices that can be reached from `start` with a weight sum of at most
+  /// `budget`, including `start` itself. Like [`dijkstra`](#method.dijkstra), this only
+  /// supports non-negative weights.
+  fn vertices_within_cost(&self, start: &V, budget: E) -> HashSet<V>
+  where E: WeightedEdge {
+    self.vertices_within_cost_with_costs(start, budget).into_keys().collect()
+  }
+
+  /// Returns all vertices that can be reached from `start` with a weight sum of at most
+  /// `budget`, together with their smallest weight sums. The traversal stops as soon as it
+  /// visits a vertex that exceeds the budget, so this terminates on infinite graphs as long
+  /// as only finitely many vertices are within the budget.
+  fn vertices_within_cost_with_costs(&self, start: &V, budget: E) -> HashMap<V, E>
+  where E: WeightedEdge {
+    self.dijkstra(start).iter_with_cost()
+    .take_while(|(_, cost)| cost <= &budget)
+    .collect()
+  }
+
+  /// Groups all vertices that can be reached from `start` with a weight sum of at most the
+  /// largest threshold into bands, one for each threshold, using a single Dijkstra traversal.
+  /// The band of `thresholds[i]` contains all vertices with a weight sum in the interval
+  /// `(thresholds[i - 1], thresholds[i]]`, where the first band starts with `start` itself.
+  ///
+  /// Returns an error if `thresholds` is not sorted in ascending order. Equal thresholds
+  /// result in empty bands.
+  fn isochrones(&self, start: &V, thresholds: &[E]) -> Result<Vec<IsochroneBand<V, E>>, UnsortedThresholdsError>
+  where E: WeightedEdge {
+    isochrone::isochrones(self, start, thresholds)
+  }
+
+  /// Returns an iterator over consecutive cost rings around `start` using a single lazy Dijkstra
+  /// traversal. The `i`-th ring contains all vertices with a weight sum in the interval
+  /// `[i * ring_width, (i + 1) * ring_width)` in ascending order of their weight sums, so the
+  /// first ring starts with `start` itself. Rings without vertices are yielded as empty
+  /// vectors, and the iterator ends after the last reachable vertex.
+  ///
+  /// Since rings are only computed on demand, this can be used on infinite graphs together
+  /// with [`take`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.take).
+  ///
+  /// # Panics
+  ///
+  /// Panics if `ring_width` is not greater than `E::default()`.
+  fn cost_rings(&self, start: &V, ring_width: E) -> CostRings<'_, Self, V, E>
+  where E: WeightedEdge {
+    CostRings::new(self.dijkstra(start), ring_width)
+  }
+
+  /// Like [`Graph::materialize`](./trait.Graph.html#method.materialize), but copies the edge
+  /// values as reported by [`neighbors_with_edges`](#method.neighbors_with_edges), so vertices
+  /// connected by multiple edges are connected by parallel edges in the copy.
+  fn materialize_with_edges(&self, start: &V, limit: MaterializeLimit<'_, V>) -> (FiniteGraph<V, E>, HashMap<V, Id>) {
+    materialize::materialize(start, limit, |vertex| self.neighbors_with_edges(vertex))
+  }
+
+  /// Returns a cycle through `vertex` with the smallest weight sum together with its weight sum,
+  /// or `None` if there is no such cycle. The cycle starts and ends with `vertex`, so a self-loop
+  /// yields `[vertex, vertex]`. Like [`dijkstra`](#method.dijkstra), this only supports
+  /// non-negative weights.
+  ///
+  /// This runs a Dijkstra traversal from every neighbor of `vertex` back to `vertex`, each of
+  /// which stops as soon as it can't find a cheaper cycle anymore. Since edges are only known by
+  /// their values here, traveling along an edge between two vertices and back counts as a cycle
+  /// of length two, if the graph reports the edge in both directions. `FiniteGraph` knows its
+  /// edge ids and excludes this reuse of the first edge, so a bidirectional edge alone is not
+  /// a cycle there, whereas two opposite edges are.
+  fn shortest_cycle_through(&self, vertex: &V) -> Option<(Vec<V>, E)>
+  where E: WeightedEdge {
+    let mut first_edges = Vec::<(V, E)>::new();
+
+    for (neighbor, edge) in self.neighbors_with_edges(vertex) {
+      match first_edges.iter_mut().find(|(other, _)| other == &neighbor) {
+        Some((_, min_edge)) if edge < *min_edge => *min_edge = edge,
+        Some(_) => {},
+        None => first_edges.push((neighbor, edge))
+      }
+    }
+
+    let mut best = None::<(Vec<V>, E)>;
+
+    for (neighbor, edge) in first_edges {
+      if &neighbor == vertex {
+        if best.as_ref().is_none_or(|(_, cost)| &edge < cost) {
+          best = Some((vec![vertex.clone(), vertex.clone()], edge));
+        }
+
+        continue;
+      }
+
+      let mut traverser = self.dijkstra(&neighbor);
+      let mut found = None;
+
+      for (other, cost) in traverser.iter_with_cost() {
+        let total = edge.clone() + cost;
+
+        if best.as_ref().is_some_and(|(_, cost)| &total >= cost) {
+          break;
+        } else if &other == vertex {
+          found = Some(total);
+          break;
+        }
+      }
+
+      if let Some(total) = found {
+        let path = iter::once(vertex.clone())
+          .chain(traverser.construct_path(vertex).unwrap())
+          .collect();
+
+        best = Some((path, total));
+      }
+    }
+
+    best
+  }
+
+  /// Returns the `k` vertices with the smallest weight sums from `start` in ascending order,
+  /// paired with their weight sums. Since `start` itself has the weight sum `E::default()`,
+  /// it is always the first vertex. Fewer vertices are returned if less than `k` vertices
+  /// can be reached.
+  fn k_nearest(&self, start: &V, k: usize) -> Vec<(V, E)>
+  where E: WeightedEdge {
+    self.dijkstra(start).iter_with_cost().take(k).collect()
+  }
+
+  /// Returns up to `k` vertices satisfying `is_target` with the smallest weight sums from
+  /// `start` in ascending order, paired with their weight sums, together with the traverser
+  /// that found them, so the paths to them can be reconstructed with
+  /// [`construct_path`](./vertex_traverser/trait.VertexTraverser.html#method.construct_path).
+  ///
+  /// The traversal stops as soon as `k` targets have been found or, if `budget` is given, a
+  /// vertex with a weight sum above `budget` is visited. Fewer targets are returned if the
+  /// traversal runs out of vertices. On infinite graphs, either `k` targets have to be reachable
+  /// or `budget` has to be given, so that finitely many vertices are within the budget.
+  fn nearest<P>(
+    &self,
+    start: &V,
+    k: usize,
+    budget: Option<E>,
+    mut is_target: P
+  ) -> (Vec<(V, E)>, DijkstraVertexTrav<'_, Self, V, E>)
+  where E: WeightedEdge, P: FnMut(&V) -> bool {
+    let mut traverser = self.dijkstra(start);
+    let mut result = vec![];
+
+    if k > 0 {
+      for (vertex, cost) in traverser.iter_with_cost() {
+        if budget.as_ref().is_some_and(|budget| &cost > budget) {
+          break;
+        }
+
+        if is_target(&vertex) {
+          result.push((vertex, cost));
+
+          if result.len() == k {
+            break;
+          }
+        }
+      }
+    }
+
+    (result, traverser)
+  }
+
   /// Returns a [`VertexTraverser`](./vertex_traverser/trait.VertexTraverser.html) that iterates the
   /// graph vertices in a smallest-estimated-weight-sum-first manner using a custom estimator function.
   /// The estimator function estimates the cost for traveling from `start` to its vertex argument.
@@ -190,8 +660,80 @@ pub trait EdgedGraph<V: Vertex, E: Edge>: Graph<V> {
   where F: Fn(&V) -> E, E: WeightedEdge {
     AstarVertexTrav::with_estimator(self, start.clone(), estimator)
   }
+
+  /// Returns a graph adapter whose vertices are [`TurnState`](./graph_adapters/struct.TurnState.html)s,
+  /// i.e. pairs of a previous and a current vertex, so that traveling from `current` to `next`
+  /// after entering `current` from `previous` costs an additional `turn_cost(previous, current, next)`.
+  /// Use [`TurnCosts::construct_path`](./graph_adapters/struct.TurnCosts.html#method.construct_path)
+  /// to find smallest paths in terms of plain vertices.
+  fn with_turn_costs<F>(&self, turn_cost: F) -> TurnCosts<'_, Self, F>
+  where F: Fn(&V, &V, &V) -> E {
+    TurnCosts::new(self, turn_cost)
+  }
+
+  /// Searches a smallest path from `start` to `target` by running an A* search forwards from
+  /// `start` and one backwards from `target` simultaneously. Returns the path together with its
+  /// weight sum, or `None` if `target` can't be reached.
+  ///
+  /// `forward_estimator` estimates the cost for traveling from its vertex argument to `target`,
+  /// and `backward_estimator` estimates the cost for traveling from `start` to its vertex argument.
+  /// The search stops as soon as the cheapest path found so far is not more expensive than the
+  /// smallest key of either search, so the returned path is optimal if both estimators are
+  /// *monotone* as described in [`astar`](#method.astar). Passing `|_| E::default()` as both
+  /// estimators results in a bidirectional Dijkstra search.
+  fn bidirectional_astar<F1, F2>(
+    &self,
+    start: &V,
+    target: &V,
+    forward_estimator: F1,
+    backward_estimator: F2
+  ) -> Option<(Vec<V>, E)>
+  where Self: ReversibleGraph<V>, F1: Fn(&V) -> E, F2: Fn(&V) -> E, E: WeightedEdge {
+    bidirectional_search::bidirectional_astar(self, start, target, forward_estimator, backward_estimator)
+  }
+}
+
+macro_rules! impl_graph_for_pointer {
+  ($([$($lifetime:lifetime)?] $pointer:ty),*) => {$(
+    impl<$($lifetime,)? V: Vertex, G: Graph<V>> Graph<V> for $pointer {
+      type NeighborsIterator = G::NeighborsIterator;
+
+      fn neighbors(&self, vertex: &V) -> Self::NeighborsIterator {
+        (**self).neighbors(vertex)
+      }
+    }
+
+    impl<$($lifetime,)? V: Vertex, G: ReversibleGraph<V>> ReversibleGraph<V> for $pointer {
+      type ReverseNeighborsIterator = G::ReverseNeighborsIterator;
+
+      fn reverse_neighbors(&self, vertex: &V) -> Self::ReverseNeighborsIterator {
+        (**self).reverse_neighbors(vertex)
+      }
+    }
+
+    impl<$($lifetime,)? V: Vertex, E: Edge, G: EdgedGraph<V, E>> EdgedGraph<V, E> for $pointer {
+      type EdgesIterator = G::EdgesIterator;
+
+      fn edges(&self, vertex: &V, other: &V) -> Self::EdgesIterator {
+        (**self).edges(vertex, other)
+      }
+
+      fn neighbors_with_edges<'s>(&'s self, vertex: &V) -> impl Iterator<Item = (V, E)> + use<'s, $($lifetime,)? V, E, G> {
+        (**self).neighbors_with_edges(vertex)
+      }
+
+      fn shortest_cycle_through(&self, vertex: &V) -> Option<(Vec<V>, E)>
+      where E: WeightedEdge {
+        (**self).shortest_cycle_through(vertex)
+      }
+    }
+  )*};
 }
 
+// Forwarding impls, so graphs behind references and smart pointers can be passed
+// to generic code and vertex traverser constructors.
+impl_graph_for_pointer!(['a] &'a G, [] Box<G>, [] Rc<G>, [] Arc<G>);
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -228,6 +770,29 @@ mod tests {
     }
   }
 
+  impl ReversibleGraph<usize> for NumberGraph {
+    type ReverseNeighborsIterator = Vec<usize>;
+
+    fn reverse_neighbors(&self, vertex: &usize) -> Vec<usize> {
+      self.numbers.iter()
+      .filter(|&v| v != vertex && vertex.is_multiple_of(*v))
+      .copied()
+      .collect()
+    }
+  }
+
+  impl EdgedGraph<usize, usize> for NumberGraph {
+    type EdgesIterator = Vec<usize>;
+
+    fn edges(&self, vertex: &usize, other: &usize) -> Vec<usize> {
+      if other.is_multiple_of(*vertex) {
+        vec![other / vertex - 1]
+      } else {
+        vec![]
+      }
+    }
+  }
+
   struct FullyConnectedGraph {
     vertices: Vec<Position>
   }
@@ -244,7 +809,7 @@ mod tests {
     type EdgesIterator = Vec<u32>;
 
     fn edges(&self, &(x1, y1): &Position, &(x2, y2): &Position) -> Vec<u32> {
-      vec![(x2 - x1).abs() as u32 + (y2 - y1).pow(2) as u32]
+      vec![(x2 - x1).unsigned_abs() + (y2 - y1).pow(2) as u32]
     }
   }
 
@@ -262,6 +827,24 @@ mod tests {
     assert_eq!(path[7], (0, 5));
   }
 
+  #[test]
+  fn test_bfs_distances() {
+    let graph = LatticeGraph {
+      blocked: vec![(0, 1)]
+    };
+
+    let mut bfs_traverser = graph.bfs(&(0, 0));
+
+    bfs_traverser.construct_path(&(0, 5)).unwrap();
+
+    assert_eq!(bfs_traverser.distance_to(&(0, 0)), Some(0));
+    assert_eq!(bfs_traverser.distance_to(&(0, 5)), Some(7));
+    assert_eq!(bfs_traverser.distance_to(&(100, 100)), None);
+    assert!(bfs_traverser.all_distances().iter().all(|(v, &d)| {
+      bfs_traverser.predecessor_iter(v).count() == d + 1
+    }));
+  }
+
   #[test]
   fn test_dijkstra_astar_algorithm() {
     let graph = FullyConnectedGraph {
@@ -282,6 +865,35 @@ mod tests {
     assert_eq!(astar_path, path);
   }
 
+  #[test]
+  fn test_dijkstra_with_initial_costs() {
+    use vertex_traverser::AstarVertexTrav;
+
+    let graph = FullyConnectedGraph {
+      vertices: vec![(0, 0), (0, 10), (2, 5), (4, 7), (10, 0), (10, 10)]
+    };
+
+    let full_order = graph.dijkstra(&(0, 0)).iter().collect::<Vec<_>>();
+    let costs = vec![((2, 5), 27), ((4, 7), 33)].into_iter().collect();
+
+    let mut traverser = AstarVertexTrav::with_initial_costs(&graph, &(0, 0), costs);
+    let warm_order = traverser.iter().collect::<Vec<_>>();
+
+    assert_eq!(full_order.len(), graph.vertices.len());
+    assert_eq!(full_order, warm_order);
+    assert_eq!(traverser.predecessor(&(2, 5)), None);
+    assert_eq!(traverser.construct_path(&(10, 10)), Some(vec![(4, 7), (10, 10)]));
+  }
+
+  #[test]
+  fn test_neighbors_with_edges() {
+    let graph = FullyConnectedGraph {
+      vertices: vec![(0, 0), (2, 5), (0, 0)]
+    };
+
+    assert_eq!(graph.neighbors_with_edges(&(0, 0)).collect::<Vec<_>>(), vec![((0, 0), 0), ((2, 5), 27)]);
+  }
+
   #[test]
   fn test_dfs_prepostordering() {
     let graph = NumberGraph {
@@ -296,4 +908,638 @@ mod tests {
 
     assert_eq!(topological_order, vec![1, 3, 2, 6, 4]);
   }
+
+  #[test]
+  fn test_bfs_restart_on_exhausted_component() {
+    let graph = NumberGraph {
+      numbers: vec![2, 4, 3, 9, 8]
+    };
+
+    let mut bfs_traverser = graph.bfs(&2);
+    let first_component = bfs_traverser.iter().collect::<Vec<_>>();
+
+    assert_eq!(first_component, vec![2, 4, 8]);
+    assert!(bfs_traverser.exhausted_component());
+
+    bfs_traverser.restart(&4);
+    assert!(bfs_traverser.exhausted_component());
+
+    bfs_traverser.restart(&3);
+    assert!(!bfs_traverser.exhausted_component());
+
+    let second_component = bfs_traverser.iter().collect::<Vec<_>>();
+
+    assert_eq!(second_component, vec![3, 9]);
+    // Paths start at the start vertex of their own component, not at `first()`
+    assert_eq!(bfs_traverser.first(), 3);
+    assert_eq!(bfs_traverser.construct_path(&8), Some(vec![2, 8]));
+    assert_eq!(bfs_traverser.construct_path(&2), Some(vec![2]));
+    assert_eq!(bfs_traverser.construct_path(&9), Some(vec![3, 9]));
+  }
+
+  #[test]
+  fn test_bfs_frontier_size() {
+    let graph = LatticeGraph { blocked: vec![] };
+    let mut bfs_traverser = graph.bfs(&(0, 0));
+
+    assert_eq!((bfs_traverser.frontier_size(), bfs_traverser.frontier_depth()), (1, Some(0)));
+
+    bfs_traverser.next();
+    assert_eq!((bfs_traverser.frontier_size(), bfs_traverser.frontier_depth()), (4, Some(1)));
+
+    bfs_traverser.next();
+    assert_eq!((bfs_traverser.frontier_size(), bfs_traverser.frontier_depth()), (3, Some(1)));
+
+    for _ in 0..3 {
+      bfs_traverser.next();
+    }
+
+    assert_eq!((bfs_traverser.frontier_size(), bfs_traverser.frontier_depth()), (8, Some(2)));
+
+    for _ in 0..8 {
+      bfs_traverser.next();
+    }
+
+    assert_eq!((bfs_traverser.frontier_size(), bfs_traverser.frontier_depth()), (12, Some(3)));
+
+    let graph = NumberGraph {
+      numbers: vec![2, 4, 3, 9, 8]
+    };
+    let mut bfs_traverser = graph.bfs(&2);
+
+    bfs_traverser.iter().count();
+    assert_eq!((bfs_traverser.frontier_size(), bfs_traverser.frontier_depth()), (0, None));
+
+    bfs_traverser.restart(&3);
+    assert_eq!((bfs_traverser.frontier_size(), bfs_traverser.frontier_depth()), (1, Some(0)));
+  }
+
+  #[test]
+  fn test_dfs_timed_prepostordering() {
+    use vertex_traverser::TimedPrePostItem::*;
+
+    let graph = NumberGraph {
+      numbers: vec![1, 4, 2]
+    };
+
+    let events = graph.dfs(&1).pre_post_iter_timed().collect::<Vec<_>>();
+
+    assert_eq!(events, vec![
+      PreorderItem(1, 0),
+      PreorderItem(2, 1),
+      PreorderItem(4, 2),
+      PostorderItem(4, 3),
+      PostorderItem(2, 4),
+      PostorderItem(1, 5)
+    ]);
+  }
+
+  fn check_construct_path_semantics<T: VertexTraverser<usize>>(graph: &NumberGraph, mut traverser: T) {
+    let is_valid_path = |path: &[usize], target: usize| {
+      path.first() == Some(&2)
+      && path.last() == Some(&target)
+      && path.windows(2).all(|pair| graph.neighbors(&pair[0]).contains(&pair[1]))
+    };
+
+    assert_eq!(traverser.construct_path(&2), Some(vec![2]));
+    assert_eq!(traverser.construct_path(&4), Some(vec![2, 4]));
+
+    let path = traverser.construct_path(&24).unwrap();
+    assert!(is_valid_path(&path, 24));
+
+    assert_eq!(traverser.construct_path(&3), None);
+    assert_eq!(traverser.construct_path(&2), Some(vec![2]));
+
+    for &target in &[8, 24, 6, 4] {
+      let path = traverser.construct_path(&target).unwrap();
+      assert!(is_valid_path(&path, target));
+    }
+
+    assert_eq!(traverser.construct_path(&100), None);
+  }
+
+  #[test]
+  fn test_construct_path_semantics() {
+    let graph = NumberGraph {
+      numbers: vec![2, 3, 4, 6, 8, 24]
+    };
+
+    check_construct_path_semantics(&graph, graph.bfs(&2));
+    check_construct_path_semantics(&graph, graph.dfs(&2));
+    check_construct_path_semantics(&graph, graph.dijkstra(&2));
+
+    assert_eq!(graph.bfs(&2).construct_path(&24).map(|path| path.len()), Some(2));
+    assert_eq!(graph.dijkstra(&2).construct_path(&24), Some(vec![2, 4, 8, 24]));
+  }
+
+  #[test]
+  fn test_construct_path_to_start_on_infinite_graph() {
+    let graph = LatticeGraph {
+      blocked: vec![]
+    };
+
+    let mut bfs_traverser = graph.bfs(&(0, 0));
+
+    assert_eq!(bfs_traverser.construct_path(&(2, 0)).map(|path| path.len()), Some(3));
+    assert_eq!(bfs_traverser.construct_path(&(0, 0)), Some(vec![(0, 0)]));
+
+    let mut dfs_traverser = graph.dfs(&(0, 0));
+
+    assert!(dfs_traverser.construct_path(&(0, 3)).is_some());
+    assert_eq!(dfs_traverser.construct_path(&(0, 0)), Some(vec![(0, 0)]));
+  }
+
+  #[test]
+  fn test_construct_paths_for_multiple_targets() {
+    let graph = NumberGraph {
+      numbers: vec![2, 3, 4, 6, 8, 24, 48]
+    };
+
+    let mut bfs_traverser = graph.bfs(&2);
+    let mut dijkstra_traverser = graph.dijkstra(&2);
+
+    assert_eq!(bfs_traverser.construct_path(&4), Some(vec![2, 4]));
+    assert_eq!(dijkstra_traverser.construct_path(&4), Some(vec![2, 4]));
+
+    let bfs_paths = bfs_traverser.construct_paths(&[4, 24, 3, 2, 48]);
+    let dijkstra_paths = dijkstra_traverser.construct_paths(&[4, 24, 3, 2, 48]);
+
+    assert_eq!(bfs_paths, vec![
+      Some(vec![2, 4]),
+      Some(vec![2, 24]),
+      None,
+      Some(vec![2]),
+      Some(vec![2, 48])
+    ]);
+
+    assert_eq!(dijkstra_paths, vec![
+      Some(vec![2, 4]),
+      Some(vec![2, 4, 8, 24]),
+      None,
+      Some(vec![2]),
+      Some(vec![2, 4, 8, 24, 48])
+    ]);
+
+    assert_eq!(dijkstra_paths, graph.dijkstra(&2).construct_paths(&[4, 24, 3, 2, 48]));
+  }
+
+  fn check_predecessor_semantics<T: VertexTraverser<usize>>(mut traverser: T, predecessor_of_24: usize) {
+    assert_eq!(traverser.predecessor(&2), None);
+    assert_eq!(traverser.predecessor(&24), None);
+    assert!(!traverser.reached(&24));
+
+    assert_eq!(traverser.predecessor_or_search(&24), Some(predecessor_of_24));
+    assert!(traverser.reached(&24));
+    assert_eq!(traverser.predecessor(&24), Some(predecessor_of_24));
+
+    assert_eq!(traverser.predecessor_or_search(&2), None);
+    assert_eq!(traverser.predecessor_or_search(&3), None);
+    assert_eq!(traverser.predecessor_or_search(&8), Some(4));
+  }
+
+  #[test]
+  fn test_predecessor_semantics() {
+    let graph = NumberGraph {
+      numbers: vec![2, 8, 3, 4, 24]
+    };
+
+    check_predecessor_semantics(graph.dijkstra(&2), 8);
+    check_predecessor_semantics(graph.dfs(&2), 2);
+
+    let mut bfs_traverser = graph.bfs(&2);
+
+    assert_eq!(bfs_traverser.predecessor(&24), None);
+    assert_eq!(bfs_traverser.predecessor_or_search(&24), Some(2));
+    assert_eq!(bfs_traverser.predecessor_or_search(&8), Some(2));
+    assert_eq!(bfs_traverser.predecessor_or_search(&3), None);
+  }
+
+  #[test]
+  fn test_ancestors_and_descendants() {
+    let graph = NumberGraph {
+      numbers: vec![2, 3, 4, 6, 8, 12]
+    };
+
+    assert_eq!(graph.ancestors(&12), vec![2, 3, 4, 6].into_iter().collect());
+    assert_eq!(graph.descendants(&2), vec![4, 6, 8, 12].into_iter().collect());
+    assert_eq!(graph.descendants(&8), HashSet::new());
+
+    assert!(graph.is_ancestor(&3, &12));
+    assert!(!graph.is_ancestor(&3, &8));
+    assert!(!graph.is_ancestor(&2, &2));
+    assert!(graph.is_descendant(&12, &3));
+    assert!(!graph.is_descendant(&3, &12));
+  }
+
+  #[test]
+  fn test_dfs_prepostordering_with_parents() {
+    use vertex_traverser::ParentedPrePostItem::*;
+
+    let graph = NumberGraph {
+      numbers: vec![1, 4, 2, 3]
+    };
+
+    let events = graph.dfs(&1).pre_post_iter_with_parents().collect::<Vec<_>>();
+
+    // 4 is pushed by 1 first, but visited from 2
+    assert_eq!(events, vec![
+      PreorderItem { vertex: 1, parent: None },
+      PreorderItem { vertex: 3, parent: Some(1) },
+      PostorderItem { vertex: 3, parent: Some(1) },
+      PreorderItem { vertex: 2, parent: Some(1) },
+      PreorderItem { vertex: 4, parent: Some(2) },
+      PostorderItem { vertex: 4, parent: Some(2) },
+      PostorderItem { vertex: 2, parent: Some(1) },
+      PostorderItem { vertex: 1, parent: None }
+    ]);
+  }
+
+  fn check_pointer_traversal<G: EdgedGraph<Position, u32>>(graph: G) {
+    assert_eq!(graph.bfs(&(0, 0)).construct_path(&(10, 10)).unwrap().len(), 2);
+    assert_eq!(
+      graph.dijkstra(&(0, 0)).construct_path(&(10, 10)).unwrap(),
+      [(0, 0), (2, 5), (4, 7), (10, 10)]
+    );
+  }
+
+  #[test]
+  fn test_traversal_through_pointers() {
+    let graph = FullyConnectedGraph {
+      vertices: vec![(0, 0), (0, 10), (2, 5), (4, 7), (10, 0), (10, 10)]
+    };
+
+    check_pointer_traversal(&graph);
+    check_pointer_traversal(Rc::new(&graph));
+    check_pointer_traversal(Arc::new(&graph));
+    check_pointer_traversal(Box::new(graph));
+
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4]
+    };
+
+    assert!(Rc::new(&graph).is_ancestor(&1, &4));
+    assert!(Arc::new(graph).rev().bfs(&4).iter().any(|v| v == 1));
+  }
+
+  #[test]
+  fn test_path_validation_and_cost() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+
+    assert!(!graph.is_valid_path(&[]));
+    assert!(graph.is_valid_path(&[5]));
+    assert!(graph.is_valid_path(&[1, 2, 4, 12]));
+    assert!(!graph.is_valid_path(&[1, 2, 3]));
+
+    assert_eq!(graph.path_cost(&[]), None);
+    assert_eq!(graph.path_cost(&[5]), Some(0));
+    assert_eq!(graph.path_cost(&[1, 2, 4, 12]), Some(1 + 1 + 2));
+    assert_eq!(graph.path_cost(&[1, 2, 3]), None);
+
+    let path = graph.dijkstra(&1).construct_path(&12).unwrap();
+    assert!(graph.is_valid_path(&path));
+  }
+
+  #[test]
+  fn test_path_between() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+
+    let path = graph.path_between(&1, &12).unwrap();
+    let weighted_path = graph.weighted_path_between(&1, &12).unwrap();
+
+    assert_eq!(path, vec![1, 12]);
+    assert!(graph.is_valid_path(&weighted_path));
+    assert_eq!(graph.path_cost(&weighted_path), Some(4));
+    assert_eq!(graph.path_between(&2, &3), None);
+    assert_eq!(graph.weighted_path_between(&3, &3), Some(vec![3]));
+  }
+
+  #[test]
+  fn test_vertices_within_cost() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+
+    let costs = graph.vertices_within_cost_with_costs(&1, 2);
+
+    assert_eq!(costs, vec![(1, 0), (2, 1), (3, 2), (4, 2)].into_iter().collect());
+    assert_eq!(graph.vertices_within_cost(&1, 0), vec![1].into_iter().collect());
+    assert_eq!(graph.vertices_within_cost(&2, 100), vec![2, 4, 6, 12].into_iter().collect());
+
+    let fully_connected = FullyConnectedGraph {
+      vertices: vec![(0, 0), (0, 10), (2, 5), (4, 7), (10, 0), (10, 10)]
+    };
+
+    assert_eq!(fully_connected.vertices_within_cost(&(0, 0), 27).len(), 3);
+  }
+
+  #[test]
+  fn test_k_nearest() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+
+    assert_eq!(graph.k_nearest(&1, 0), vec![]);
+    assert_eq!(graph.k_nearest(&1, 3), vec![(1, 0), (2, 1), (3, 2)]);
+    assert_eq!(graph.k_nearest(&3, 10), vec![(3, 0), (6, 1), (12, 2)]);
+  }
+
+  #[test]
+  fn test_nearest_targets() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+    let is_even = |vertex: &usize| vertex.is_multiple_of(2);
+
+    let (targets, mut traverser) = graph.nearest(&1, 2, None, is_even);
+
+    assert_eq!(targets, vec![(2, 1), (4, 2)]);
+    assert_eq!(traverser.construct_path(&4), Some(vec![1, 2, 4]));
+    assert_eq!(graph.nearest(&1, 10, None, is_even).0, vec![(2, 1), (4, 2), (6, 3), (12, 4)]);
+    assert_eq!(graph.nearest(&1, 10, Some(2), is_even).0, vec![(2, 1), (4, 2)]);
+    assert_eq!(graph.nearest(&1, 0, None, is_even).0, vec![]);
+  }
+
+  #[test]
+  fn test_iter_with_depth_and_cost() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+
+    assert_eq!(
+      graph.bfs(&2).iter_with_depth().collect::<Vec<_>>(),
+      vec![(2, 0), (4, 1), (6, 1), (12, 1)]
+    );
+    assert_eq!(
+      graph.dijkstra(&2).iter_with_cost().collect::<Vec<_>>(),
+      vec![(2, 0), (4, 1), (6, 2), (12, 3)]
+    );
+  }
+
+  #[test]
+  fn test_isochrones() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+
+    let bands = graph.isochrones(&1, &[0, 1, 1, 3]).unwrap();
+
+    assert_eq!(bands.iter().map(|band| band.threshold).collect::<Vec<_>>(), vec![0, 1, 1, 3]);
+    assert_eq!(bands[0].vertices, vec![(1, 0)]);
+    assert_eq!(bands[1].vertices, vec![(2, 1)]);
+    assert_eq!(bands[2].vertices, vec![]);
+    assert_eq!(bands[3].vertices.len(), 3);
+    assert!(bands[3].vertices.iter().all(|&(_, cost)| cost > 1 && cost <= 3));
+
+    assert_eq!(graph.isochrones(&1, &[]), Ok(vec![]));
+    assert_eq!(graph.isochrones(&1, &[2, 3, 1]), Err(UnsortedThresholdsError { index: 2 }));
+  }
+
+  #[test]
+  fn test_astar_with_tiebreaker() {
+    use std::cmp::Reverse;
+
+    let graph = FullyConnectedGraph {
+      vertices: vec![(1, 0), (0, 1), (0, 0), (-1, 0), (0, -1), (1, 1)]
+    };
+
+    assert_eq!(
+      graph.dijkstra(&(0, 0)).iter().collect::<Vec<_>>(),
+      vec![(0, 0), (1, 0), (0, 1), (-1, 0), (0, -1), (1, 1)]
+    );
+    assert_eq!(
+      graph.dijkstra(&(0, 0)).with_tiebreaker(|&vertex| vertex).iter().collect::<Vec<_>>(),
+      vec![(0, 0), (-1, 0), (0, -1), (0, 1), (1, 0), (1, 1)]
+    );
+
+    let mut traverser = graph.dijkstra(&(0, 0)).with_tiebreaker(|&vertex| Reverse(vertex));
+
+    assert_eq!(
+      traverser.iter().take(5).collect::<Vec<_>>(),
+      vec![(0, 0), (1, 0), (0, 1), (0, -1), (-1, 0)]
+    );
+    assert_eq!(traverser.cost_to(&(1, 1)), None);
+    assert_eq!(traverser.construct_path(&(1, 1)), Some(vec![(0, 0), (1, 1)]));
+    assert_eq!(traverser.cost_to(&(1, 1)), Some(&2));
+    assert_eq!(traverser.cost_to(&(0, -1)), Some(&1));
+  }
+
+  #[test]
+  fn test_lexicographic_bfs_and_chordality() {
+    // A four-cycle 0 - 1 - 2 - 3 with a pendant vertex 4 attached to 2
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..5).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for &(from, to) in &[(0, 1), (1, 2), (2, 3), (3, 0), (2, 4)] {
+      graph.insert_bi_edge(v[from], v[to], ()).unwrap();
+    }
+
+    assert_eq!(graph.lexicographic_bfs(v.clone()), vec![v[0], v[1], v[3], v[2], v[4]]);
+    assert!(!graph.is_chordal(v.clone()));
+    assert!(graph.is_chordal(vec![v[0], v[1], v[2], v[4]]));
+
+    graph.insert_edge(v[1], v[3], ()).unwrap();
+
+    assert!(graph.is_chordal(v.clone()));
+    assert!(graph.is_perfect_elimination_ordering(&[v[4], v[0], v[1], v[2], v[3]]));
+    assert!(!graph.is_perfect_elimination_ordering(&[v[2], v[0], v[1], v[3], v[4]]));
+    assert!(!graph.is_perfect_elimination_ordering(&[v[4], v[4]]));
+
+    let mut order = graph.lexicographic_bfs(v.clone());
+    order.reverse();
+
+    assert!(graph.is_perfect_elimination_ordering(&order));
+  }
+
+  #[cfg(feature = "rand")]
+  #[test]
+  fn test_random_spanning_tree() {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    // A four-cycle has four spanning trees, each missing one of the edges
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..5).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    for i in 0..4 {
+      graph.insert_bi_edge(v[i], v[(i + 1) % 4], ()).unwrap();
+    }
+
+    graph.insert_edge(v[4], v[0], ()).unwrap();
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut counts = HashMap::new();
+
+    for _ in 0..2000 {
+      let tree = graph.random_spanning_tree(&v[0], v.clone(), &mut rng);
+
+      assert_eq!(tree.len(), 5);
+      assert_eq!(tree[&v[0]], None);
+      assert_eq!(tree[&v[4]], Some(v[0]));
+
+      for (vertex, parent) in &tree {
+        if let Some(parent) = parent {
+          assert!(graph.neighbors(vertex).contains(parent));
+        }
+      }
+
+      let mut missing = (0..4)
+        .filter(|&i| tree[&v[i]] != Some(v[(i + 1) % 4]) && tree[&v[(i + 1) % 4]] != Some(v[i]));
+
+      *counts.entry(missing.next().unwrap()).or_insert(0) += 1;
+      assert_eq!(missing.next(), None);
+    }
+
+    assert_eq!(counts.len(), 4);
+    assert!(counts.values().all(|&count| count > 400 && count < 600));
+
+    let tree = graph.random_spanning_tree(&v[4], v.clone(), &mut rng);
+
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[&v[4]], None);
+  }
+
+  #[test]
+  fn test_find_regular_subgraph() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+
+    assert_eq!(graph.find_regular_subgraph(0, vec![1, 2, 3]), Some(vec![]));
+    assert_eq!(graph.find_regular_subgraph(1, vec![1, 2, 3]), None);
+
+    let graph = FullyConnectedGraph {
+      vertices: vec![(0, 0), (0, 1), (1, 0), (1, 1)]
+    };
+
+    for k in 1..4 {
+      let edges = graph.find_regular_subgraph(k, graph.vertices.clone()).unwrap();
+
+      assert_eq!(edges.len(), 2 * k);
+      assert!(edges.iter().all(|(from, to)| from < to));
+      assert!(graph.vertices.iter().all(|vertex| {
+        edges.iter().filter(|(from, to)| from == vertex || to == vertex).count() == k
+      }));
+    }
+
+    assert_eq!(graph.find_regular_subgraph(4, graph.vertices.clone()), None);
+
+    // A triangle has no perfect matching, but it is 2-regular itself
+    let graph = FullyConnectedGraph {
+      vertices: vec![(0, 0), (0, 1), (1, 0)]
+    };
+
+    assert_eq!(graph.find_regular_subgraph(1, graph.vertices.clone()), None);
+    assert_eq!(
+      graph.find_regular_subgraph(2, graph.vertices.clone()),
+      Some(vec![((0, 0), (0, 1)), ((0, 0), (1, 0)), ((0, 1), (1, 0))])
+    );
+
+    // The divisibility triangles 2, 4, 8 and 3, 9, 27 pass the flow check on the double cover,
+    // since each can be covered by a directed cycle, but have no perfect matching
+    let graph = NumberGraph {
+      numbers: vec![2, 3, 4, 8, 9, 27]
+    };
+
+    assert_eq!(graph.find_regular_subgraph(1, graph.numbers.clone()), None);
+  }
+
+  #[test]
+  fn test_materialize() {
+    let graph = LatticeGraph {
+      blocked: vec![]
+    };
+
+    let (finite_graph, id_map) = graph.materialize(&(0, 0), MaterializeLimit::Depth(2));
+
+    assert_eq!(finite_graph.len(), (13, 32));
+    assert_eq!(id_map.len(), 13);
+    assert!(id_map.iter().all(|(&(x, y), &id)| {
+      x.abs() + y.abs() <= 2 && finite_graph.get_vertex(id) == Some(&(x, y))
+    }));
+    assert_eq!(finite_graph.neighbors(&id_map[&(0, 2)]), vec![id_map[&(0, 1)]]);
+
+    let (finite_graph, id_map) = graph.materialize(&(0, 0), MaterializeLimit::VertexCount(5));
+
+    assert_eq!(finite_graph.len(), (5, 8));
+    assert_eq!(finite_graph.out_degree(id_map[&(0, 0)]), 4);
+    assert_eq!(finite_graph.out_degree(id_map[&(1, 0)]), 1);
+
+    let predicate = |&(x, y): &Position| (0..3).contains(&x) && y == 0;
+    let (finite_graph, id_map) = graph.materialize(&(0, 0), MaterializeLimit::Predicate(&predicate));
+
+    assert_eq!(finite_graph.len(), (3, 4));
+    assert!(id_map.contains_key(&(2, 0)));
+
+    let (finite_graph, _) = graph.materialize(&(0, 0), MaterializeLimit::VertexCount(0));
+
+    assert_eq!(finite_graph.len(), (1, 0));
+  }
+
+  #[test]
+  fn test_materialize_with_edges() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+
+    let (finite_graph, id_map) = graph.materialize_with_edges(&2, MaterializeLimit::Depth(1));
+    let mut edges = finite_graph.into_iter().collect::<Vec<_>>();
+
+    edges.sort();
+
+    assert_eq!(id_map.len(), 4);
+    assert_eq!(edges, vec![(2, 4, 1), (2, 6, 2), (2, 12, 5), (4, 12, 2), (6, 12, 1)]);
+  }
+
+  #[test]
+  fn test_cost_rings() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12, 24]
+    };
+
+    let rings = graph.cost_rings(&1, 2)
+      .map(|mut ring| {
+        ring.sort();
+        ring
+      })
+      .collect::<Vec<_>>();
+
+    assert_eq!(rings, vec![vec![1, 2], vec![3, 4, 6], vec![12, 24]]);
+    assert_eq!(graph.cost_rings(&1, 1).take(2).collect::<Vec<_>>(), vec![vec![1], vec![2]]);
+    assert_eq!(graph.cost_rings(&12, 1).collect::<Vec<_>>(), vec![vec![12], vec![24]]);
+    assert_eq!(graph.cost_rings(&24, 10).collect::<Vec<_>>(), vec![vec![24]]);
+
+    let graph = NumberGraph {
+      numbers: vec![1, 6]
+    };
+
+    assert_eq!(graph.cost_rings(&1, 2).collect::<Vec<_>>(), vec![vec![1], vec![], vec![6]]);
+  }
+
+  #[test]
+  fn test_greedy_color_and_chromatic_number() {
+    let graph = NumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12]
+    };
+
+    let vertices = graph.numbers.clone();
+    let coloring = graph.greedy_color(vertices.clone());
+
+    for &vertex in &vertices {
+      for neighbor in graph.neighbors(&vertex) {
+        assert_ne!(coloring[&vertex], coloring[&neighbor]);
+      }
+    }
+
+    // 1, 2, 4, 12 are pairwise adjacent
+    assert_eq!(graph.chromatic_number_exact(vertices), 4);
+    assert_eq!(graph.chromatic_number_exact(vec![2, 3]), 1);
+    assert_eq!(graph.chromatic_number_exact(vec![]), 0);
+
+    // Edges coming from already colored vertices are respected as well
+    assert_eq!(graph.greedy_color(vec![1, 12]), vec![(1, 0), (12, 1)].into_iter().collect());
+  }
 }
@@ -1,7 +1,11 @@
 use crate::*;
 use std::marker::PhantomData;
 use graph::*;
-use vertex_traverser::{VertexTraverser, PrePostItem, DfsInnerIterEvent, DfsVertexTrav};
+use edge::{Edge, WeightedEdge};
+use vertex_traverser::{
+  VertexTraverser, PrePostItem, TimedPrePostItem, ParentedPrePostItem, DfsInnerIterEvent,
+  DfsVertexTrav, BfsVertexTrav, AstarVertexTrav
+};
 
 pub struct Iter<'a, V, T>(&'a mut T, PhantomData<&'a V>);
 
@@ -19,6 +23,99 @@ impl<'a, V: Vertex, T: VertexTraverser<V>> Iterator for Iter<'a, V, T> {
   }
 }
 
+pub struct DepthIter<'a, 'b, G, V>(&'a mut BfsVertexTrav<'b, G, V>);
+
+impl<'a, 'b, G: Graph<V>, V: Vertex> DepthIter<'a, 'b, G, V> {
+  pub(crate) fn new(traverser: &'a mut BfsVertexTrav<'b, G, V>) -> DepthIter<'a, 'b, G, V> {
+    DepthIter(traverser)
+  }
+}
+
+impl<'a, 'b, G: Graph<V>, V: Vertex> Iterator for DepthIter<'a, 'b, G, V> {
+  type Item = (V, usize);
+
+  fn next(&mut self) -> Option<(V, usize)> {
+    self.0.next().map(|vertex| {
+      let depth = self.0.distance_to(&vertex).unwrap();
+      (vertex, depth)
+    })
+  }
+}
+
+pub struct CostIter<'a, 'b, G, V, E, F>(&'a mut AstarVertexTrav<'b, G, V, E, F>);
+
+impl<'a, 'b, G, V, E, F> CostIter<'a, 'b, G, V, E, F>
+where G: EdgedGraph<V, E>, V: Vertex, E: WeightedEdge, F: Fn(&V) -> E {
+  pub(crate) fn new(traverser: &'a mut AstarVertexTrav<'b, G, V, E, F>) -> CostIter<'a, 'b, G, V, E, F> {
+    CostIter(traverser)
+  }
+}
+
+impl<'a, 'b, G, V, E, F> Iterator for CostIter<'a, 'b, G, V, E, F>
+where G: EdgedGraph<V, E>, V: Vertex, E: WeightedEdge, F: Fn(&V) -> E {
+  type Item = (V, E);
+
+  fn next(&mut self) -> Option<(V, E)> {
+    self.0.next().map(|vertex| {
+      let cost = self.0.cost_to(&vertex).unwrap().clone();
+      (vertex, cost)
+    })
+  }
+}
+
+/// An iterator over consecutive cost bands of equal width around a start vertex. See
+/// [`EdgedGraph::cost_rings`](../trait.EdgedGraph.html#method.cost_rings).
+pub struct CostRings<'a, G, V, E> {
+  traverser: AstarVertexTrav<'a, G, V, E, fn(&V) -> E>,
+  ring_width: E,
+  upper_bound: E,
+  pending: Option<(V, E)>
+}
+
+impl<'a, G, V, E> CostRings<'a, G, V, E>
+where G: EdgedGraph<V, E>, V: Vertex, E: WeightedEdge {
+  pub(crate) fn new(
+    mut traverser: AstarVertexTrav<'a, G, V, E, fn(&V) -> E>,
+    ring_width: E
+  ) -> CostRings<'a, G, V, E> {
+    assert!(ring_width > E::default(), "ring width must be positive");
+
+    let pending = traverser.iter_with_cost().next();
+
+    CostRings {
+      traverser,
+      upper_bound: ring_width.clone(),
+      ring_width,
+      pending
+    }
+  }
+}
+
+impl<'a, G, V, E> Iterator for CostRings<'a, G, V, E>
+where G: EdgedGraph<V, E>, V: Vertex, E: WeightedEdge {
+  type Item = Vec<V>;
+
+  fn next(&mut self) -> Option<Vec<V>> {
+    self.pending.as_ref()?;
+
+    let mut ring = vec![];
+
+    while let Some((vertex, cost)) = self.pending.take() {
+      if cost >= self.upper_bound {
+        self.pending = Some((vertex, cost));
+        break;
+      }
+
+      ring.push(vertex);
+      self.pending = self.traverser.iter_with_cost().next();
+    }
+
+    self.upper_bound = self.upper_bound.clone() + self.ring_width.clone();
+
+    Some(ring)
+  }
+}
+
 pub struct PredecessorIter<'a, V, T>(&'a T, Option<V>);
 
 impl<'a, V: Vertex, T: VertexTraverser<V>> PredecessorIter<'a, V, T> {
@@ -31,11 +128,8 @@ impl<'a, V: Vertex, T: VertexTraverser<V>> Iterator for PredecessorIter<'a, V, T
   type Item = V;
 
   fn next(&mut self) -> Option<V> {
-    self.1.clone().map(|vertex| {
-      let predecessor = self.0.predecessor(&vertex);
-      self.1 = predecessor;
-
-      vertex
+    self.1.clone().inspect(|vertex| {
+      self.1 = self.0.predecessor(vertex);
     })
   }
 }
@@ -63,6 +157,61 @@ impl<'a, 'b, G: Graph<V>, V: Vertex> Iterator for PrePostIter<'a, 'b, G, V> {
   }
 }
 
+pub struct PrePostTimedIter<'a, 'b, G, V>(&'a mut DfsVertexTrav<'b, G, V>);
+
+impl<'a, 'b, G: Graph<V>, V: Vertex> PrePostTimedIter<'a, 'b, G, V> {
+  pub(crate) fn new(traverser: &'a mut DfsVertexTrav<'b, G, V>) -> PrePostTimedIter<'a, 'b, G, V> {
+    PrePostTimedIter(traverser)
+  }
+}
+
+impl<'a, 'b, G: Graph<V>, V: Vertex> Iterator for PrePostTimedIter<'a, 'b, G, V> {
+  type Item = TimedPrePostItem<V>;
+
+  fn next(&mut self) -> Option<TimedPrePostItem<V>> {
+    loop {
+      let event = self.0.next_inner();
+      let time = self.0.clock() - 1;
+
+      match event {
+        Some(DfsInnerIterEvent::PreorderItem(v)) => break Some(TimedPrePostItem::PreorderItem(v, time)),
+        Some(DfsInnerIterEvent::PostorderItem(v)) => break Some(TimedPrePostItem::PostorderItem(v, time)),
+        None => break None,
+        _ => continue
+      }
+    }
+  }
+}
+
+pub struct PrePostParentIter<'a, 'b, G, V>(&'a mut DfsVertexTrav<'b, G, V>);
+
+impl<'a, 'b, G: Graph<V>, V: Vertex> PrePostParentIter<'a, 'b, G, V> {
+  pub(crate) fn new(traverser: &'a mut DfsVertexTrav<'b, G, V>) -> PrePostParentIter<'a, 'b, G, V> {
+    PrePostParentIter(traverser)
+  }
+}
+
+impl<'a, 'b, G: Graph<V>, V: Vertex> Iterator for PrePostParentIter<'a, 'b, G, V> {
+  type Item = ParentedPrePostItem<V>;
+
+  fn next(&mut self) -> Option<ParentedPrePostItem<V>> {
+    loop {
+      match self.0.next_inner() {
+        Some(DfsInnerIterEvent::PreorderItem(vertex)) => {
+          let parent = self.0.predecessor(&vertex);
+          break Some(ParentedPrePostItem::PreorderItem { vertex, parent });
+        },
+        Some(DfsInnerIterEvent::PostorderItem(vertex)) => {
+          let parent = self.0.predecessor(&vertex);
+          break Some(ParentedPrePostItem::PostorderItem { vertex, parent });
+        },
+        None => break None,
+        _ => continue
+      }
+    }
+  }
+}
+
 pub struct PostIter<'a, 'b, G, V>(&'a mut DfsVertexTrav<'b, G, V>);
 
 impl<'a, 'b, G: Graph<V>, V: Vertex> PostIter<'a, 'b, G, V> {
@@ -122,3 +271,137 @@ where T: ReversibleGraph<V> + EdgedGraph<V, E> {
     self.graph.edges(other, vertex)
   }
 }
+
+/// A vertex of [`TurnCosts`](struct.TurnCosts.html), i.e. a vertex of the underlying graph
+/// together with the vertex it has been entered from.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct TurnState<V> {
+  /// The vertex the traversal came from, or `None` for the start vertex.
+  pub previous: Option<V>,
+  pub current: V
+}
+
+impl<V> TurnState<V> {
+  /// Constructs the state of a start vertex, which has no previous vertex.
+  pub fn start(vertex: V) -> TurnState<V> {
+    TurnState {
+      previous: None,
+      current: vertex
+    }
+  }
+}
+
+/// A graph adapter that adds turn costs to the edges of a graph. See
+/// [`EdgedGraph::with_turn_costs`](../trait.EdgedGraph.html#method.with_turn_costs).
+#[derive(Clone)]
+pub struct TurnCosts<'a, T, F> {
+  graph: &'a T,
+  turn_cost: F
+}
+
+impl<'a, T, F> TurnCosts<'a, T, F> {
+  pub(crate) fn new(graph: &'a T, turn_cost: F) -> TurnCosts<'a, T, F> {
+    TurnCosts {
+      graph,
+      turn_cost
+    }
+  }
+
+  /// Returns a smallest path from `start` to `target` in terms of vertices of the
+  /// underlying graph, where the weight of a path includes all of its turn costs,
+  /// or `None` if `target` can't be reached.
+  pub fn construct_path<V, E>(&self, start: &V, target: &V) -> Option<Vec<V>>
+  where
+    V: Vertex,
+    E: WeightedEdge,
+    T: EdgedGraph<V, E>,
+    F: Fn(&V, &V, &V) -> E
+  {
+    let mut traverser = self.dijkstra(&TurnState::start(start.clone()));
+    let state = traverser.iter().find(|state| &state.current == target)?;
+
+    traverser.construct_path(&state)
+    .map(|path| path.into_iter().map(|state| state.current).collect())
+  }
+}
+
+impl<'a, V: Vertex, T: Graph<V>, F> Graph<TurnState<V>> for TurnCosts<'a, T, F> {
+  type NeighborsIterator = Vec<TurnState<V>>;
+
+  fn neighbors(&self, state: &TurnState<V>) -> Vec<TurnState<V>> {
+    self.graph.neighbors(&state.current).into_iter()
+    .map(|neighbor| TurnState {
+      previous: Some(state.current.clone()),
+      current: neighbor
+    })
+    .collect()
+  }
+}
+
+impl<'a, V, E, T, F> EdgedGraph<TurnState<V>, E> for TurnCosts<'a, T, F>
+where
+  V: Vertex,
+  E: WeightedEdge,
+  T: EdgedGraph<V, E>,
+  F: Fn(&V, &V, &V) -> E
+{
+  type EdgesIterator = Vec<E>;
+
+  fn edges(&self, state: &TurnState<V>, other: &TurnState<V>) -> Vec<E> {
+    if other.previous.as_ref() != Some(&state.current) {
+      return vec![];
+    }
+
+    let turn_cost = state.previous.as_ref()
+      .map(|previous| (self.turn_cost)(previous, &state.current, &other.current))
+      .unwrap_or_default();
+
+    self.graph.edges(&state.current, &other.current).into_iter()
+    .map(|edge| edge + turn_cost.clone())
+    .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  type Position = (i32, i32);
+
+  #[test]
+  fn turn_costs_avoid_sharp_turns() {
+    let mut graph = FiniteGraph::<Position, u32>::new();
+    let s = graph.insert_vertex((0, 0));
+    let a = graph.insert_vertex((1, 0));
+    let b = graph.insert_vertex((1, 1));
+    let c = graph.insert_vertex((2, 0));
+    let t = graph.insert_vertex((2, 1));
+
+    // The zigzag route via `a` and `b` is shorter, but has two turns instead of one
+    graph.insert_edge(s, a, 1).unwrap();
+    graph.insert_edge(a, b, 1).unwrap();
+    graph.insert_edge(b, t, 1).unwrap();
+    graph.insert_edge(s, c, 4).unwrap();
+    graph.insert_edge(c, t, 1).unwrap();
+
+    let direction = |from: &Id, to: &Id| {
+      let ((x1, y1), (x2, y2)) = (graph.get_vertex(*from).unwrap(), graph.get_vertex(*to).unwrap());
+      ((x2 - x1).signum(), (y2 - y1).signum())
+    };
+
+    let turn_costs = graph.with_turn_costs(|previous, current, next| {
+      if direction(previous, current) == direction(current, next) { 0 } else { 5 }
+    });
+
+    assert_eq!(graph.dijkstra(&s).construct_path(&t), Some(vec![s, a, b, t]));
+    assert_eq!(turn_costs.construct_path(&s, &t), Some(vec![s, c, t]));
+    assert_eq!(turn_costs.construct_path(&s, &s), Some(vec![s]));
+    assert_eq!(turn_costs.construct_path(&t, &s), None);
+
+    let mut traverser = turn_costs.dijkstra(&TurnState::start(s));
+    let state = TurnState { previous: Some(c), current: t };
+
+    traverser.construct_path(&state).unwrap();
+    assert_eq!(traverser.cost_to(&state), Some(&10));
+  }
+}
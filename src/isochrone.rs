@@ -0,0 +1,74 @@
+use crate::*;
+use std::error::Error;
+use std::fmt;
+use graph::EdgedGraph;
+use vertex::Vertex;
+use edge::WeightedEdge;
+
+/// The vertices whose smallest weight sums from a start vertex lie between the previous
+/// threshold (exclusive) and `threshold` (inclusive). See
+/// [`EdgedGraph::isochrones`](./trait.EdgedGraph.html#method.isochrones).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IsochroneBand<V, E> {
+  pub threshold: E,
+  /// The vertices of the band together with their weight sums, in ascending order of
+  /// their weight sums.
+  pub vertices: Vec<(V, E)>
+}
+
+/// The error returned by [`EdgedGraph::isochrones`](./trait.EdgedGraph.html#method.isochrones)
+/// if the given thresholds are not sorted in ascending order.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct UnsortedThresholdsError {
+  /// The index of the first threshold that is smaller than its predecessor.
+  pub index: usize
+}
+
+impl fmt::Display for UnsortedThresholdsError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "threshold at index {} is smaller than its predecessor", self.index)
+  }
+}
+
+impl Error for UnsortedThresholdsError {}
+
+/// See [`EdgedGraph::isochrones`](./trait.EdgedGraph.html#method.isochrones).
+pub(crate) fn isochrones<G, V, E>(
+  graph: &G,
+  start: &V,
+  thresholds: &[E]
+) -> Result<Vec<IsochroneBand<V, E>>, UnsortedThresholdsError>
+where
+  G: EdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge
+{
+  if let Some(index) = (1..thresholds.len()).find(|&i| thresholds[i] < thresholds[i - 1]) {
+    return Err(UnsortedThresholdsError { index });
+  }
+
+  let mut bands = thresholds.iter()
+    .map(|threshold| IsochroneBand { threshold: threshold.clone(), vertices: vec![] })
+    .collect::<Vec<_>>();
+  let max_threshold = match thresholds.last() {
+    Some(threshold) => threshold,
+    None => return Ok(bands)
+  };
+
+  let mut band_index = 0;
+
+  for (vertex, cost) in graph.dijkstra(start).iter_with_cost() {
+    if &cost > max_threshold {
+      break;
+    }
+
+    // Costs are ascending, so the matching band never lies before the current one
+    while cost > bands[band_index].threshold {
+      band_index += 1;
+    }
+
+    bands[band_index].vertices.push((vertex, cost));
+  }
+
+  Ok(bands)
+}
@@ -1,12 +1,29 @@
 mod graph;
+mod try_graph;
 mod finite_graph;
 mod vertex;
 mod edge;
+mod dyn_graph;
 mod vertex_container;
+mod bidirectional_search;
+mod isochrone;
+mod materialize;
+mod regular_subgraph;
+mod flow_network;
+mod chordal;
+mod trace;
+mod batch;
+#[cfg(feature = "rand")]
+mod spanning_tree;
 pub mod vertex_traverser;
 pub mod graph_adapters;
 
 pub use graph::*;
+pub use try_graph::*;
 pub use finite_graph::*;
 pub use vertex::*;
 pub use edge::*;
+pub use dyn_graph::*;
+pub use isochrone::*;
+pub use materialize::*;
+pub use trace::*;
@@ -0,0 +1,74 @@
+use crate::*;
+use std::collections::{HashMap, VecDeque};
+use finite_graph::{FiniteGraph, Id};
+use vertex::Vertex;
+
+/// Determines which vertices are copied by [`Graph::materialize`](./trait.Graph.html#method.materialize)
+/// and [`EdgedGraph::materialize_with_edges`](./trait.EdgedGraph.html#method.materialize_with_edges).
+/// The start vertex is always copied.
+pub enum MaterializeLimit<'a, V> {
+  /// Copies the given number of vertices in breadth-first order.
+  VertexCount(usize),
+  /// Copies all vertices that can be reached using at most the given number of edges.
+  Depth(usize),
+  /// Copies all vertices for which the predicate returns `true` and that can be reached by
+  /// traveling along such vertices. On infinite graphs, the predicate has to reject all but
+  /// finitely many vertices.
+  Predicate(&'a dyn Fn(&V) -> bool)
+}
+
+/// See [`EdgedGraph::materialize_with_edges`](./trait.EdgedGraph.html#method.materialize_with_edges).
+pub(crate) fn materialize<V, E, I>(
+  start: &V,
+  limit: MaterializeLimit<'_, V>,
+  neighbors: impl Fn(&V) -> I
+) -> (FiniteGraph<V, E>, HashMap<V, Id>)
+where
+  V: Vertex,
+  I: IntoIterator<Item = (V, E)>
+{
+  let mut graph = FiniteGraph::new();
+  let mut id_map = HashMap::new();
+  let mut queue = VecDeque::new();
+  let mut expanded = vec![];
+
+  id_map.insert(start.clone(), graph.insert_vertex(start.clone()));
+  queue.push_back((start.clone(), 0));
+
+  while let Some((vertex, depth)) = queue.pop_front() {
+    let neighbors = neighbors(&vertex).into_iter().collect::<Vec<_>>();
+
+    for (neighbor, _) in &neighbors {
+      if id_map.contains_key(neighbor) {
+        continue;
+      }
+
+      let admitted = match limit {
+        MaterializeLimit::VertexCount(count) => id_map.len() < count,
+        MaterializeLimit::Depth(max_depth) => depth < max_depth,
+        MaterializeLimit::Predicate(predicate) => predicate(neighbor)
+      };
+
+      if admitted {
+        id_map.insert(neighbor.clone(), graph.insert_vertex(neighbor.clone()));
+        queue.push_back((neighbor.clone(), depth + 1));
+      }
+    }
+
+    expanded.push((vertex, neighbors));
+  }
+
+  // Edges can only be inserted once all copied vertices are known, edges to other
+  // vertices are dropped
+  for (vertex, neighbors) in expanded {
+    let from = id_map[&vertex];
+
+    for (neighbor, edge) in neighbors {
+      if let Some(&to) = id_map.get(&neighbor) {
+        graph.insert_edge(from, to, edge);
+      }
+    }
+  }
+
+  (graph, id_map)
+}
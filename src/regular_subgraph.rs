@@ -0,0 +1,127 @@
+use crate::*;
+use std::collections::HashMap;
+use graph::Graph;
+use vertex::Vertex;
+use flow_network::FlowNetwork;
+
+/// See [`Graph::find_regular_subgraph`](./trait.Graph.html#method.find_regular_subgraph).
+pub(crate) fn find_regular_subgraph<G, V>(graph: &G, k: usize, all_vertices: Vec<V>) -> Option<Vec<(V, V)>>
+where
+  G: Graph<V>,
+  V: Vertex
+{
+  let index_map = all_vertices.iter().cloned()
+    .enumerate()
+    .map(|(index, vertex)| (vertex, index))
+    .collect::<HashMap<_, _>>();
+  let n = all_vertices.len();
+  let mut adjacency = vec![vec![]; n];
+
+  // Edge directions are ignored, so an edge in either direction connects two vertices
+  for (index, vertex) in all_vertices.iter().enumerate() {
+    for neighbor in graph.neighbors(vertex) {
+      match index_map.get(&neighbor) {
+        Some(&other) if other != index => {
+          adjacency[index].push(other);
+          adjacency[other].push(index);
+        },
+        _ => {}
+      }
+    }
+  }
+
+  for neighbors in adjacency.iter_mut() {
+    neighbors.sort();
+    neighbors.dedup();
+  }
+
+  if (n * k) % 2 == 1 || adjacency.iter().any(|neighbors| neighbors.len() < k) || !has_double_cover_factor(&adjacency, k) {
+    return None;
+  }
+
+  let mut remaining = vec![k; n];
+  let mut edges = vec![];
+
+  if !select_edges(&adjacency, &mut remaining, &mut edges, 0) {
+    return None;
+  }
+
+  Some(
+    edges.into_iter()
+    .map(|(from, to)| (all_vertices[from].clone(), all_vertices[to].clone()))
+    .collect()
+  )
+}
+
+/// Returns `true` if the bipartite double cover of the graph, which has an outgoing and an
+/// incoming copy of every vertex, has a `k`-regular spanning subgraph. Every `k`-regular
+/// subgraph of the graph yields one with both orientations of its edges, so this is a necessary
+/// condition that rules out most graphs without a solution in `O(k * n * m)` time.
+fn has_double_cover_factor(adjacency: &[Vec<usize>], k: usize) -> bool {
+  let n = adjacency.len();
+  // Node 0 is the source, node 1 the sink, followed by an outgoing and an incoming node
+  // for every vertex
+  let (source, sink) = (0, 1);
+  let mut network = FlowNetwork::new(2 * n + 2);
+
+  for (index, neighbors) in adjacency.iter().enumerate() {
+    network.add_arc(source, 2 + index, k, ());
+    network.add_arc(2 + n + index, sink, k, ());
+
+    for &other in neighbors {
+      network.add_arc(2 + index, 2 + n + other, 1, ());
+    }
+  }
+
+  network.max_flow(source, sink, n * k) == n * k
+}
+
+/// Selects the edges of `vertex` and all later vertices by backtracking, so that vertex `i`
+/// gets `remaining[i]` more edges. Edges to earlier vertices have already been decided, so
+/// only edges to later vertices are chosen here.
+fn select_edges(adjacency: &[Vec<usize>], remaining: &mut [usize], edges: &mut Vec<(usize, usize)>, vertex: usize) -> bool {
+  if vertex == adjacency.len() {
+    return true;
+  }
+
+  let candidates = adjacency[vertex].iter()
+    .copied()
+    .filter(|&other| other > vertex && remaining[other] > 0)
+    .collect::<Vec<_>>();
+
+  select_candidates(adjacency, remaining, edges, vertex, &candidates)
+}
+
+/// Selects the remaining edges of `vertex` among `candidates` in every possible way and
+/// continues with the next vertex.
+fn select_candidates(
+  adjacency: &[Vec<usize>],
+  remaining: &mut [usize],
+  edges: &mut Vec<(usize, usize)>,
+  vertex: usize,
+  candidates: &[usize]
+) -> bool {
+  if remaining[vertex] == 0 {
+    return select_edges(adjacency, remaining, edges, vertex + 1);
+  }
+
+  for (i, &other) in candidates.iter().enumerate() {
+    if candidates.len() - i < remaining[vertex] {
+      break;
+    }
+
+    remaining[vertex] -= 1;
+    remaining[other] -= 1;
+    edges.push((vertex, other));
+
+    if select_candidates(adjacency, remaining, edges, vertex, &candidates[i + 1..]) {
+      return true;
+    }
+
+    edges.pop();
+    remaining[vertex] += 1;
+    remaining[other] += 1;
+  }
+
+  false
+}
@@ -0,0 +1,95 @@
+use crate::*;
+use std::collections::{HashMap, VecDeque};
+use rand::{Rng, RngCore};
+use graph::Graph;
+use vertex::Vertex;
+
+/// See [`Graph::random_spanning_tree`](./trait.Graph.html#method.random_spanning_tree).
+pub(crate) fn random_spanning_tree<G, V>(
+  graph: &G,
+  root: &V,
+  all_vertices: Vec<V>,
+  mut rng: impl RngCore
+) -> HashMap<V, Option<V>>
+where
+  G: Graph<V>,
+  V: Vertex
+{
+  let mut vertices = vec![root.clone()];
+  let mut index_map = HashMap::new();
+
+  index_map.insert(root.clone(), 0);
+
+  for vertex in all_vertices {
+    if !index_map.contains_key(&vertex) {
+      index_map.insert(vertex.clone(), vertices.len());
+      vertices.push(vertex);
+    }
+  }
+
+  let n = vertices.len();
+  let adjacency = vertices.iter()
+    .map(|vertex| {
+      graph.neighbors(vertex).into_iter()
+      .filter_map(|neighbor| index_map.get(&neighbor).copied())
+      .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+
+  // Random walks must only enter vertices that can reach the root, or they never end
+  let mut reverse_adjacency = vec![vec![]; n];
+
+  for (index, neighbors) in adjacency.iter().enumerate() {
+    for &neighbor in neighbors {
+      reverse_adjacency[neighbor].push(index);
+    }
+  }
+
+  let mut reaches_root = vec![false; n];
+  let mut queue = VecDeque::new();
+
+  reaches_root[0] = true;
+  queue.push_back(0);
+
+  while let Some(index) = queue.pop_front() {
+    for &other in &reverse_adjacency[index] {
+      if !reaches_root[other] {
+        reaches_root[other] = true;
+        queue.push_back(other);
+      }
+    }
+  }
+
+  let adjacency = adjacency.into_iter()
+    .map(|neighbors| neighbors.into_iter().filter(|&neighbor| reaches_root[neighbor]).collect::<Vec<_>>())
+    .collect::<Vec<_>>();
+  let mut in_tree = vec![false; n];
+  let mut next = vec![None; n];
+
+  in_tree[0] = true;
+
+  for start in (0..n).filter(|&index| reaches_root[index]) {
+    // Overwriting the successor of revisited vertices erases the loops of the walk
+    let mut index = start;
+
+    while !in_tree[index] {
+      let neighbors = &adjacency[index];
+      let successor = neighbors[rng.gen_range(0..neighbors.len())];
+
+      next[index] = Some(successor);
+      index = successor;
+    }
+
+    index = start;
+
+    while !in_tree[index] {
+      in_tree[index] = true;
+      index = next[index].unwrap();
+    }
+  }
+
+  (0..n)
+  .filter(|&index| in_tree[index])
+  .map(|index| (vertices[index].clone(), next[index].map(|successor| vertices[successor].clone())))
+  .collect()
+}
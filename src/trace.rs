@@ -0,0 +1,199 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+/// A step of a traversal, recorded by a [`TraversalRecorder`](struct.TraversalRecorder.html).
+/// Costs are weight sums from the start vertex, or the number of edges for breadth-first
+/// traversers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TraceEvent<V, E> {
+  /// The vertex has been taken from the queue and is visited with the cost.
+  Pop { vertex: V, cost: E },
+  /// The vertex has been taken from the queue, but is skipped since it has been reached with
+  /// a lower cost in the meantime.
+  Skip { vertex: V, cost: E },
+  /// The edge from `from` to `to` has been examined. The tentative cost of `to` was
+  /// `old_cost` and `new_cost` is the cost of the path through `from`, which is only taken
+  /// if it's lower.
+  Relax { from: V, to: V, old_cost: Option<E>, new_cost: E },
+  /// The vertex has been put on the queue with the score, i.e. its tentative cost plus the
+  /// estimate to the target.
+  Push { vertex: V, score: E }
+}
+
+impl<V: fmt::Debug, E: fmt::Debug> fmt::Display for TraceEvent<V, E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TraceEvent::Pop { vertex, cost } => write!(f, "pop {:?} at {:?}", vertex, cost),
+      TraceEvent::Skip { vertex, cost } => write!(f, "skip {:?} at {:?}", vertex, cost),
+      TraceEvent::Relax { from, to, old_cost: Some(old_cost), new_cost } => {
+        write!(f, "relax {:?} -> {:?}: {:?} -> {:?}", from, to, old_cost, new_cost)
+      },
+      TraceEvent::Relax { from, to, old_cost: None, new_cost } => {
+        write!(f, "relax {:?} -> {:?}: none -> {:?}", from, to, new_cost)
+      },
+      TraceEvent::Push { vertex, score } => write!(f, "push {:?} with {:?}", vertex, score)
+    }
+  }
+}
+
+/// Collects the [events](enum.TraceEvent.html) of a traversal to replay what it did, e.g. to
+/// find out why a path has been chosen. Attach it to a traverser with
+/// [`AstarVertexTrav::with_recorder`](vertex_traverser/struct.AstarVertexTrav.html#method.with_recorder)
+/// or [`BfsVertexTrav::with_recorder`](vertex_traverser/struct.BfsVertexTrav.html#method.with_recorder).
+///
+/// Only the most recent `capacity` events are kept, older events are dropped.
+///
+/// # Example
+///
+/// ```
+/// # use graph_iter::{EdgedGraph, FiniteGraph, TraceEvent, TraversalRecorder};
+/// # use graph_iter::vertex_traverser::VertexTraverser;
+/// let mut graph = FiniteGraph::new();
+/// let a = graph.insert_vertex('a');
+/// let b = graph.insert_vertex('b');
+///
+/// graph.insert_edge(a, b, 2).unwrap();
+///
+/// let mut recorder = TraversalRecorder::new(100);
+///
+/// graph.dijkstra(&a).with_recorder(&mut recorder).construct_path(&b);
+///
+/// assert_eq!(recorder.dump()[1], TraceEvent::Relax { from: a, to: b, old_cost: None, new_cost: 2 });
+/// ```
+#[derive(Clone, Debug)]
+pub struct TraversalRecorder<V, E> {
+  events: VecDeque<TraceEvent<V, E>>,
+  capacity: usize,
+  dropped: usize
+}
+
+impl<V, E> TraversalRecorder<V, E> {
+  /// Creates an empty recorder that keeps at most `capacity` events.
+  pub fn new(capacity: usize) -> TraversalRecorder<V, E> {
+    TraversalRecorder {
+      events: VecDeque::with_capacity(capacity),
+      capacity,
+      dropped: 0
+    }
+  }
+
+  pub(crate) fn record(&mut self, event: TraceEvent<V, E>) {
+    if self.capacity == 0 {
+      self.dropped += 1;
+      return;
+    }
+
+    if self.events.len() == self.capacity {
+      self.events.pop_front();
+      self.dropped += 1;
+    }
+
+    self.events.push_back(event);
+  }
+
+  /// Returns the number of events that have been dropped because the recorder was full.
+  pub fn dropped_events(&self) -> usize {
+    self.dropped
+  }
+
+  /// Removes all events.
+  pub fn clear(&mut self) {
+    self.events.clear();
+    self.dropped = 0;
+  }
+}
+
+impl<V: Clone, E: Clone> TraversalRecorder<V, E> {
+  /// Returns the kept events from oldest to newest.
+  pub fn dump(&self) -> Vec<TraceEvent<V, E>> {
+    self.events.iter().cloned().collect()
+  }
+}
+
+impl<V: fmt::Debug, E: fmt::Debug> fmt::Display for TraversalRecorder<V, E> {
+  /// Writes the kept events from oldest to newest, one per line.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.dropped > 0 {
+      writeln!(f, "({} earlier events dropped)", self.dropped)?;
+    }
+
+    for event in &self.events {
+      writeln!(f, "{}", event)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{EdgedGraph, FiniteGraph, Graph};
+  use crate::vertex_traverser::VertexTraverser;
+
+  #[test]
+  fn recorder_replays_dijkstra_relaxations() {
+    let mut graph = FiniteGraph::new();
+    let v = "abcd".chars().map(|name| graph.insert_vertex(name)).collect::<Vec<_>>();
+
+    graph.insert_edge(v[0], v[1], 4).unwrap();
+    graph.insert_edge(v[0], v[2], 1).unwrap();
+    graph.insert_edge(v[2], v[1], 2).unwrap();
+    graph.insert_edge(v[1], v[3], 1).unwrap();
+
+    let mut recorder = TraversalRecorder::new(100);
+    let path = graph.dijkstra(&v[0]).with_recorder(&mut recorder).iter().collect::<Vec<_>>();
+
+    assert_eq!(path, vec![v[0], v[2], v[1], v[3]]);
+    assert_eq!(recorder.dump(), vec![
+      TraceEvent::Pop { vertex: v[0], cost: 0 },
+      TraceEvent::Relax { from: v[0], to: v[1], old_cost: None, new_cost: 4 },
+      TraceEvent::Relax { from: v[0], to: v[2], old_cost: None, new_cost: 1 },
+      TraceEvent::Push { vertex: v[1], score: 4 },
+      TraceEvent::Push { vertex: v[2], score: 1 },
+      TraceEvent::Pop { vertex: v[2], cost: 1 },
+      TraceEvent::Relax { from: v[2], to: v[1], old_cost: Some(4), new_cost: 3 },
+      TraceEvent::Push { vertex: v[1], score: 3 },
+      TraceEvent::Pop { vertex: v[1], cost: 3 },
+      TraceEvent::Relax { from: v[1], to: v[3], old_cost: None, new_cost: 4 },
+      TraceEvent::Push { vertex: v[3], score: 4 },
+      TraceEvent::Skip { vertex: v[1], cost: 4 },
+      TraceEvent::Pop { vertex: v[3], cost: 4 }
+    ]);
+
+    let mut recorder = TraversalRecorder::new(3);
+
+    graph.dijkstra(&v[0]).with_recorder(&mut recorder).construct_path(&v[1]);
+
+    assert_eq!(recorder.dropped_events(), 8);
+    assert_eq!(
+      recorder.to_string(),
+      format!("(8 earlier events dropped)\npop {:?} at 3\nrelax {:?} -> {:?}: none -> 4\npush {:?} with 4\n", v[1], v[1], v[3], v[3])
+    );
+  }
+
+  #[test]
+  fn recorder_replays_bfs() {
+    let mut graph = FiniteGraph::<(), ()>::new();
+    let v = (0..3).map(|_| graph.insert_vertex(())).collect::<Vec<_>>();
+
+    graph.insert_bi_edge(v[0], v[1], ()).unwrap();
+    graph.insert_edge(v[1], v[2], ()).unwrap();
+
+    let mut recorder = TraversalRecorder::new(10);
+    let mut traverser = graph.bfs(&v[0]).with_recorder(&mut recorder);
+
+    assert_eq!(traverser.construct_path(&v[2]), Some(v.clone()));
+    assert_eq!(traverser.distance_to(&v[2]), Some(2));
+    assert_eq!(recorder.dump(), vec![
+      TraceEvent::Pop { vertex: v[0], cost: 0 },
+      TraceEvent::Relax { from: v[0], to: v[1], old_cost: None, new_cost: 1 },
+      TraceEvent::Push { vertex: v[1], score: 1 },
+      TraceEvent::Pop { vertex: v[1], cost: 1 },
+      TraceEvent::Relax { from: v[1], to: v[0], old_cost: Some(0), new_cost: 2 },
+      TraceEvent::Relax { from: v[1], to: v[2], old_cost: None, new_cost: 2 },
+      TraceEvent::Push { vertex: v[2], score: 2 },
+      TraceEvent::Pop { vertex: v[2], cost: 2 }
+    ]);
+  }
+}
@@ -0,0 +1,184 @@
+use crate::*;
+use std::collections::HashSet;
+use vertex::Vertex;
+use edge::{Edge, WeightedEdge};
+use vertex_traverser::{TryBfsVertexTrav, TryDijkstraVertexTrav};
+
+/// Represents a directed, potentially infinite, graph whose neighbors can't always be
+/// generated, e.g. because generating them involves I/O.
+///
+/// This is the fallible counterpart of [`Graph<V>`](./trait.Graph.html). Its traversers
+/// implement [`TryVertexTraverser`](./vertex_traverser/trait.TryVertexTraverser.html), which
+/// stops advancing as soon as an error occurs, so the traversal can be retried later.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::Cell;
+/// use graph_iter::TryGraph;
+/// use graph_iter::vertex_traverser::TryVertexTraverser;
+///
+/// /// A line of integers, where generating neighbors fails every other time.
+/// struct FlakyGraph {
+///   fail: Cell<bool>
+/// }
+///
+/// impl TryGraph<i32> for FlakyGraph {
+///   type NeighborsIterator = Vec<i32>;
+///   type Error = String;
+///
+///   fn try_neighbors(&self, &vertex: &i32) -> Result<Vec<i32>, String> {
+///     self.fail.set(!self.fail.get());
+///
+///     if self.fail.get() {
+///       Err(format!("failed to load neighbors of {}", vertex))
+///     } else {
+///       Ok(vec![vertex - 1, vertex + 1])
+///     }
+///   }
+/// }
+///
+/// let graph = FlakyGraph { fail: Cell::new(false) };
+/// let mut traverser = graph.try_bfs(&0);
+///
+/// assert!(traverser.try_construct_path(&2).is_err());
+///
+/// // Retrying continues where the traversal has stopped
+/// let path = loop {
+///   if let Ok(path) = traverser.try_construct_path(&2) {
+///     break path;
+///   }
+/// };
+///
+/// assert_eq!(path, Some(vec![0, 1, 2]));
+/// ```
+pub trait TryGraph<V: Vertex> where Self: Sized {
+  type NeighborsIterator: IntoIterator<Item = V>;
+  type Error;
+
+  /// Tries to generate a list of adjacent vertices that can be reached from `vertex` by
+  /// traveling along an edge.
+  fn try_neighbors(&self, vertex: &V) -> Result<Self::NeighborsIterator, Self::Error>;
+
+  /// Returns a [`TryVertexTraverser`](./vertex_traverser/trait.TryVertexTraverser.html) that
+  /// iterates the graph vertices in a breadth-first manner.
+  fn try_bfs(&self, start: &V) -> TryBfsVertexTrav<'_, Self, V> {
+    TryBfsVertexTrav::new(self, start.clone())
+  }
+}
+
+/// The fallible counterpart of [`EdgedGraph<V, E>`](./trait.EdgedGraph.html).
+pub trait TryEdgedGraph<V: Vertex, E: Edge>: TryGraph<V> {
+  type EdgesIterator: IntoIterator<Item = E>;
+
+  /// Tries to generate a list of edges that connect `vertex` with `other`.
+  fn try_edges(&self, vertex: &V, other: &V) -> Result<Self::EdgesIterator, Self::Error>;
+
+  /// Tries to generate a list of adjacent vertices of `vertex`, each paired with an edge that
+  /// connects `vertex` with it, like
+  /// [`EdgedGraph::neighbors_with_edges`](./trait.EdgedGraph.html#method.neighbors_with_edges).
+  fn try_neighbors_with_edges(&self, vertex: &V) -> Result<Vec<(V, E)>, Self::Error> {
+    let mut visited = HashSet::new();
+    let mut result = vec![];
+
+    for neighbor in self.try_neighbors(vertex)? {
+      if visited.insert(neighbor.clone()) {
+        for edge in self.try_edges(vertex, &neighbor)? {
+          result.push((neighbor.clone(), edge));
+        }
+      }
+    }
+
+    Ok(result)
+  }
+
+  /// Returns a [`TryVertexTraverser`](./vertex_traverser/trait.TryVertexTraverser.html) that
+  /// iterates the graph vertices in a smallest-weight-sum-first manner, like
+  /// [`EdgedGraph::dijkstra`](./trait.EdgedGraph.html#method.dijkstra).
+  fn try_dijkstra(&self, start: &V) -> TryDijkstraVertexTrav<'_, Self, V, E>
+  where E: WeightedEdge {
+    TryDijkstraVertexTrav::new(self, start.clone())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::cell::RefCell;
+  use vertex_traverser::TryVertexTraverser;
+
+  /// Numbers with edges to their multiples, where generating the neighbors of
+  /// vertices in `failing` fails once.
+  struct FlakyNumberGraph {
+    numbers: Vec<usize>,
+    failing: RefCell<HashSet<usize>>
+  }
+
+  impl TryGraph<usize> for FlakyNumberGraph {
+    type NeighborsIterator = Vec<usize>;
+    type Error = usize;
+
+    fn try_neighbors(&self, vertex: &usize) -> Result<Vec<usize>, usize> {
+      if self.failing.borrow_mut().remove(vertex) {
+        return Err(*vertex);
+      }
+
+      Ok(
+        self.numbers.iter()
+        .filter(|&v| v != vertex && v.is_multiple_of(*vertex))
+        .copied()
+        .collect()
+      )
+    }
+  }
+
+  impl TryEdgedGraph<usize, usize> for FlakyNumberGraph {
+    type EdgesIterator = Vec<usize>;
+
+    fn try_edges(&self, vertex: &usize, other: &usize) -> Result<Vec<usize>, usize> {
+      Ok(vec![other / vertex - 1])
+    }
+  }
+
+  fn flaky_graph(failing: &[usize]) -> FlakyNumberGraph {
+    FlakyNumberGraph {
+      numbers: vec![1, 2, 3, 4, 6, 12],
+      failing: RefCell::new(failing.iter().copied().collect())
+    }
+  }
+
+  #[test]
+  fn try_bfs_resumes_after_errors() {
+    let graph = flaky_graph(&[1, 3]);
+    let mut traverser = graph.try_bfs(&1);
+
+    assert_eq!(traverser.try_next(), Err(1));
+    assert_eq!(traverser.try_next(), Ok(Some(1)));
+    assert_eq!(traverser.try_next(), Ok(Some(2)));
+    assert_eq!(traverser.try_next(), Err(3));
+    assert_eq!(traverser.distance_to(&12), Some(1));
+    assert_eq!(traverser.try_next(), Ok(Some(3)));
+
+    let mut vertices = vec![];
+
+    while let Some(vertex) = traverser.try_next().unwrap() {
+      vertices.push(vertex);
+    }
+
+    assert_eq!(vertices, vec![4, 6, 12]);
+  }
+
+  #[test]
+  fn try_dijkstra_matches_dijkstra_despite_errors() {
+    let graph = flaky_graph(&[2, 4]);
+    let mut traverser = graph.try_dijkstra(&1);
+
+    assert_eq!(traverser.try_construct_path(&12), Err(2));
+    assert_eq!(traverser.cost_to(&1), Some(&0));
+    assert_eq!(traverser.try_construct_path(&12), Err(4));
+    assert_eq!(traverser.try_construct_path(&3), Ok(Some(vec![1, 3])));
+    assert_eq!(traverser.try_construct_path(&12).unwrap().unwrap().len(), 4);
+    assert_eq!(traverser.cost_to(&12), Some(&4));
+    assert_eq!(traverser.try_construct_path(&5), Ok(None));
+  }
+}
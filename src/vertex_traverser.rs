@@ -1,11 +1,15 @@
 use crate::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::iter;
+use std::ops::Deref;
+use std::sync::Arc;
 use graph::EdgedGraph;
+use try_graph::{TryGraph, TryEdgedGraph};
 use vertex::Vertex;
 use edge::WeightedEdge;
+use trace::{TraceEvent, TraversalRecorder};
 use vertex_container::{VertexContainer, DfsContainer, BfsContainer, AstarContainer};
-use graph_adapters::{Iter, PredecessorIter, PrePostIter, PostIter};
+use graph_adapters::{Iter, DepthIter, CostIter, PredecessorIter, PrePostIter, PrePostTimedIter, PrePostParentIter, PostIter};
 
 /// An interface for dealing with vertex traversers over a graph.
 pub trait VertexTraverser<V: Vertex> where Self: Sized {
@@ -17,8 +21,24 @@ pub trait VertexTraverser<V: Vertex> where Self: Sized {
 
   /// Returns the predecessor vertex of the given vertex
   /// or `None` if `vertex` is the start vertex or has not been reached yet.
+  ///
+  /// This method never advances the traverser, so it returns `None` for vertices that are
+  /// reachable but have not been discovered so far. Depending on the traverser, the predecessor
+  /// of a discovered vertex may still change until the vertex is [`reached`](#method.reached).
+  /// Use [`predecessor_or_search`](#method.predecessor_or_search) to search on demand.
   fn predecessor(&self, vertex: &V) -> Option<V>;
 
+  /// Advances the traverser until `vertex` has been reached or the graph is exhausted,
+  /// and returns its predecessor vertex, or `None` if `vertex` is the start vertex or
+  /// cannot be reached.
+  fn predecessor_or_search(&mut self, vertex: &V) -> Option<V> {
+    if !self.reached(vertex) {
+      self.iter().find(|v| v == vertex);
+    }
+
+    self.predecessor(vertex)
+  }
+
   /// Returns an [`Iterator`](https://doc.rust-lang.org/std/iter/trait.Iterator.html)
   /// that lets you iterate over the traverser.
   fn iter(&mut self) -> Iter<'_, V, Self> {
@@ -31,43 +51,194 @@ pub trait VertexTraverser<V: Vertex> where Self: Sized {
     PredecessorIter::new(self, vertex.clone())
   }
 
+  /// Returns `true` if `vertex` has been reached and its predecessor chain back to the
+  /// start vertex will not change anymore by advancing the traverser.
+  fn reached(&self, vertex: &V) -> bool {
+    vertex == &self.first() || self.predecessor(vertex).is_some()
+  }
+
   /// Traverses through the graph until we reach `target` and returns a path from start vertex
   /// to `target`, or `None` if the `target` vertex cannot be reached.
+  ///
+  /// The path to the start vertex consists of the start vertex only. This method can be called
+  /// multiple times with different targets; it will only advance the traverser if `target` has
+  /// not been reached yet. If the traverser has been restarted, like with
+  /// [`BfsVertexTrav::restart`](struct.BfsVertexTrav.html#method.restart), the path starts at
+  /// the start vertex of the component of `target` instead of [`first`](#tymethod.first).
   fn construct_path(&mut self, target: &V) -> Option<Vec<V>> {
-    if self.predecessor(target).is_none() {
+    if !self.reached(target) {
       self.iter().find(|v| v == target);
     }
 
+    if !self.reached(target) {
+      return None;
+    }
+
     let mut path = self.predecessor_iter(target).collect::<Vec<_>>();
     path.reverse();
 
-    if path.len() > 1 || target == &self.first() {
-      Some(path)
-    } else {
-      None
+    Some(path)
+  }
+
+  /// Traverses through the graph until all `targets` are reached and returns a path from
+  /// start vertex to each target in the same order, or `None` for targets that cannot be reached.
+  /// The traverser is only advanced as far as necessary for the farthest target.
+  fn construct_paths(&mut self, targets: &[V]) -> Vec<Option<Vec<V>>> {
+    let mut remaining = targets.iter()
+      .filter(|&target| !self.reached(target))
+      .cloned()
+      .collect::<HashSet<_>>();
+
+    while !remaining.is_empty() {
+      match self.next() {
+        Some(vertex) => remaining.remove(&vertex),
+        None => break
+      };
+    }
+
+    targets.iter()
+    .map(|target| self.construct_path(target))
+    .collect()
+  }
+}
+
+/// A handle to the graph a traverser operates on, either borrowed or shared.
+pub(crate) enum GraphRef<'a, G> {
+  Borrowed(&'a G),
+  Shared(Arc<G>)
+}
+
+impl<'a, G> Clone for GraphRef<'a, G> {
+  fn clone(&self) -> GraphRef<'a, G> {
+    match self {
+      GraphRef::Borrowed(graph) => GraphRef::Borrowed(graph),
+      GraphRef::Shared(graph) => GraphRef::Shared(graph.clone())
+    }
+  }
+}
+
+impl<'a, G> Deref for GraphRef<'a, G> {
+  type Target = G;
+
+  fn deref(&self) -> &G {
+    match self {
+      GraphRef::Borrowed(graph) => graph,
+      GraphRef::Shared(graph) => graph
     }
   }
 }
 
+impl<'a, G> From<&'a G> for GraphRef<'a, G> {
+  fn from(graph: &'a G) -> GraphRef<'a, G> {
+    GraphRef::Borrowed(graph)
+  }
+}
+
+impl<G> From<Arc<G>> for GraphRef<'static, G> {
+  fn from(graph: Arc<G>) -> GraphRef<'static, G> {
+    GraphRef::Shared(graph)
+  }
+}
+
 #[derive(Clone)]
 pub struct BfsVertexTrav<'a, G, V> {
-  graph: &'a G,
+  graph: GraphRef<'a, G>,
   start: V,
   queue: BfsContainer<V>,
-  predecessor_map: HashMap<V, Option<V>>
+  // The depths of consecutive runs of queued vertices with the same depth and their lengths
+  level_counts: VecDeque<(usize, usize)>,
+  predecessor_map: HashMap<V, Option<V>>,
+  distance_map: HashMap<V, usize>
 }
 
 impl<'a, G: Graph<V>, V: Vertex> BfsVertexTrav<'a, G, V> {
-  pub(crate) fn new(graph: &G, start: V) -> BfsVertexTrav<'_, G, V> {
+  pub(crate) fn new(graph: impl Into<GraphRef<'a, G>>, start: V) -> BfsVertexTrav<'a, G, V> {
     let mut container = BfsContainer::new();
     container.push(start.clone());
 
     BfsVertexTrav {
-      graph,
+      graph: graph.into(),
       start: start.clone(),
       queue: container,
-      predecessor_map: iter::once((start, None)).collect()
+      level_counts: iter::once((0, 1)).collect(),
+      predecessor_map: iter::once((start.clone(), None)).collect(),
+      distance_map: iter::once((start, 0)).collect()
+    }
+  }
+
+  /// Puts `vertex` at the end of the queue and updates the level counts.
+  fn push_queue(&mut self, vertex: V, depth: usize) {
+    match self.level_counts.back_mut() {
+      Some((level, count)) if *level == depth => *count += 1,
+      _ => self.level_counts.push_back((depth, 1))
+    }
+
+    self.queue.push(vertex);
+  }
+
+  /// Returns the number of queued vertices that have the same depth as the vertex the
+  /// traverser yields next, i.e. the size of the current frontier that has yet to be expanded,
+  /// or `0` if the queue is empty. Vertices discovered from the frontier one level deeper
+  /// are not counted until the frontier is exhausted.
+  pub fn frontier_size(&self) -> usize {
+    self.level_counts.front().map_or(0, |&(_, count)| count)
+  }
+
+  /// Returns the depth of the current [frontier](#method.frontier_size), or `None` if the
+  /// queue is empty.
+  pub fn frontier_depth(&self) -> Option<usize> {
+    self.level_counts.front().map(|&(depth, _)| depth)
+  }
+
+  /// Returns the number of edges on a shortest path from the start vertex to `vertex`,
+  /// or `None` if `vertex` has not been reached yet.
+  pub fn distance_to(&self, vertex: &V) -> Option<usize> {
+    self.distance_map.get(vertex).copied()
+  }
+
+  /// Returns an [`Iterator`](https://doc.rust-lang.org/std/iter/trait.Iterator.html) over the
+  /// traverser that yields each vertex together with its [distance](#method.distance_to).
+  pub fn iter_with_depth(&mut self) -> DepthIter<'_, 'a, G, V> {
+    DepthIter::new(self)
+  }
+
+  /// Returns the distances of all vertices reached so far.
+  pub fn all_distances(&self) -> &HashMap<V, usize> {
+    &self.distance_map
+  }
+
+  /// Returns `true` if all vertices reachable from the current start vertex have been visited.
+  pub fn exhausted_component(&self) -> bool {
+    self.queue.peek().is_none()
+  }
+
+  /// Continues the traversal from `new_start`, which becomes the new start vertex,
+  /// while keeping track of all vertices visited so far. Does nothing if `new_start`
+  /// has already been reached.
+  ///
+  /// Together with [`exhausted_component`](#method.exhausted_component), this can be used
+  /// to construct a breadth-first forest over multiple components. Afterwards,
+  /// [`first`](trait.VertexTraverser.html#tymethod.first) returns `new_start`, but the paths
+  /// of [`construct_path`](trait.VertexTraverser.html#method.construct_path) still start at
+  /// the start vertex of the component they were found in, which is not necessarily
+  /// `new_start`.
+  pub fn restart(&mut self, new_start: &V) {
+    if self.predecessor_map.contains_key(new_start) {
+      return;
     }
+
+    self.start = new_start.clone();
+    self.push_queue(new_start.clone(), 0);
+    self.predecessor_map.insert(new_start.clone(), None);
+    self.distance_map.insert(new_start.clone(), 0);
+  }
+}
+
+impl<G: Graph<V> + 'static, V: Vertex> BfsVertexTrav<'static, G, V> {
+  /// Constructs a breadth-first traverser that shares ownership of `graph`,
+  /// so it can be moved to other threads or tasks independently of the graph.
+  pub fn from_arc(graph: Arc<G>, start: &V) -> BfsVertexTrav<'static, G, V> {
+    BfsVertexTrav::new(graph, start.clone())
   }
 }
 
@@ -76,27 +247,109 @@ impl<'a, G: Graph<V>, V: Vertex> VertexTraverser<V> for BfsVertexTrav<'a, G, V>
     self.start.clone()
   }
 
+  fn reached(&self, vertex: &V) -> bool {
+    self.predecessor_map.contains_key(vertex)
+  }
+
   fn predecessor(&self, vertex: &V) -> Option<V> {
     self.predecessor_map.get(vertex)
     .and_then(|predecessor| predecessor.clone())
   }
 
   fn next(&mut self) -> Option<V> {
+    self.next_recorded(None)
+  }
+}
+
+impl<'a, G: Graph<V>, V: Vertex> BfsVertexTrav<'a, G, V> {
+  fn next_recorded(&mut self, mut recorder: Option<&mut TraversalRecorder<V, usize>>) -> Option<V> {
     let vertex = self.queue.pop();
 
-    vertex.map(|vertex| {
-      for neighbor in self.graph.neighbors(&vertex) {
+    vertex.inspect(|vertex| {
+      let distance = self.distance_map[vertex] + 1;
+
+      if let Some((_, count)) = self.level_counts.front_mut() {
+        *count -= 1;
+
+        if *count == 0 {
+          self.level_counts.pop_front();
+        }
+      }
+
+      if let Some(recorder) = recorder.as_deref_mut() {
+        recorder.record(TraceEvent::Pop { vertex: vertex.clone(), cost: distance - 1 });
+      }
+
+      for neighbor in self.graph.neighbors(vertex) {
+        if let Some(recorder) = recorder.as_deref_mut() {
+          recorder.record(TraceEvent::Relax {
+            from: vertex.clone(),
+            to: neighbor.clone(),
+            old_cost: self.distance_map.get(&neighbor).copied(),
+            new_cost: distance
+          });
+        }
+
         if self.predecessor_map.contains_key(&neighbor) {
           continue;
         }
 
-        self.queue.push(neighbor.clone());
+        if let Some(recorder) = recorder.as_deref_mut() {
+          recorder.record(TraceEvent::Push { vertex: neighbor.clone(), score: distance });
+        }
+
+        self.push_queue(neighbor.clone(), distance);
         self.predecessor_map.insert(neighbor.clone(), Some(vertex.clone()));
+        self.distance_map.insert(neighbor, distance);
       }
-
-      vertex
     })
   }
+
+  /// Converts the traverser into one that records every step into `recorder`, see
+  /// [`TraversalRecorder`](../struct.TraversalRecorder.html). Costs are distances.
+  pub fn with_recorder(self, recorder: &mut TraversalRecorder<V, usize>) -> RecordedBfsTrav<'a, '_, G, V> {
+    RecordedBfsTrav {
+      inner: self,
+      recorder
+    }
+  }
+}
+
+/// A breadth-first traverser that records its steps. See
+/// [`BfsVertexTrav::with_recorder`](struct.BfsVertexTrav.html#method.with_recorder).
+pub struct RecordedBfsTrav<'a, 'r, G, V> {
+  inner: BfsVertexTrav<'a, G, V>,
+  recorder: &'r mut TraversalRecorder<V, usize>
+}
+
+impl<'a, 'r, G: Graph<V>, V: Vertex> RecordedBfsTrav<'a, 'r, G, V> {
+  /// See [`BfsVertexTrav::distance_to`](struct.BfsVertexTrav.html#method.distance_to).
+  pub fn distance_to(&self, vertex: &V) -> Option<usize> {
+    self.inner.distance_to(vertex)
+  }
+
+  /// Stops recording and returns the traverser.
+  pub fn into_inner(self) -> BfsVertexTrav<'a, G, V> {
+    self.inner
+  }
+}
+
+impl<'a, 'r, G: Graph<V>, V: Vertex> VertexTraverser<V> for RecordedBfsTrav<'a, 'r, G, V> {
+  fn first(&self) -> V {
+    self.inner.first()
+  }
+
+  fn reached(&self, vertex: &V) -> bool {
+    self.inner.reached(vertex)
+  }
+
+  fn predecessor(&self, vertex: &V) -> Option<V> {
+    self.inner.predecessor(vertex)
+  }
+
+  fn next(&mut self) -> Option<V> {
+    self.inner.next_recorded(Some(self.recorder))
+  }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -105,6 +358,22 @@ pub enum PrePostItem<V> {
   PostorderItem(V)
 }
 
+/// A [`PrePostItem`](enum.PrePostItem.html) that additionally carries the value of the
+/// depth-first search clock, which increases by one on every preorder and postorder event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimedPrePostItem<V> {
+  PreorderItem(V, usize),
+  PostorderItem(V, usize)
+}
+
+/// A [`PrePostItem`](enum.PrePostItem.html) that additionally carries the parent of the vertex
+/// in the depth-first search tree, or `None` for the start vertex.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParentedPrePostItem<V> {
+  PreorderItem { vertex: V, parent: Option<V> },
+  PostorderItem { vertex: V, parent: Option<V> }
+}
+
 pub(crate) enum DfsInnerIterEvent<V> {
   PreorderItem(V),
   PostorderItem(V),
@@ -113,27 +382,43 @@ pub(crate) enum DfsInnerIterEvent<V> {
 
 #[derive(Clone)]
 pub struct DfsVertexTrav<'a, G, V> {
-  graph: &'a G,
+  graph: GraphRef<'a, G>,
   start: V,
   queue: DfsContainer<(V, Option<V>)>,
   predecessor_finished_map: HashMap<V, (Option<V>, bool)>,
-  reached_cycle: bool
+  reached_cycle: bool,
+  clock: usize,
+  pending_roots: Vec<V>
 }
 
 impl<'a, G: Graph<V>, V: Vertex> DfsVertexTrav<'a, G, V> {
-  pub(crate) fn new(graph: &G, start: V) -> DfsVertexTrav<'_, G, V> {
+  pub(crate) fn new(graph: impl Into<GraphRef<'a, G>>, start: V) -> DfsVertexTrav<'a, G, V> {
     let mut container = DfsContainer::new();
     container.push((start.clone(), None));
 
     DfsVertexTrav {
-      graph,
+      graph: graph.into(),
       start: start.clone(),
       queue: container,
       predecessor_finished_map: HashMap::new(),
-      reached_cycle: false
+      reached_cycle: false,
+      clock: 0,
+      pending_roots: vec![]
     }
   }
 
+  /// Constructs a traverser that visits all `roots` in the given order. Whenever it runs
+  /// out of vertices, it restarts from the next root that has not been visited yet.
+  pub(crate) fn with_roots(graph: impl Into<GraphRef<'a, G>>, start: V, mut roots: Vec<V>) -> DfsVertexTrav<'a, G, V> {
+    let mut result = DfsVertexTrav::new(graph, start);
+    result.queue.pop();
+
+    roots.reverse();
+    result.pending_roots = roots;
+
+    result
+  }
+
   pub(crate) fn next_inner(&mut self) -> Option<DfsInnerIterEvent<V>> {
     let vertex = loop {
       let item = self.queue.peek().cloned();
@@ -148,6 +433,7 @@ impl<'a, G: Graph<V>, V: Vertex> DfsVertexTrav<'a, G, V> {
             continue;
           } else if predecessor == &p {
             *finished = true;
+            self.clock += 1;
             return Some(DfsInnerIterEvent::PostorderItem(v.clone()));
           } else {
             self.reached_cycle = true;
@@ -158,7 +444,19 @@ impl<'a, G: Graph<V>, V: Vertex> DfsVertexTrav<'a, G, V> {
           predecessor_finished_map.insert(v.clone(), (p.clone(), false));
           break v.clone();
         },
-        (None, _) => return None
+        (None, _) => {
+          let root = loop {
+            match self.pending_roots.pop() {
+              Some(root) if predecessor_finished_map.contains_key(&root) => continue,
+              root => break root
+            }
+          };
+
+          match root {
+            Some(root) => self.queue.push((root, None)),
+            None => return None
+          }
+        }
       }
     };
 
@@ -166,10 +464,17 @@ impl<'a, G: Graph<V>, V: Vertex> DfsVertexTrav<'a, G, V> {
       self.queue.push((neighbor.clone(), Some(vertex.clone())));
     }
 
+    self.clock += 1;
     Some(DfsInnerIterEvent::PreorderItem(vertex))
   }
 
-  pub(crate) fn next_cycle<'s>(&'s mut self) -> Option<(V, V)> {
+  /// Returns the number of preorder and postorder events that have happened so far.
+  pub(crate) fn clock(&self) -> usize {
+    self.clock
+  }
+
+  #[allow(dead_code)]
+  pub(crate) fn next_cycle(&mut self) -> Option<(V, V)> {
     loop {
       match self.next_inner() {
         Some(DfsInnerIterEvent::CycleEdge(v, w)) => break Some((v, w)),
@@ -183,6 +488,20 @@ impl<'a, G: Graph<V>, V: Vertex> DfsVertexTrav<'a, G, V> {
     PrePostIter::new(self)
   }
 
+  /// Returns an [`Iterator`](https://doc.rust-lang.org/std/iter/trait.Iterator.html) over
+  /// preorder and postorder events together with the depth-first search clock value at which
+  /// they happen. Useful for algorithms that need discovery and finish times.
+  pub fn pre_post_iter_timed(&mut self) -> PrePostTimedIter<'_, 'a, G, V> {
+    PrePostTimedIter::new(self)
+  }
+
+  /// Returns an [`Iterator`](https://doc.rust-lang.org/std/iter/trait.Iterator.html) over
+  /// preorder and postorder events together with the parent of each vertex in the
+  /// depth-first search tree.
+  pub fn pre_post_iter_with_parents(&mut self) -> PrePostParentIter<'_, 'a, G, V> {
+    PrePostParentIter::new(self)
+  }
+
   pub fn post_iter(&mut self) -> PostIter<'_, 'a, G, V> {
     PostIter::new(self)
   }
@@ -192,11 +511,23 @@ impl<'a, G: Graph<V>, V: Vertex> DfsVertexTrav<'a, G, V> {
   }
 }
 
+impl<G: Graph<V> + 'static, V: Vertex> DfsVertexTrav<'static, G, V> {
+  /// Constructs a depth-first traverser that shares ownership of `graph`,
+  /// so it can be moved to other threads or tasks independently of the graph.
+  pub fn from_arc(graph: Arc<G>, start: &V) -> DfsVertexTrav<'static, G, V> {
+    DfsVertexTrav::new(graph, start.clone())
+  }
+}
+
 impl<'a, G: Graph<V>, V: Vertex> VertexTraverser<V> for DfsVertexTrav<'a, G, V> {
   fn first(&self) -> V {
     self.start.clone()
   }
 
+  fn reached(&self, vertex: &V) -> bool {
+    self.predecessor_finished_map.contains_key(vertex)
+  }
+
   fn predecessor(&self, vertex: &V) -> Option<V> {
     self.predecessor_finished_map.get(vertex)
     .and_then(|(predecessor, _)| predecessor.clone())
@@ -215,11 +546,12 @@ impl<'a, G: Graph<V>, V: Vertex> VertexTraverser<V> for DfsVertexTrav<'a, G, V>
 
 #[derive(Clone)]
 pub struct AstarVertexTrav<'a, G, V, E, F> {
-  graph: &'a G,
+  graph: GraphRef<'a, G>,
   start: V,
   queue: AstarContainer<(V, E), E>,
   predecessor_map: HashMap<V, Option<V>>,
   min_edge_map: HashMap<V, E>,
+  settled_set: HashSet<V>,
   estimator: Option<F>
 }
 
@@ -230,26 +562,103 @@ where
   E: WeightedEdge,
   F: Fn(&V) -> E
 {
-  pub(crate) fn new(graph: &G, start: V) -> AstarVertexTrav<'_, G, V, E, F> {
+  pub(crate) fn new(graph: impl Into<GraphRef<'a, G>>, start: V) -> AstarVertexTrav<'a, G, V, E, F> {
     let mut container = AstarContainer::new();
     container.push((start.clone(), E::default()), E::default());
 
     AstarVertexTrav {
-      graph,
+      graph: graph.into(),
       start: start.clone(),
       queue: container,
       predecessor_map: iter::once((start.clone(), None)).collect(),
       min_edge_map: iter::once((start, E::default())).collect(),
+      settled_set: HashSet::new(),
       estimator: None
     }
   }
 
-  pub(crate) fn with_estimator(graph: &G, start: V, estimator: F) -> AstarVertexTrav<'_, G, V, E, F> {
+  pub(crate) fn with_estimator(
+    graph: impl Into<GraphRef<'a, G>>,
+    start: V,
+    estimator: F
+  ) -> AstarVertexTrav<'a, G, V, E, F> {
     let mut result = AstarVertexTrav::new(graph, start);
     result.estimator = Some(estimator);
 
     result
   }
+
+  /// Returns the weight sum of a smallest path from the start vertex to `vertex`,
+  /// or `None` if `vertex` has not been visited yet.
+  pub fn cost_to(&self, vertex: &V) -> Option<&E> {
+    if self.settled_set.contains(vertex) {
+      self.min_edge_map.get(vertex)
+    } else {
+      None
+    }
+  }
+
+  /// Returns an [`Iterator`](https://doc.rust-lang.org/std/iter/trait.Iterator.html) over the
+  /// traverser that yields each vertex together with its final [cost](#method.cost_to).
+  /// Costs are yielded in ascending order if the traverser has no estimator.
+  pub fn iter_with_cost(&mut self) -> CostIter<'_, 'a, G, V, E, F> {
+    CostIter::new(self)
+  }
+}
+
+impl<'a, G, V, E> AstarVertexTrav<'a, G, V, E, fn(&V) -> E>
+where
+  G: EdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge
+{
+  /// Constructs a Dijkstra traverser that is warm-started with known costs of vertices,
+  /// e.g. from a previous traversal. The vertices in `costs` are treated as additional
+  /// roots reached at their given costs, so they have no predecessor. The cost of the start
+  /// vertex is always `E::default()`.
+  pub fn with_initial_costs(graph: &'a G, start: &V, costs: HashMap<V, E>) -> AstarVertexTrav<'a, G, V, E, fn(&V) -> E> {
+    let mut result = AstarVertexTrav::new(graph, start.clone());
+
+    for (vertex, cost) in costs {
+      if &vertex == start {
+        continue;
+      }
+
+      result.queue.push((vertex.clone(), cost.clone()), cost.clone());
+      result.predecessor_map.insert(vertex.clone(), None);
+      result.min_edge_map.insert(vertex, cost);
+    }
+
+    result
+  }
+}
+
+impl<G, V, E> AstarVertexTrav<'static, G, V, E, fn(&V) -> E>
+where
+  G: EdgedGraph<V, E> + 'static,
+  V: Vertex,
+  E: WeightedEdge
+{
+  /// Constructs a Dijkstra traverser that shares ownership of `graph`,
+  /// so it can be moved to other threads or tasks independently of the graph.
+  pub fn from_arc(graph: Arc<G>, start: &V) -> AstarVertexTrav<'static, G, V, E, fn(&V) -> E> {
+    AstarVertexTrav::new(graph, start.clone())
+  }
+}
+
+impl<G, V, E, F> AstarVertexTrav<'static, G, V, E, F>
+where
+  G: EdgedGraph<V, E> + 'static,
+  V: Vertex,
+  E: WeightedEdge,
+  F: Fn(&V) -> E
+{
+  /// Constructs an A* traverser with the given estimator function that shares
+  /// ownership of `graph`, so it can be moved to other threads or tasks independently
+  /// of the graph.
+  pub fn from_arc_with_estimator(graph: Arc<G>, start: &V, estimator: F) -> AstarVertexTrav<'static, G, V, E, F> {
+    AstarVertexTrav::with_estimator(graph, start.clone(), estimator)
+  }
 }
 
 impl<'a, G, V, E, F> VertexTraverser<V> for AstarVertexTrav<'a, G, V, E, F>
@@ -263,49 +672,468 @@ where
     self.start.clone()
   }
 
+  fn reached(&self, vertex: &V) -> bool {
+    self.settled_set.contains(vertex)
+  }
+
   fn predecessor(&self, vertex: &V) -> Option<V> {
     self.predecessor_map.get(vertex)
     .and_then(|predecessor| predecessor.clone())
   }
 
   fn next(&mut self) -> Option<V> {
-    let vertex_edge = self.queue.pop();
+    self.next_recorded(None)
+  }
+}
 
-    vertex_edge.map(|((vertex, edge), _)| {
-      for neighbor in self.graph.neighbors(&vertex) {
-        let outgoing_edge = self.graph
-          .edges(&vertex, &neighbor)
-          .into_iter()
-          .min();
-
-        if let Some(outgoing_edge) = outgoing_edge {
-          let new_edge = edge.clone() + outgoing_edge;
-          let mut edge_shorter = false;
-
-          if let Some(min_edge) = self.min_edge_map.get_mut(&neighbor) {
-            if &new_edge < min_edge {
-              *min_edge = new_edge.clone();
-              edge_shorter = true;
-            }
-          } else {
-            self.min_edge_map.insert(neighbor.clone(), new_edge.clone());
-            edge_shorter = true;
+impl<'a, G, V, E, F> AstarVertexTrav<'a, G, V, E, F>
+where
+  G: EdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge,
+  F: Fn(&V) -> E
+{
+  fn next_recorded(&mut self, mut recorder: Option<&mut TraversalRecorder<V, E>>) -> Option<V> {
+    let vertex_edge = loop {
+      match self.queue.pop() {
+        // Skip stale entries of vertices that have been reached with a lower cost since
+        Some(((vertex, edge), _)) if edge > self.min_edge_map[&vertex] => {
+          if let Some(recorder) = recorder.as_deref_mut() {
+            recorder.record(TraceEvent::Skip { vertex, cost: edge });
           }
+        },
+        item => break item
+      }
+    };
+
+    vertex_edge.map(|((vertex, edge), _)| {
+      if let Some(recorder) = recorder.as_deref_mut() {
+        recorder.record(TraceEvent::Pop { vertex: vertex.clone(), cost: edge.clone() });
+      }
 
-          if edge_shorter {
-            let mut score = new_edge.clone();
+      for (neighbor, new_edge, score) in self.settle(&vertex, edge, recorder.as_deref_mut()) {
+        if let Some(recorder) = recorder.as_deref_mut() {
+          recorder.record(TraceEvent::Push { vertex: neighbor.clone(), score: score.clone() });
+        }
 
-            if let Some(estimator) = self.estimator.as_ref() {
-              score = score + estimator(&neighbor);
-            }
+        self.queue.push((neighbor, new_edge), score);
+      }
 
-            self.queue.push((neighbor.clone(), new_edge), score);
-            self.predecessor_map.insert(neighbor, Some(vertex.clone()));
-          }
+      vertex
+    })
+  }
+
+  /// Marks `vertex` as settled with the weight sum `edge` and relaxes its outgoing edges.
+  /// Returns all neighbors whose weight sum has been lowered, together with their new weight
+  /// sum and score, which still need to be pushed onto the queue.
+  fn settle(&mut self, vertex: &V, edge: E, mut recorder: Option<&mut TraversalRecorder<V, E>>) -> Vec<(V, E, E)> {
+    self.settled_set.insert(vertex.clone());
+
+    let mut outgoing_edges = Vec::<(V, E)>::new();
+    let mut index_map = HashMap::<V, usize>::new();
+
+    for (neighbor, outgoing_edge) in self.graph.neighbors_with_edges(vertex) {
+      if let Some(&index) = index_map.get(&neighbor) {
+        if outgoing_edge < outgoing_edges[index].1 {
+          outgoing_edges[index].1 = outgoing_edge;
         }
+      } else {
+        index_map.insert(neighbor.clone(), outgoing_edges.len());
+        outgoing_edges.push((neighbor, outgoing_edge));
+      }
+    }
+
+    let mut result = vec![];
+
+    for (neighbor, outgoing_edge) in outgoing_edges {
+      let new_edge = edge.clone() + outgoing_edge;
+      let mut edge_shorter = false;
+
+      if let Some(recorder) = recorder.as_deref_mut() {
+        recorder.record(TraceEvent::Relax {
+          from: vertex.clone(),
+          to: neighbor.clone(),
+          old_cost: self.min_edge_map.get(&neighbor).cloned(),
+          new_cost: new_edge.clone()
+        });
+      }
+
+      if let Some(min_edge) = self.min_edge_map.get_mut(&neighbor) {
+        if &new_edge < min_edge {
+          *min_edge = new_edge.clone();
+          edge_shorter = true;
+        }
+      } else {
+        self.min_edge_map.insert(neighbor.clone(), new_edge.clone());
+        edge_shorter = true;
+      }
+
+      if edge_shorter {
+        let mut score = new_edge.clone();
+
+        if let Some(estimator) = self.estimator.as_ref() {
+          score = score + estimator(&neighbor);
+        }
+
+        self.predecessor_map.insert(neighbor.clone(), Some(vertex.clone()));
+        result.push((neighbor, new_edge, score));
+      }
+    }
+
+    result
+  }
+
+  /// Converts the traverser into one that breaks ties between vertices with equal scores by
+  /// the smallest `tiebreaker` value, independent of the order in which they were discovered.
+  /// Vertices with equal scores and tiebreaker values are visited in discovery order.
+  ///
+  /// This is useful for reproducible output and canonical paths, e.g. a tiebreaker returning
+  /// the vertex itself prefers lexicographically smaller vertices.
+  pub fn with_tiebreaker<T, H>(self, tiebreaker: H) -> AstarWithTiebreaker<'a, G, V, E, F, T, H>
+  where T: Ord, H: Fn(&V) -> T {
+    AstarWithTiebreaker::new(self, tiebreaker)
+  }
+
+  /// Converts the traverser into one that records every step into `recorder`, see
+  /// [`TraversalRecorder`](../struct.TraversalRecorder.html). Parallel edges are relaxed
+  /// once, with the lightest edge.
+  pub fn with_recorder(self, recorder: &mut TraversalRecorder<V, E>) -> RecordedAstarTrav<'a, '_, G, V, E, F> {
+    RecordedAstarTrav {
+      inner: self,
+      recorder
+    }
+  }
+}
+
+/// An A* traverser that records its steps. See
+/// [`AstarVertexTrav::with_recorder`](struct.AstarVertexTrav.html#method.with_recorder).
+pub struct RecordedAstarTrav<'a, 'r, G, V, E, F> {
+  inner: AstarVertexTrav<'a, G, V, E, F>,
+  recorder: &'r mut TraversalRecorder<V, E>
+}
+
+impl<'a, 'r, G, V, E, F> RecordedAstarTrav<'a, 'r, G, V, E, F>
+where
+  G: EdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge,
+  F: Fn(&V) -> E
+{
+  /// See [`AstarVertexTrav::cost_to`](struct.AstarVertexTrav.html#method.cost_to).
+  pub fn cost_to(&self, vertex: &V) -> Option<&E> {
+    self.inner.cost_to(vertex)
+  }
+
+  /// Stops recording and returns the traverser.
+  pub fn into_inner(self) -> AstarVertexTrav<'a, G, V, E, F> {
+    self.inner
+  }
+}
+
+impl<'a, 'r, G, V, E, F> VertexTraverser<V> for RecordedAstarTrav<'a, 'r, G, V, E, F>
+where
+  G: EdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge,
+  F: Fn(&V) -> E
+{
+  fn first(&self) -> V {
+    self.inner.first()
+  }
+
+  fn reached(&self, vertex: &V) -> bool {
+    self.inner.reached(vertex)
+  }
+
+  fn predecessor(&self, vertex: &V) -> Option<V> {
+    self.inner.predecessor(vertex)
+  }
+
+  fn next(&mut self) -> Option<V> {
+    self.inner.next_recorded(Some(self.recorder))
+  }
+}
+
+/// An A* traverser that breaks ties between equal scores with a secondary key. See
+/// [`AstarVertexTrav::with_tiebreaker`](struct.AstarVertexTrav.html#method.with_tiebreaker).
+#[derive(Clone)]
+pub struct AstarWithTiebreaker<'a, G, V, E, F, T, H> {
+  inner: AstarVertexTrav<'a, G, V, E, F>,
+  queue: AstarContainer<(V, E), (E, T)>,
+  tiebreaker: H
+}
+
+impl<'a, G, V, E, F, T, H> AstarWithTiebreaker<'a, G, V, E, F, T, H>
+where
+  G: EdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge,
+  F: Fn(&V) -> E,
+  T: Ord,
+  H: Fn(&V) -> T
+{
+  fn new(mut inner: AstarVertexTrav<'a, G, V, E, F>, tiebreaker: H) -> AstarWithTiebreaker<'a, G, V, E, F, T, H> {
+    let mut queue = AstarContainer::new();
+
+    while let Some(((vertex, edge), score)) = inner.queue.pop() {
+      let key = tiebreaker(&vertex);
+      queue.push((vertex, edge), (score, key));
+    }
+
+    AstarWithTiebreaker {
+      inner,
+      queue,
+      tiebreaker
+    }
+  }
+
+  /// Returns the weight sum of a smallest path from the start vertex to `vertex`,
+  /// or `None` if `vertex` has not been visited yet.
+  pub fn cost_to(&self, vertex: &V) -> Option<&E> {
+    self.inner.cost_to(vertex)
+  }
+}
+
+impl<'a, G, V, E, F, T, H> VertexTraverser<V> for AstarWithTiebreaker<'a, G, V, E, F, T, H>
+where
+  G: EdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge,
+  F: Fn(&V) -> E,
+  T: Ord,
+  H: Fn(&V) -> T
+{
+  fn first(&self) -> V {
+    self.inner.first()
+  }
+
+  fn reached(&self, vertex: &V) -> bool {
+    self.inner.reached(vertex)
+  }
+
+  fn predecessor(&self, vertex: &V) -> Option<V> {
+    self.inner.predecessor(vertex)
+  }
+
+  fn next(&mut self) -> Option<V> {
+    let vertex_edge = loop {
+      match self.queue.pop() {
+        Some(((vertex, edge), _)) if edge > self.inner.min_edge_map[&vertex] => continue,
+        item => break item
+      }
+    };
+
+    vertex_edge.map(|((vertex, edge), _)| {
+      for (neighbor, new_edge, score) in self.inner.settle(&vertex, edge, None) {
+        let key = (self.tiebreaker)(&neighbor);
+        self.queue.push((neighbor, new_edge), (score, key));
       }
 
       vertex
     })
   }
 }
+
+/// The fallible counterpart of [`VertexTraverser`](trait.VertexTraverser.html) for traversers
+/// over a [`TryGraph`](../trait.TryGraph.html).
+///
+/// If expanding a vertex fails, the error is returned and the traverser state stays as it was
+/// before, so calling [`try_next`](#tymethod.try_next) again retries expanding the same vertex.
+pub trait TryVertexTraverser<V: Vertex> where Self: Sized {
+  type Error;
+
+  /// Returns start vertex.
+  fn first(&self) -> V;
+
+  /// Tries to advance the traverser and returns the next value.
+  fn try_next(&mut self) -> Result<Option<V>, Self::Error>;
+
+  /// Returns the predecessor vertex of the given vertex
+  /// or `None` if `vertex` is the start vertex or has not been reached yet.
+  fn predecessor(&self, vertex: &V) -> Option<V>;
+
+  /// Returns `true` if `vertex` has been reached and its predecessor chain back to the
+  /// start vertex will not change anymore by advancing the traverser.
+  fn reached(&self, vertex: &V) -> bool;
+
+  /// Tries to traverse through the graph until we reach `target` and returns a path from start
+  /// vertex to `target`, or `None` if the `target` vertex cannot be reached. See
+  /// [`VertexTraverser::construct_path`](trait.VertexTraverser.html#method.construct_path).
+  fn try_construct_path(&mut self, target: &V) -> Result<Option<Vec<V>>, Self::Error> {
+    while !self.reached(target) {
+      if self.try_next()?.is_none() {
+        return Ok(None);
+      }
+    }
+
+    let mut path = vec![target.clone()];
+
+    while let Some(predecessor) = self.predecessor(path.last().unwrap()) {
+      path.push(predecessor);
+    }
+
+    path.reverse();
+    Ok(Some(path))
+  }
+}
+
+pub struct TryBfsVertexTrav<'a, G, V> {
+  graph: &'a G,
+  start: V,
+  queue: VecDeque<V>,
+  predecessor_map: HashMap<V, Option<V>>,
+  distance_map: HashMap<V, usize>
+}
+
+impl<'a, G: TryGraph<V>, V: Vertex> TryBfsVertexTrav<'a, G, V> {
+  pub(crate) fn new(graph: &'a G, start: V) -> TryBfsVertexTrav<'a, G, V> {
+    TryBfsVertexTrav {
+      graph,
+      start: start.clone(),
+      queue: iter::once(start.clone()).collect(),
+      predecessor_map: iter::once((start.clone(), None)).collect(),
+      distance_map: iter::once((start, 0)).collect()
+    }
+  }
+
+  /// Returns the number of edges on a shortest path from the start vertex to `vertex`,
+  /// or `None` if `vertex` has not been reached yet.
+  pub fn distance_to(&self, vertex: &V) -> Option<usize> {
+    self.distance_map.get(vertex).copied()
+  }
+}
+
+impl<'a, G: TryGraph<V>, V: Vertex> TryVertexTraverser<V> for TryBfsVertexTrav<'a, G, V> {
+  type Error = G::Error;
+
+  fn first(&self) -> V {
+    self.start.clone()
+  }
+
+  fn reached(&self, vertex: &V) -> bool {
+    self.predecessor_map.contains_key(vertex)
+  }
+
+  fn predecessor(&self, vertex: &V) -> Option<V> {
+    self.predecessor_map.get(vertex)
+    .and_then(|predecessor| predecessor.clone())
+  }
+
+  fn try_next(&mut self) -> Result<Option<V>, G::Error> {
+    let vertex = match self.queue.front() {
+      Some(vertex) => vertex.clone(),
+      None => return Ok(None)
+    };
+
+    // Leave the vertex in the queue until its neighbors have been generated successfully
+    let neighbors = self.graph.try_neighbors(&vertex)?;
+    let distance = self.distance_map[&vertex] + 1;
+
+    self.queue.pop_front();
+
+    for neighbor in neighbors {
+      if self.predecessor_map.contains_key(&neighbor) {
+        continue;
+      }
+
+      self.queue.push_back(neighbor.clone());
+      self.predecessor_map.insert(neighbor.clone(), Some(vertex.clone()));
+      self.distance_map.insert(neighbor, distance);
+    }
+
+    Ok(Some(vertex))
+  }
+}
+
+pub struct TryDijkstraVertexTrav<'a, G, V, E> {
+  graph: &'a G,
+  start: V,
+  queue: AstarContainer<(V, E), E>,
+  predecessor_map: HashMap<V, Option<V>>,
+  min_edge_map: HashMap<V, E>,
+  settled_set: HashSet<V>
+}
+
+impl<'a, G, V, E> TryDijkstraVertexTrav<'a, G, V, E>
+where
+  G: TryEdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge
+{
+  pub(crate) fn new(graph: &'a G, start: V) -> TryDijkstraVertexTrav<'a, G, V, E> {
+    let mut container = AstarContainer::new();
+    container.push((start.clone(), E::default()), E::default());
+
+    TryDijkstraVertexTrav {
+      graph,
+      start: start.clone(),
+      queue: container,
+      predecessor_map: iter::once((start.clone(), None)).collect(),
+      min_edge_map: iter::once((start, E::default())).collect(),
+      settled_set: HashSet::new()
+    }
+  }
+
+  /// Returns the weight sum of a smallest path from the start vertex to `vertex`,
+  /// or `None` if `vertex` has not been visited yet.
+  pub fn cost_to(&self, vertex: &V) -> Option<&E> {
+    if self.settled_set.contains(vertex) {
+      self.min_edge_map.get(vertex)
+    } else {
+      None
+    }
+  }
+}
+
+impl<'a, G, V, E> TryVertexTraverser<V> for TryDijkstraVertexTrav<'a, G, V, E>
+where
+  G: TryEdgedGraph<V, E>,
+  V: Vertex,
+  E: WeightedEdge
+{
+  type Error = G::Error;
+
+  fn first(&self) -> V {
+    self.start.clone()
+  }
+
+  fn reached(&self, vertex: &V) -> bool {
+    self.settled_set.contains(vertex)
+  }
+
+  fn predecessor(&self, vertex: &V) -> Option<V> {
+    self.predecessor_map.get(vertex)
+    .and_then(|predecessor| predecessor.clone())
+  }
+
+  fn try_next(&mut self) -> Result<Option<V>, G::Error> {
+    let (vertex, edge) = loop {
+      match self.queue.peek() {
+        // Skip stale entries of vertices that have been reached with a lower cost since
+        Some(((vertex, edge), _)) if edge > &self.min_edge_map[vertex] => {
+          self.queue.pop();
+        },
+        Some(((vertex, edge), _)) => break (vertex.clone(), edge.clone()),
+        None => return Ok(None)
+      }
+    };
+
+    // Leave the vertex in the queue until its neighbors have been generated successfully
+    let outgoing_edges = self.graph.try_neighbors_with_edges(&vertex)?;
+
+    self.queue.pop();
+    self.settled_set.insert(vertex.clone());
+
+    for (neighbor, outgoing_edge) in outgoing_edges {
+      let new_edge = edge.clone() + outgoing_edge;
+
+      if self.min_edge_map.get(&neighbor).is_none_or(|min_edge| &new_edge < min_edge) {
+        self.min_edge_map.insert(neighbor.clone(), new_edge.clone());
+        self.queue.push((neighbor.clone(), new_edge.clone()), new_edge);
+        self.predecessor_map.insert(neighbor, Some(vertex.clone()));
+      }
+    }
+
+    Ok(Some(vertex))
+  }
+}